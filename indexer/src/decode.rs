@@ -0,0 +1,157 @@
+//! Decoding of contract events from XDR.
+//!
+//! Events are published with the topics `(category, action)` or
+//! `(category, action, subject)` and a `ContractEvent` as data. The subject
+//! is a remittance, batch, cycle or quote ID (`u64`), a migration batch
+//! number (`u32`) or the affected address.
+
+use std::fmt;
+
+use soroban_sdk::xdr::{
+    self, ContractEventBody, ContractEventType, ContractEventV0, Limits, ReadXdr, ScAddress, ScVal,
+};
+use soroban_sdk::{Address, Env, Symbol, TryFromVal, Val};
+use swiftremit::{ContractEvent, EventPayload};
+
+/// Third topic of an event.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Subject {
+    /// Remittance, batch, cycle or quote ID
+    Id(u64),
+    /// Migration batch number
+    Number(u32),
+    /// Affected address
+    Address(Address),
+}
+
+/// Contract event decoded into the contract's event types.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DecodedEvent {
+    /// Contract that emitted the event
+    pub contract: Address,
+    /// First topic, such as `remit` or `config`
+    pub category: String,
+    /// Second topic, such as `created` or `fx`
+    pub action: String,
+    /// Third topic, if the event has one
+    pub subject: Option<Subject>,
+    /// Envelope and payload
+    pub event: ContractEvent,
+}
+
+impl DecodedEvent {
+    /// Returns the per-contract sequence number of the event.
+    pub fn sequence(&self) -> u64 {
+        self.event.envelope.sequence
+    }
+
+    /// Returns the payload of the event.
+    pub fn payload(&self) -> &EventPayload {
+        &self.event.payload
+    }
+}
+
+/// Errors returned when decoding an event.
+#[derive(Debug)]
+pub enum DecodeError {
+    /// Event is a system or diagnostic event, or has no contract ID
+    NotContractEvent,
+    /// Topics are not `(category, action)` or `(category, action, subject)`
+    InvalidTopics,
+    /// Data is not a `ContractEvent`
+    InvalidData,
+    /// Input is not valid XDR
+    Xdr(xdr::Error),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::NotContractEvent => write!(f, "not a contract event"),
+            DecodeError::InvalidTopics => write!(f, "unexpected event topics"),
+            DecodeError::InvalidData => write!(f, "event data is not a ContractEvent"),
+            DecodeError::Xdr(err) => write!(f, "invalid XDR: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+impl From<xdr::Error> for DecodeError {
+    fn from(err: xdr::Error) -> Self {
+        DecodeError::Xdr(err)
+    }
+}
+
+/// Decodes an XDR `ContractEvent`.
+pub fn decode_event(env: &Env, event: &xdr::ContractEvent) -> Result<DecodedEvent, DecodeError> {
+    if event.type_ != ContractEventType::Contract {
+        return Err(DecodeError::NotContractEvent);
+    }
+    let hash = event
+        .contract_id
+        .clone()
+        .ok_or(DecodeError::NotContractEvent)?;
+    let contract = Address::try_from_val(env, &ScAddress::Contract(hash))
+        .map_err(|_| DecodeError::NotContractEvent)?;
+
+    let ContractEventBody::V0(ContractEventV0 { topics, data }) = &event.body;
+    decode_parts(env, &contract, topics, data)
+}
+
+/// Decodes a `ContractEvent` from its XDR bytes.
+pub fn decode_event_xdr(env: &Env, bytes: &[u8]) -> Result<DecodedEvent, DecodeError> {
+    let event = xdr::ContractEvent::from_xdr(bytes, Limits::none())?;
+    decode_event(env, &event)
+}
+
+/// Decodes a `ContractEvent` from base64-encoded XDR.
+pub fn decode_event_base64(env: &Env, encoded: &str) -> Result<DecodedEvent, DecodeError> {
+    let event = xdr::ContractEvent::from_xdr_base64(encoded, Limits::none())?;
+    decode_event(env, &event)
+}
+
+/// Decodes the topics and data of an event emitted by `contract`.
+///
+/// For sources that deliver topics and data separately, such as RPC
+/// `getEvents`, after decoding each with `ScVal::from_xdr_base64`.
+pub fn decode_parts(
+    env: &Env,
+    contract: &Address,
+    topics: &[ScVal],
+    data: &ScVal,
+) -> Result<DecodedEvent, DecodeError> {
+    let (category, action, subject) = match topics {
+        [category, action] => (category, action, None),
+        [category, action, subject] => (category, action, Some(decode_subject(env, subject)?)),
+        _ => return Err(DecodeError::InvalidTopics),
+    };
+
+    let data = Val::try_from_val(env, data).map_err(|_| DecodeError::InvalidData)?;
+    let event = ContractEvent::try_from_val(env, &data).map_err(|_| DecodeError::InvalidData)?;
+
+    Ok(DecodedEvent {
+        contract: contract.clone(),
+        category: decode_symbol(env, category)?,
+        action: decode_symbol(env, action)?,
+        subject,
+        event,
+    })
+}
+
+fn decode_symbol(env: &Env, topic: &ScVal) -> Result<String, DecodeError> {
+    let val = Val::try_from_val(env, topic).map_err(|_| DecodeError::InvalidTopics)?;
+    let symbol = Symbol::try_from_val(env, &val).map_err(|_| DecodeError::InvalidTopics)?;
+    Ok(symbol.to_string())
+}
+
+fn decode_subject(env: &Env, topic: &ScVal) -> Result<Subject, DecodeError> {
+    match topic {
+        ScVal::U64(id) => Ok(Subject::Id(*id)),
+        ScVal::U32(number) => Ok(Subject::Number(*number)),
+        ScVal::Address(address) => Address::try_from_val(env, address)
+            .map(Subject::Address)
+            .map_err(|_| DecodeError::InvalidTopics),
+        _ => Err(DecodeError::InvalidTopics),
+    }
+}
@@ -0,0 +1,374 @@
+//! Remittance state rebuilt from an event stream.
+//!
+//! The indexer applies events in sequence order. Remittance events update
+//! the remittance they name; all other events only advance the sequence.
+//! An event that fails to apply leaves the indexer unchanged.
+//!
+//! Skipped sequence numbers are kept as open gaps. An event from an open gap
+//! is applied when it arrives, and the gap shrinks until it is closed. Such
+//! an event is older than the events applied around it, so a state only
+//! replaces one earlier in the lifecycle, and a memo or batch membership
+//! only replaces an older one.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use soroban_sdk::{Address, BytesN};
+use swiftremit::{EventPayload, EVENT_SCHEMA_VERSION};
+
+use crate::DecodedEvent;
+
+/// Lifecycle state of an indexed remittance, in lifecycle order.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RemittanceState {
+    /// Created and awaiting settlement
+    Pending,
+    /// Paid out to the agent
+    Settled,
+    /// Cancelled and refunded to the sender
+    Cancelled,
+    /// Closed by an admin after settlement
+    Finalized,
+}
+
+impl RemittanceState {
+    fn stage(self) -> u8 {
+        match self {
+            RemittanceState::Pending => 0,
+            RemittanceState::Settled | RemittanceState::Cancelled => 1,
+            RemittanceState::Finalized => 2,
+        }
+    }
+}
+
+/// Remittance as rebuilt from events.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IndexedRemittance {
+    /// Remittance ID
+    pub id: u64,
+    /// Sender address
+    pub sender: Address,
+    /// Agent address
+    pub agent: Address,
+    /// Amount sent, including fees
+    pub amount: i128,
+    /// Platform fee
+    pub fee: i128,
+    /// Integrator fee
+    pub integrator_fee: i128,
+    /// Lifecycle state
+    pub state: RemittanceState,
+    /// Memo, if one was set
+    pub memo: Option<BytesN<32>>,
+    /// Sequence number of the event that set the memo
+    pub memo_sequence: u64,
+    /// Settlement cycle the remittance was queued in
+    pub cycle_id: Option<u64>,
+    /// Settlement batch the remittance is locked in
+    pub batch_id: Option<u64>,
+    /// Sequence number of the event that last changed the batch membership
+    pub batch_sequence: u64,
+    /// Settlement ID, once settled
+    pub settlement_id: Option<BytesN<32>>,
+    /// Amount paid out to the agent, once settled
+    pub payout_amount: Option<i128>,
+    /// Receipt hash, once issued
+    pub receipt_hash: Option<BytesN<32>>,
+    /// Travel-rule commitment made at creation
+    pub travel_rule_commitment: Option<BytesN<32>>,
+    /// Travel-rule acknowledgement from the beneficiary VASP
+    pub travel_rule_ack: Option<BytesN<32>>,
+    /// Sequence number of the last event that changed the remittance
+    pub last_sequence: u64,
+}
+
+impl IndexedRemittance {
+    fn advance(&mut self, state: RemittanceState) {
+        if state.stage() > self.state.stage() {
+            self.state = state;
+        }
+    }
+
+    fn set_batch(&mut self, batch_id: Option<u64>, sequence: u64) {
+        if sequence > self.batch_sequence {
+            self.batch_id = batch_id;
+            self.batch_sequence = sequence;
+        }
+    }
+}
+
+/// Range of sequence numbers not yet received, inclusive.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Gap {
+    /// First missing sequence number
+    pub first_missing: u64,
+    /// Last missing sequence number
+    pub last_missing: u64,
+}
+
+/// Outcome of applying an event.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Applied {
+    /// Event followed the previous one, or came from an open gap, and was
+    /// applied
+    Applied,
+    /// Event was applied before and was ignored
+    Duplicate,
+    /// Event was applied, but the events in the gap before it are missing
+    AfterGap(Gap),
+}
+
+/// Errors returned when applying an event.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum IndexError {
+    /// Event uses an event schema version this crate does not decode
+    UnsupportedSchema(u32),
+    /// Event names a remittance whose creation was not indexed
+    UnknownRemittance(u64),
+}
+
+impl fmt::Display for IndexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IndexError::UnsupportedSchema(version) => {
+                write!(f, "unsupported event schema version {version}")
+            }
+            IndexError::UnknownRemittance(id) => write!(f, "unknown remittance {id}"),
+        }
+    }
+}
+
+impl std::error::Error for IndexError {}
+
+/// Remittance state rebuilt from the events of one contract.
+#[derive(Clone, Debug, Default)]
+pub struct Indexer {
+    last_sequence: u64,
+    remittances: BTreeMap<u64, IndexedRemittance>,
+    gaps: Vec<Gap>,
+}
+
+impl Indexer {
+    /// Creates an indexer expecting the stream to start at sequence 1.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an indexer resuming after `last_sequence` with previously
+    /// indexed remittances and the gaps that were still open.
+    pub fn resume(
+        last_sequence: u64,
+        remittances: impl IntoIterator<Item = IndexedRemittance>,
+        gaps: impl IntoIterator<Item = Gap>,
+    ) -> Self {
+        let mut gaps: Vec<Gap> = gaps.into_iter().collect();
+        gaps.sort_by_key(|gap| gap.first_missing);
+        Self {
+            last_sequence,
+            remittances: remittances.into_iter().map(|r| (r.id, r)).collect(),
+            gaps,
+        }
+    }
+
+    /// Returns the sequence number of the last applied event.
+    pub fn last_sequence(&self) -> u64 {
+        self.last_sequence
+    }
+
+    /// Returns an indexed remittance.
+    pub fn remittance(&self, id: u64) -> Option<&IndexedRemittance> {
+        self.remittances.get(&id)
+    }
+
+    /// Returns all indexed remittances in ID order.
+    pub fn remittances(&self) -> impl Iterator<Item = &IndexedRemittance> {
+        self.remittances.values()
+    }
+
+    /// Returns the gaps that are still open, in stream order.
+    pub fn gaps(&self) -> &[Gap] {
+        &self.gaps
+    }
+
+    /// Applies an event.
+    ///
+    /// # Errors
+    ///
+    /// * `UnsupportedSchema` - The event uses a different schema version
+    /// * `UnknownRemittance` - The event names a remittance not yet indexed,
+    ///   typically because its creation is in a gap
+    pub fn apply(&mut self, event: &DecodedEvent) -> Result<Applied, IndexError> {
+        let envelope = &event.event.envelope;
+        if envelope.schema_version != EVENT_SCHEMA_VERSION {
+            return Err(IndexError::UnsupportedSchema(envelope.schema_version));
+        }
+
+        let sequence = envelope.sequence;
+        if sequence <= self.last_sequence {
+            let Some(index) = self.open_gap(sequence) else {
+                return Ok(Applied::Duplicate);
+            };
+            self.check_known(event.payload())?;
+            self.apply_payload(event.payload(), sequence);
+            self.fill_gap(index, sequence);
+            return Ok(Applied::Applied);
+        }
+        self.check_known(event.payload())?;
+
+        self.apply_payload(event.payload(), sequence);
+        let expected = self.last_sequence + 1;
+        self.last_sequence = sequence;
+
+        if sequence == expected {
+            return Ok(Applied::Applied);
+        }
+        let gap = Gap {
+            first_missing: expected,
+            last_missing: sequence - 1,
+        };
+        self.gaps.push(gap);
+        Ok(Applied::AfterGap(gap))
+    }
+
+    fn open_gap(&self, sequence: u64) -> Option<usize> {
+        self.gaps
+            .iter()
+            .position(|gap| gap.first_missing <= sequence && sequence <= gap.last_missing)
+    }
+
+    fn fill_gap(&mut self, index: usize, sequence: u64) {
+        let gap = self.gaps[index];
+        match (sequence == gap.first_missing, sequence == gap.last_missing) {
+            (true, true) => {
+                self.gaps.remove(index);
+            }
+            (true, false) => self.gaps[index].first_missing += 1,
+            (false, true) => self.gaps[index].last_missing -= 1,
+            (false, false) => {
+                self.gaps[index].last_missing = sequence - 1;
+                self.gaps.insert(
+                    index + 1,
+                    Gap {
+                        first_missing: sequence + 1,
+                        last_missing: gap.last_missing,
+                    },
+                );
+            }
+        }
+    }
+
+    fn check_known(&self, payload: &EventPayload) -> Result<(), IndexError> {
+        let known = |id: u64| {
+            if self.remittances.contains_key(&id) {
+                Ok(())
+            } else {
+                Err(IndexError::UnknownRemittance(id))
+            }
+        };
+
+        match payload {
+            EventPayload::RemittanceCancelled(e) => known(e.remittance_id),
+            EventPayload::RemittanceFinalized(id) => known(*id),
+            EventPayload::MemoSet(e) => known(e.remittance_id),
+            EventPayload::SettlementCompleted(e) => known(e.remittance_id),
+            EventPayload::ReceiptIssued(e) => known(e.remittance_id),
+            EventPayload::RemittanceQueued(e) => known(e.remittance_id),
+            EventPayload::TravelRuleCommitted(e) => known(e.remittance_id),
+            EventPayload::TravelRuleAcknowledged(e) => known(e.remittance_id),
+            EventPayload::BatchAppended(e) => e.remittance_ids.iter().try_for_each(known),
+            EventPayload::BatchAborted(e) | EventPayload::BatchReleased(e) => {
+                e.remittance_ids.iter().try_for_each(known)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn apply_payload(&mut self, payload: &EventPayload, sequence: u64) {
+        match payload {
+            EventPayload::RemittanceCreated(e) => {
+                self.remittances.insert(
+                    e.remittance_id,
+                    IndexedRemittance {
+                        id: e.remittance_id,
+                        sender: e.sender.clone(),
+                        agent: e.agent.clone(),
+                        amount: e.amount,
+                        fee: e.fee,
+                        integrator_fee: e.integrator_fee,
+                        state: RemittanceState::Pending,
+                        memo: None,
+                        memo_sequence: 0,
+                        cycle_id: None,
+                        batch_id: None,
+                        batch_sequence: 0,
+                        settlement_id: None,
+                        payout_amount: None,
+                        receipt_hash: None,
+                        travel_rule_commitment: None,
+                        travel_rule_ack: None,
+                        last_sequence: sequence,
+                    },
+                );
+            }
+            EventPayload::RemittanceCancelled(e) => {
+                self.update(e.remittance_id, sequence, |r| {
+                    r.advance(RemittanceState::Cancelled)
+                });
+            }
+            EventPayload::RemittanceFinalized(id) => {
+                self.update(*id, sequence, |r| r.advance(RemittanceState::Finalized));
+            }
+            EventPayload::MemoSet(e) => {
+                self.update(e.remittance_id, sequence, |r| {
+                    if sequence > r.memo_sequence {
+                        r.memo = Some(e.memo.clone());
+                        r.memo_sequence = sequence;
+                    }
+                });
+            }
+            EventPayload::SettlementCompleted(e) => {
+                self.update(e.remittance_id, sequence, |r| {
+                    r.advance(RemittanceState::Settled);
+                    r.settlement_id = Some(e.settlement_id.clone());
+                    r.payout_amount = Some(e.amount);
+                });
+            }
+            EventPayload::ReceiptIssued(e) => {
+                self.update(e.remittance_id, sequence, |r| {
+                    r.receipt_hash = Some(e.receipt_hash.clone())
+                });
+            }
+            EventPayload::RemittanceQueued(e) => {
+                self.update(e.remittance_id, sequence, |r| r.cycle_id = Some(e.cycle_id));
+            }
+            EventPayload::TravelRuleCommitted(e) => {
+                self.update(e.remittance_id, sequence, |r| {
+                    r.travel_rule_commitment = Some(e.originator_commitment.clone())
+                });
+            }
+            EventPayload::TravelRuleAcknowledged(e) => {
+                self.update(e.remittance_id, sequence, |r| {
+                    r.travel_rule_ack = Some(e.beneficiary_ack.clone())
+                });
+            }
+            EventPayload::BatchAppended(e) => {
+                for id in e.remittance_ids.iter() {
+                    self.update(id, sequence, |r| r.set_batch(Some(e.batch_id), sequence));
+                }
+            }
+            EventPayload::BatchAborted(e) | EventPayload::BatchReleased(e) => {
+                for id in e.remittance_ids.iter() {
+                    self.update(id, sequence, |r| r.set_batch(None, sequence));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn update(&mut self, id: u64, sequence: u64, change: impl FnOnce(&mut IndexedRemittance)) {
+        if let Some(remittance) = self.remittances.get_mut(&id) {
+            change(remittance);
+            remittance.last_sequence = remittance.last_sequence.max(sequence);
+        }
+    }
+}
@@ -0,0 +1,35 @@
+//! Host-side decoder and indexer for SwiftRemit contract events.
+//!
+//! Contract events reach off-chain consumers as XDR: a list of topics and a
+//! data value per event. This crate decodes them into the contract's own
+//! event types, so the decoder cannot drift from the contract, and folds the
+//! decoded stream into remittance state.
+//!
+//! ## Usage
+//!
+//! ```ignore
+//! let env = Env::default();
+//! let mut indexer = Indexer::new();
+//! for xdr in events {
+//!     let event = decode_event_base64(&env, &xdr)?;
+//!     if let Applied::AfterGap(gap) = indexer.apply(&event)? {
+//!         // refetch events gap.first_missing..=gap.last_missing
+//!     }
+//! }
+//! ```
+//!
+//! `Env` is only used as a host for converting XDR values into contract
+//! types; it holds no contract state.
+//!
+//! ## Gaps
+//!
+//! Every event carries a per-contract sequence number that increases by one
+//! per event. The indexer reports skipped sequence numbers as gaps and
+//! ignores sequence numbers it has already applied, so events can be
+//! refetched and replayed safely.
+
+mod decode;
+mod index;
+
+pub use decode::*;
+pub use index::*;
@@ -0,0 +1,364 @@
+use soroban_sdk::testutils::{Address as _, Events};
+use soroban_sdk::xdr::{
+    self, ContractEventBody, ContractEventType, ContractEventV0, ExtensionPoint, Limits, ScAddress,
+    ScVal, WriteXdr,
+};
+use soroban_sdk::{token, vec, Address, BytesN, Env, String, TryFromVal};
+use swiftremit::{
+    BatchSettlementEntry, Corridor, RemittanceStatus, SwiftRemitContract, SwiftRemitContractClient,
+};
+use swiftremit_indexer::{
+    decode_event, decode_event_base64, decode_event_xdr, decode_parts, Applied, DecodeError,
+    DecodedEvent, Gap, IndexError, Indexer, RemittanceState, Subject,
+};
+
+struct Fixture<'a> {
+    env: Env,
+    contract: SwiftRemitContractClient<'a>,
+    sender: Address,
+    agent: Address,
+}
+
+fn setup<'a>() -> Fixture<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    token::StellarAssetClient::new(&env, &token).mint(&sender, &10000);
+
+    let contract =
+        SwiftRemitContractClient::new(&env, &env.register_contract(None, SwiftRemitContract {}));
+    contract.whitelist_token(&admin, &token);
+    contract.initialize(&admin, &token, &250, &0);
+    contract.register_agent(&agent);
+
+    Fixture {
+        env,
+        contract,
+        sender,
+        agent,
+    }
+}
+
+fn corridor(env: &Env) -> Corridor {
+    Corridor {
+        currency: String::from_str(env, "USD"),
+        country: String::from_str(env, "US"),
+    }
+}
+
+/// Creates three remittances: the first settled with a memo, the second
+/// cancelled and the third left pending.
+fn run_flows(f: &Fixture) -> [u64; 3] {
+    let create = || {
+        f.contract.create_remittance(
+            &f.sender,
+            &f.agent,
+            &1000,
+            &corridor(&f.env),
+            &None,
+            &None,
+            &None,
+            &None,
+        )
+    };
+    let settled = create();
+    let cancelled = create();
+    let pending = create();
+
+    f.contract
+        .set_remittance_memo(&settled, &BytesN::from_array(&f.env, &[7; 32]));
+    f.contract.confirm_payout(&settled);
+    f.contract.cancel_remittance(&cancelled);
+
+    [settled, cancelled, pending]
+}
+
+/// Returns the events the contract has emitted, as the XDR an RPC node
+/// would serve them.
+fn capture(f: &Fixture) -> Vec<xdr::ContractEvent> {
+    let contract = &f.contract.address;
+    let ScAddress::Contract(hash) = ScAddress::from(contract) else {
+        panic!("contract address is not a contract");
+    };
+
+    f.env
+        .events()
+        .all()
+        .iter()
+        .filter(|(address, _, _)| address == contract)
+        .map(|(_, topics, data)| {
+            let topics: Vec<ScVal> = topics
+                .iter()
+                .map(|topic| ScVal::try_from_val(&f.env, &topic).unwrap())
+                .collect();
+            xdr::ContractEvent {
+                ext: ExtensionPoint::V0,
+                contract_id: Some(hash.clone()),
+                type_: ContractEventType::Contract,
+                body: ContractEventBody::V0(ContractEventV0 {
+                    topics: topics.try_into().unwrap(),
+                    data: ScVal::try_from_val(&f.env, &data).unwrap(),
+                }),
+            }
+        })
+        .collect()
+}
+
+fn decode_all(env: &Env, events: &[xdr::ContractEvent]) -> Vec<DecodedEvent> {
+    events
+        .iter()
+        .map(|event| {
+            let encoded = event.to_xdr_base64(Limits::none()).unwrap();
+            decode_event_base64(env, &encoded).unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn test_decodes_captured_events() {
+    let f = setup();
+    let [settled, _, _] = run_flows(&f);
+    let captured = capture(&f);
+
+    let events = decode_all(&f.env, &captured);
+    assert_eq!(events.len() as u64, f.contract.get_event_sequence());
+    for (i, event) in events.iter().enumerate() {
+        assert_eq!(event.contract, f.contract.address);
+        assert_eq!(event.sequence(), i as u64 + 1);
+    }
+
+    let created = events
+        .iter()
+        .find(|e| e.category == "remit" && e.action == "created")
+        .unwrap();
+    assert_eq!(created.subject, Some(Subject::Id(settled)));
+
+    let registered = events.iter().find(|e| e.category == "agent").unwrap();
+    assert_eq!(registered.subject, Some(Subject::Address(f.agent.clone())));
+
+    let bytes = captured[0].to_xdr(Limits::none()).unwrap();
+    assert_eq!(decode_event_xdr(&f.env, &bytes).unwrap(), events[0]);
+    assert_eq!(decode_event(&f.env, &captured[0]).unwrap(), events[0]);
+}
+
+#[test]
+fn test_rejects_malformed_events() {
+    let f = setup();
+    let mut captured = capture(&f);
+    let ContractEventBody::V0(body) = captured[0].body.clone();
+
+    let result = decode_parts(&f.env, &f.contract.address, &body.topics[..1], &body.data);
+    assert!(matches!(result, Err(DecodeError::InvalidTopics)));
+
+    let result = decode_parts(&f.env, &f.contract.address, &body.topics, &ScVal::U64(1));
+    assert!(matches!(result, Err(DecodeError::InvalidData)));
+
+    captured[0].type_ = ContractEventType::Diagnostic;
+    let result = decode_event(&f.env, &captured[0]);
+    assert!(matches!(result, Err(DecodeError::NotContractEvent)));
+
+    let result = decode_event_base64(&f.env, "not xdr");
+    assert!(matches!(result, Err(DecodeError::Xdr(_))));
+}
+
+#[test]
+fn test_rebuilds_remittance_state_from_captured_events() {
+    let f = setup();
+    let [settled, cancelled, pending] = run_flows(&f);
+    let events = decode_all(&f.env, &capture(&f));
+
+    let mut indexer = Indexer::new();
+    for event in &events {
+        assert_eq!(indexer.apply(event), Ok(Applied::Applied));
+    }
+    assert_eq!(indexer.last_sequence(), f.contract.get_event_sequence());
+    assert!(indexer.gaps().is_empty());
+    assert_eq!(indexer.remittances().count(), 3);
+
+    for id in [settled, cancelled, pending] {
+        let indexed = indexer.remittance(id).unwrap();
+        let stored = f.contract.get_remittance(&id);
+        assert_eq!(indexed.sender, stored.sender);
+        assert_eq!(indexed.agent, stored.agent);
+        assert_eq!(indexed.amount, stored.amount);
+        assert_eq!(indexed.fee, stored.fee);
+    }
+
+    let remittance = indexer.remittance(settled).unwrap();
+    assert_eq!(remittance.state, RemittanceState::Settled);
+    assert_eq!(remittance.memo, Some(BytesN::from_array(&f.env, &[7; 32])));
+    assert_eq!(
+        remittance.settlement_id,
+        f.contract.get_settlement_id(&settled)
+    );
+    assert_eq!(
+        remittance.receipt_hash,
+        Some(f.contract.get_receipt_hash(&settled))
+    );
+    assert_eq!(
+        remittance.payout_amount,
+        Some(remittance.amount - remittance.fee - remittance.integrator_fee)
+    );
+
+    let remittance = indexer.remittance(cancelled).unwrap();
+    assert_eq!(
+        f.contract.get_remittance(&cancelled).status,
+        RemittanceStatus::Cancelled
+    );
+    assert_eq!(remittance.state, RemittanceState::Cancelled);
+
+    let remittance = indexer.remittance(pending).unwrap();
+    assert_eq!(
+        f.contract.get_remittance(&pending).status,
+        RemittanceStatus::Pending
+    );
+    assert_eq!(remittance.state, RemittanceState::Pending);
+    assert_eq!(remittance.settlement_id, None);
+}
+
+#[test]
+fn test_detects_gaps_and_fills_them_on_replay() {
+    let f = setup();
+    run_flows(&f);
+    let events = decode_all(&f.env, &capture(&f));
+
+    let dropped = events.iter().position(|e| e.category == "agent").unwrap();
+    let mut indexer = Indexer::new();
+    for (i, event) in events.iter().enumerate() {
+        let expected = match i {
+            i if i == dropped => continue,
+            i if i == dropped + 1 => Applied::AfterGap(Gap {
+                first_missing: events[dropped].sequence(),
+                last_missing: events[dropped].sequence(),
+            }),
+            _ => Applied::Applied,
+        };
+        assert_eq!(indexer.apply(event), Ok(expected));
+    }
+    assert_eq!(indexer.gaps().len(), 1);
+
+    let last_sequence = indexer.last_sequence();
+    for (i, event) in events.iter().enumerate() {
+        let expected = if i == dropped {
+            Applied::Applied
+        } else {
+            Applied::Duplicate
+        };
+        assert_eq!(indexer.apply(event), Ok(expected));
+    }
+    assert_eq!(indexer.last_sequence(), last_sequence);
+    assert!(indexer.gaps().is_empty());
+    assert_eq!(indexer.apply(&events[dropped]), Ok(Applied::Duplicate));
+}
+
+#[test]
+fn test_replayed_gap_event_restores_remittance_state() {
+    let f = setup();
+    let [settled, _, _] = run_flows(&f);
+    let events = decode_all(&f.env, &capture(&f));
+
+    let completed = events
+        .iter()
+        .position(|e| e.category == "settle" && e.action == "complete")
+        .unwrap();
+    let memo = events
+        .iter()
+        .position(|e| e.category == "remit" && e.action == "memo")
+        .unwrap();
+
+    let mut indexer = Indexer::new();
+    for (i, event) in events.iter().enumerate() {
+        if i != completed && i != memo {
+            indexer.apply(event).unwrap();
+        }
+    }
+    assert!(!indexer.gaps().is_empty());
+    assert_eq!(
+        indexer.remittance(settled).unwrap().state,
+        RemittanceState::Pending
+    );
+
+    assert_eq!(indexer.apply(&events[completed]), Ok(Applied::Applied));
+    assert!(!indexer.gaps().is_empty());
+    assert_eq!(indexer.apply(&events[memo]), Ok(Applied::Applied));
+    assert!(indexer.gaps().is_empty());
+
+    let remittance = indexer.remittance(settled).unwrap();
+    assert_eq!(remittance.state, RemittanceState::Settled);
+    assert_eq!(remittance.memo, Some(BytesN::from_array(&f.env, &[7; 32])));
+    assert_eq!(
+        remittance.settlement_id,
+        f.contract.get_settlement_id(&settled)
+    );
+}
+
+#[test]
+fn test_rejects_events_for_unindexed_remittances() {
+    let f = setup();
+    let [settled, _, _] = run_flows(&f);
+    let events = decode_all(&f.env, &capture(&f));
+
+    let memo = events
+        .iter()
+        .find(|e| e.category == "remit" && e.action == "memo")
+        .unwrap();
+    let mut indexer = Indexer::resume(memo.sequence() - 1, [], []);
+
+    assert_eq!(
+        indexer.apply(memo),
+        Err(IndexError::UnknownRemittance(settled))
+    );
+    assert_eq!(indexer.last_sequence(), memo.sequence() - 1);
+
+    let mut unsupported = memo.clone();
+    unsupported.event.envelope.schema_version += 1;
+    assert_eq!(
+        indexer.apply(&unsupported),
+        Err(IndexError::UnsupportedSchema(
+            unsupported.event.envelope.schema_version
+        ))
+    );
+}
+
+#[test]
+fn test_aborted_batch_releases_remittances() {
+    let f = setup();
+    let [_, _, pending] = run_flows(&f);
+
+    let batch_id = f.contract.open_settlement_batch(&f.agent);
+    let entries = vec![
+        &f.env,
+        BatchSettlementEntry {
+            remittance_id: pending,
+        },
+    ];
+    f.contract.append_settlement_batch(&batch_id, &entries);
+    f.contract.abort_settlement_batch(&batch_id);
+    let events = decode_all(&f.env, &capture(&f));
+
+    let appended = events
+        .iter()
+        .position(|e| e.category == "batch" && e.action == "appended")
+        .unwrap();
+
+    let mut indexer = Indexer::new();
+    for event in &events {
+        indexer.apply(event).unwrap();
+    }
+    assert_eq!(indexer.remittance(pending).unwrap().batch_id, None);
+
+    let mut indexer = Indexer::new();
+    for (i, event) in events.iter().enumerate() {
+        if i != appended {
+            indexer.apply(event).unwrap();
+        }
+    }
+    assert_eq!(indexer.apply(&events[appended]), Ok(Applied::Applied));
+    assert_eq!(indexer.remittance(pending).unwrap().batch_id, None);
+}
@@ -0,0 +1,443 @@
+//! Hash-chained audit log of state-changing operations.
+//!
+//! Events can be missed by indexers. To make gaps detectable, every
+//! state-changing entry point folds a canonical record of what it did into a
+//! running hash, the audit head, and emits the record together with its
+//! sequence number and the new head. An indexer that recomputes the chain
+//! from the emitted records detects any missing, duplicated or reordered
+//! record as a mismatch.
+//!
+//! ## Chain
+//!
+//! The chain starts from sequence 0 and an all-zero hash. Record `n` moves
+//! the head to:
+//!
+//! - sequence = n
+//! - hash = SHA-256(previous hash || n as u64 BE || record)
+//!
+//! ## Record Encoding
+//!
+//! Every record starts with the operation code as u32 BE, the ledger
+//! timestamp as u64 BE and the XDR encoding of the acting address, followed
+//! by the operation's payload. The acting address is the sender for
+//! `Created`, `Cancelled`, `MemoSet` and `BatchReleased`, the agent for
+//! `Settled`, `DelegationGranted`, `DelegationRevoked`,
+//! `TravelRuleAcknowledged` and `CycleConfirmed`, the batch owner for
+//! `BatchOpened`, `BatchAppended`, `BatchAborted` and `BatchFinalized`, the
+//! contract itself for `CycleClosed`, and the admin for every other
+//! operation.
+//!
+//! | Operation                                      | Payload                                                           |
+//! |------------------------------------------------|-------------------------------------------------------------------|
+//! | `Created`, `Settled`, `Cancelled`, `Finalized` | remittance ID (u64 BE), amount (i128 BE)                          |
+//! | `FeeUpdated`, `IntegratorFeeUpdated`           | old fee (u32 BE), new fee (u32 BE)                                |
+//! | `FeesWithdrawn`                                | XDR of the recipient address, amount (i128 BE)                    |
+//! | `AgentRegistered`, `AgentRemoved`              | XDR of the agent address                                          |
+//! | `AdminAdded`, `AdminRemoved`                   | XDR of the admin address                                          |
+//! | `Paused`, `Unpaused`                           | empty                                                             |
+//! | `TokenWhitelisted`, `TokenRemoved`             | XDR of the token address                                          |
+//! | `DelegationGranted`, `DelegationRevoked`       | XDR of the operator address                                       |
+//! | `DailyLimitUpdated`                            | XDR of the currency, XDR of the country, limit (i128 BE)          |
+//! | `RateLimitUpdated`                             | XDR of the new `RateLimitConfig`                                  |
+//! | `CooldownUpdated`                              | old cooldown (u64 BE), new cooldown (u64 BE)                      |
+//! | `Upgraded`                                     | new WASM hash (32 bytes)                                          |
+//! | `IdempotencyTtlUpdated`                        | old TTL (u64 BE), new TTL (u64 BE)                                |
+//! | `TravelRuleThresholdUpdated`                   | XDR of the corridor, threshold (i128 BE)                          |
+//! | `SettlementCycleUpdated`                       | XDR of the corridor, XDR of the new `SettlementCycleConfig`       |
+//! | `FxConfigUpdated`                              | XDR of the new `FxConfig`                                         |
+//! | `MemoSet`                                      | remittance ID (u64 BE), memo (32 bytes)                           |
+//! | `TravelRuleAcknowledged`                       | remittance ID (u64 BE), beneficiary acknowledgement (32 bytes)    |
+//! | `CycleConfirmed`                               | cycle ID (u64 BE), remittance ID (u64 BE)                         |
+//! | `CycleClosed`                                  | cycle ID (u64 BE), gross volume, net volume, fees (i128 BE each)  |
+//! | `BatchOpened`                                  | batch ID (u64 BE)                                                 |
+//! | `BatchAppended`, `BatchAborted`                | batch ID (u64 BE), XDR of the appended or released remittance IDs |
+//! | `BatchReleased`                                | batch ID (u64 BE), remittance ID (u64 BE)                         |
+//! | `BatchFinalized`                               | batch ID (u64 BE), remittances (u32 BE), net transfers (u32 BE)   |
+//! | `MigrationSessionOpened`                       | expected batches (u32 BE), batches root (32 bytes)                |
+//! | `MigrationBatchImported`                       | batch number (u32 BE), batch hash (32 bytes)                      |
+//! | `MigrationSessionFinalized`                    | remittance counter (u64 BE)                                       |
+//! | `MigrationSessionAborted`                      | batches imported (u32 BE), batches root (32 bytes)                |
+//!
+//! For `Created`, `Cancelled` and `Finalized` the amount is the remittance
+//! amount, for `Settled` it is the amount paid out to the agent.
+//! `BatchFinalized` is recorded once, by the call that completes the batch;
+//! the remittances it settles are recorded as `Settled` along the way.
+
+use soroban_sdk::{contracttype, xdr::ToXdr, Address, Bytes, BytesN, Env, String, Vec};
+
+use crate::{
+    address_to_bytes, emit_audit_record, Corridor, FxConfig, RateLimitConfig,
+    SettlementCycleConfig,
+};
+
+/// State-changing operation recorded in the audit chain.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum AuditOp {
+    /// Remittance created
+    Created = 1,
+    /// Remittance settled, individually or in a batch or cycle
+    Settled = 2,
+    /// Remittance cancelled and refunded
+    Cancelled = 3,
+    /// Platform fee updated
+    FeeUpdated = 4,
+    /// Agent registered
+    AgentRegistered = 5,
+    /// Agent removed
+    AgentRemoved = 6,
+    /// Admin added
+    AdminAdded = 7,
+    /// Admin removed
+    AdminRemoved = 8,
+    /// Contract paused
+    Paused = 9,
+    /// Contract unpaused
+    Unpaused = 10,
+    /// Accumulated platform fees withdrawn
+    FeesWithdrawn = 11,
+    /// Integrator fee updated
+    IntegratorFeeUpdated = 12,
+    /// Token added to the whitelist
+    TokenWhitelisted = 13,
+    /// Token removed from the whitelist
+    TokenRemoved = 14,
+    /// Settlement delegation granted or replaced
+    DelegationGranted = 15,
+    /// Settlement delegation revoked
+    DelegationRevoked = 16,
+    /// Settled remittance finalized
+    Finalized = 17,
+    /// Daily send limit set for a currency and country
+    DailyLimitUpdated = 18,
+    /// Rate limit configuration updated
+    RateLimitUpdated = 19,
+    /// Settlement cooldown updated
+    CooldownUpdated = 20,
+    /// Contract code upgraded
+    Upgraded = 21,
+    /// Idempotency key retention window updated
+    IdempotencyTtlUpdated = 22,
+    /// Travel-rule threshold set for a corridor
+    TravelRuleThresholdUpdated = 23,
+    /// Settlement cycle configured for a corridor
+    SettlementCycleUpdated = 24,
+    /// FX configuration updated
+    FxConfigUpdated = 25,
+    /// Memo attached to a remittance
+    MemoSet = 26,
+    /// Travel-rule exchange acknowledged by the agent
+    TravelRuleAcknowledged = 27,
+    /// Remittance queued in a settlement cycle
+    CycleConfirmed = 28,
+    /// Settlement cycle closed and settled
+    CycleClosed = 29,
+    /// Multi-transaction settlement batch opened
+    BatchOpened = 30,
+    /// Remittances appended to a settlement batch
+    BatchAppended = 31,
+    /// Settlement batch aborted
+    BatchAborted = 32,
+    /// Remittance released from an expired settlement batch
+    BatchReleased = 33,
+    /// Settlement batch completed
+    BatchFinalized = 34,
+    /// Batched migration session opened
+    MigrationSessionOpened = 35,
+    /// Migration batch imported
+    MigrationBatchImported = 36,
+    /// Batched migration session finalized
+    MigrationSessionFinalized = 37,
+    /// Batched migration session aborted
+    MigrationSessionAborted = 38,
+}
+
+/// Latest link of the audit chain.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuditHead {
+    /// Sequence number of the latest record, 0 before the first
+    pub sequence: u64,
+    /// Hash over every record up to and including the latest
+    pub hash: BytesN<32>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub(crate) enum AuditKey {
+    /// Latest audit head (instance storage)
+    Head,
+}
+
+/// Returns the current audit head, or the genesis head if nothing has been
+/// recorded yet.
+pub fn get_audit_head(env: &Env) -> AuditHead {
+    env.storage()
+        .instance()
+        .get(&AuditKey::Head)
+        .unwrap_or(AuditHead {
+            sequence: 0,
+            hash: BytesN::from_array(env, &[0u8; 32]),
+        })
+}
+
+/// Sets the audit head, as when restoring the chain from a migration.
+pub fn set_audit_head(env: &Env, head: &AuditHead) {
+    env.storage().instance().set(&AuditKey::Head, head);
+}
+
+/// Computes the head that follows `head` after appending `record`.
+pub fn next_audit_head(env: &Env, head: &AuditHead, record: &Bytes) -> AuditHead {
+    let sequence = head.sequence + 1;
+
+    let mut buf = Bytes::new(env);
+    buf.extend_from_array(&head.hash.to_array());
+    buf.extend_from_array(&sequence.to_be_bytes());
+    buf.append(record);
+
+    AuditHead {
+        sequence,
+        hash: env.crypto().sha256(&buf).into(),
+    }
+}
+
+/// Records an operation on a remittance.
+pub fn record_remittance_op(
+    env: &Env,
+    op: AuditOp,
+    actor: &Address,
+    remittance_id: u64,
+    amount: i128,
+) -> AuditHead {
+    let mut payload = Bytes::new(env);
+    payload.extend_from_array(&remittance_id.to_be_bytes());
+    payload.extend_from_array(&amount.to_be_bytes());
+    append_record(env, op, actor, &payload)
+}
+
+/// Records a platform or integrator fee update.
+pub fn record_fee_update(
+    env: &Env,
+    op: AuditOp,
+    actor: &Address,
+    old_fee_bps: u32,
+    new_fee_bps: u32,
+) -> AuditHead {
+    let mut payload = Bytes::new(env);
+    payload.extend_from_array(&old_fee_bps.to_be_bytes());
+    payload.extend_from_array(&new_fee_bps.to_be_bytes());
+    append_record(env, op, actor, &payload)
+}
+
+/// Records a withdrawal of accumulated platform fees.
+pub fn record_fees_withdrawn(env: &Env, actor: &Address, to: &Address, amount: i128) -> AuditHead {
+    let mut payload = address_to_bytes(env, to);
+    payload.extend_from_array(&amount.to_be_bytes());
+    append_record(env, AuditOp::FeesWithdrawn, actor, &payload)
+}
+
+/// Records an agent, admin, token or delegation change affecting `subject`.
+pub fn record_role_op(env: &Env, op: AuditOp, actor: &Address, subject: &Address) -> AuditHead {
+    append_record(env, op, actor, &address_to_bytes(env, subject))
+}
+
+/// Records a pause or unpause.
+pub fn record_pause_op(env: &Env, op: AuditOp, actor: &Address) -> AuditHead {
+    append_record(env, op, actor, &Bytes::new(env))
+}
+
+/// Records a daily send limit update for a currency and country.
+pub fn record_daily_limit_update(
+    env: &Env,
+    actor: &Address,
+    currency: &String,
+    country: &String,
+    limit: i128,
+) -> AuditHead {
+    let mut payload = currency.clone().to_xdr(env);
+    payload.append(&country.clone().to_xdr(env));
+    payload.extend_from_array(&limit.to_be_bytes());
+    append_record(env, AuditOp::DailyLimitUpdated, actor, &payload)
+}
+
+/// Records a rate limit configuration update.
+pub fn record_rate_limit_update(env: &Env, actor: &Address, config: &RateLimitConfig) -> AuditHead {
+    append_record(env, AuditOp::RateLimitUpdated, actor, &config.clone().to_xdr(env))
+}
+
+/// Records a settlement cooldown update.
+pub fn record_cooldown_update(
+    env: &Env,
+    actor: &Address,
+    old_cooldown: u64,
+    new_cooldown: u64,
+) -> AuditHead {
+    let mut payload = Bytes::new(env);
+    payload.extend_from_array(&old_cooldown.to_be_bytes());
+    payload.extend_from_array(&new_cooldown.to_be_bytes());
+    append_record(env, AuditOp::CooldownUpdated, actor, &payload)
+}
+
+/// Records a contract code upgrade.
+pub fn record_upgrade(env: &Env, actor: &Address, new_wasm_hash: &BytesN<32>) -> AuditHead {
+    let payload = Bytes::from_array(env, &new_wasm_hash.to_array());
+    append_record(env, AuditOp::Upgraded, actor, &payload)
+}
+
+/// Records an idempotency key retention window update.
+pub fn record_idempotency_ttl_update(
+    env: &Env,
+    actor: &Address,
+    old_ttl: u64,
+    new_ttl: u64,
+) -> AuditHead {
+    let mut payload = Bytes::new(env);
+    payload.extend_from_array(&old_ttl.to_be_bytes());
+    payload.extend_from_array(&new_ttl.to_be_bytes());
+    append_record(env, AuditOp::IdempotencyTtlUpdated, actor, &payload)
+}
+
+/// Records a travel-rule threshold update for a corridor.
+pub fn record_travel_rule_threshold_update(
+    env: &Env,
+    actor: &Address,
+    corridor: &Corridor,
+    threshold: i128,
+) -> AuditHead {
+    let mut payload = corridor.clone().to_xdr(env);
+    payload.extend_from_array(&threshold.to_be_bytes());
+    append_record(env, AuditOp::TravelRuleThresholdUpdated, actor, &payload)
+}
+
+/// Records a settlement cycle configuration update for a corridor.
+pub fn record_settlement_cycle_update(
+    env: &Env,
+    actor: &Address,
+    corridor: &Corridor,
+    config: &SettlementCycleConfig,
+) -> AuditHead {
+    let mut payload = corridor.clone().to_xdr(env);
+    payload.append(&config.clone().to_xdr(env));
+    append_record(env, AuditOp::SettlementCycleUpdated, actor, &payload)
+}
+
+/// Records an FX configuration update.
+pub fn record_fx_config_update(env: &Env, actor: &Address, config: &FxConfig) -> AuditHead {
+    append_record(env, AuditOp::FxConfigUpdated, actor, &config.clone().to_xdr(env))
+}
+
+/// Records a memo or travel-rule acknowledgement attached to a remittance.
+pub fn record_remittance_attachment(
+    env: &Env,
+    op: AuditOp,
+    actor: &Address,
+    remittance_id: u64,
+    value: &BytesN<32>,
+) -> AuditHead {
+    let mut payload = Bytes::new(env);
+    payload.extend_from_array(&remittance_id.to_be_bytes());
+    payload.extend_from_array(&value.to_array());
+    append_record(env, op, actor, &payload)
+}
+
+/// Records a remittance queued in a settlement cycle, or released from a
+/// settlement batch.
+pub fn record_queue_op(
+    env: &Env,
+    op: AuditOp,
+    actor: &Address,
+    queue_id: u64,
+    remittance_id: u64,
+) -> AuditHead {
+    let mut payload = Bytes::new(env);
+    payload.extend_from_array(&queue_id.to_be_bytes());
+    payload.extend_from_array(&remittance_id.to_be_bytes());
+    append_record(env, op, actor, &payload)
+}
+
+/// Records the close of a settlement cycle.
+pub fn record_cycle_closed(
+    env: &Env,
+    actor: &Address,
+    cycle_id: u64,
+    gross_volume: i128,
+    net_volume: i128,
+    fees: i128,
+) -> AuditHead {
+    let mut payload = Bytes::new(env);
+    payload.extend_from_array(&cycle_id.to_be_bytes());
+    payload.extend_from_array(&gross_volume.to_be_bytes());
+    payload.extend_from_array(&net_volume.to_be_bytes());
+    payload.extend_from_array(&fees.to_be_bytes());
+    append_record(env, AuditOp::CycleClosed, actor, &payload)
+}
+
+/// Records a settlement batch being opened.
+pub fn record_batch_opened(env: &Env, actor: &Address, batch_id: u64) -> AuditHead {
+    let payload = Bytes::from_array(env, &batch_id.to_be_bytes());
+    append_record(env, AuditOp::BatchOpened, actor, &payload)
+}
+
+/// Records remittances appended to or released by aborting a settlement
+/// batch.
+pub fn record_batch_membership(
+    env: &Env,
+    op: AuditOp,
+    actor: &Address,
+    batch_id: u64,
+    remittance_ids: &Vec<u64>,
+) -> AuditHead {
+    let mut payload = Bytes::from_array(env, &batch_id.to_be_bytes());
+    payload.append(&remittance_ids.clone().to_xdr(env));
+    append_record(env, op, actor, &payload)
+}
+
+/// Records the completion of a settlement batch.
+pub fn record_batch_finalized(
+    env: &Env,
+    actor: &Address,
+    batch_id: u64,
+    remittance_count: u32,
+    transfer_count: u32,
+) -> AuditHead {
+    let mut payload = Bytes::from_array(env, &batch_id.to_be_bytes());
+    payload.extend_from_array(&remittance_count.to_be_bytes());
+    payload.extend_from_array(&transfer_count.to_be_bytes());
+    append_record(env, AuditOp::BatchFinalized, actor, &payload)
+}
+
+/// Records a migration session being opened or aborted, or one of its
+/// batches being imported.
+pub fn record_migration_step(
+    env: &Env,
+    op: AuditOp,
+    actor: &Address,
+    number: u32,
+    hash: &BytesN<32>,
+) -> AuditHead {
+    let mut payload = Bytes::from_array(env, &number.to_be_bytes());
+    payload.extend_from_array(&hash.to_array());
+    append_record(env, op, actor, &payload)
+}
+
+/// Records the finalization of a migration session.
+pub fn record_migration_finalized(env: &Env, actor: &Address, remittance_counter: u64) -> AuditHead {
+    let payload = Bytes::from_array(env, &remittance_counter.to_be_bytes());
+    append_record(env, AuditOp::MigrationSessionFinalized, actor, &payload)
+}
+
+fn append_record(env: &Env, op: AuditOp, actor: &Address, payload: &Bytes) -> AuditHead {
+    let mut record = Bytes::new(env);
+    record.extend_from_array(&(op as u32).to_be_bytes());
+    record.extend_from_array(&env.ledger().timestamp().to_be_bytes());
+    record.append(&address_to_bytes(env, actor));
+    record.append(payload);
+
+    let head = next_audit_head(env, &get_audit_head(env), &record);
+    set_audit_head(env, &head);
+
+    emit_audit_record(env, actor.clone(), head.sequence, record, head.hash.clone());
+
+    head
+}
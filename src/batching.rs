@@ -0,0 +1,313 @@
+//! Resource-aware batch settlement for the SwiftRemit contract.
+//!
+//! A single `batch_settle_with_netting` call must fit within one
+//! transaction's CPU and memory budget. This module provides:
+//!
+//! - a cost model that estimates what settling a batch will consume and how
+//!   many of its entries can safely be settled in one transaction, and
+//! - multi-transaction settlement batches for settlements that do not fit:
+//!   a batch is opened, entries are appended over several calls, and the
+//!   batch is then finalized in chunks, with a cursor tracking progress.
+//!
+//! Multi-transaction batches always net multilaterally. Multilateral netting
+//! keeps one running position per party and makes at most one transfer per
+//! party, so a batch is bounded by its distinct parties rather than by its
+//! entries. Remittances appended to a batch are locked until the batch has
+//! settled them.
+//!
+//! Locks do not outlive an abandoned batch. The owner can abort a batch that
+//! is still open, releasing every remittance in it. Once a batch has been
+//! open for `BATCH_LOCK_DURATION`, each sender can release their own
+//! remittance from it, and a batch whose finalization has started can be
+//! finished by anyone, since its transfers are already fixed.
+
+use soroban_sdk::{contracttype, Address, Env, Map, Vec};
+
+use crate::{ContractError, MerkleFrontier, NetTransfer, NettingMode, MAX_BATCH_SIZE};
+
+/// Maximum number of remittances in a multi-transaction settlement batch.
+pub const MAX_BATCHED_ENTRIES: u32 = 1000;
+
+/// Maximum number of distinct parties, including the fee collector, in a
+/// multi-transaction settlement batch.
+pub const MAX_BATCHED_PARTIES: u32 = 100;
+
+/// Finalization steps performed by a single `finalize_settlement_batch`
+/// call. Each step executes one transfer or settles one remittance.
+pub const FINALIZE_CHUNK_SIZE: u32 = MAX_BATCH_SIZE;
+
+/// Seconds after opening after which a batch no longer holds its
+/// remittances: senders can release them and anyone can finish finalizing.
+pub const BATCH_LOCK_DURATION: u64 = 7 * 24 * 60 * 60;
+
+/// CPU instruction limit of a single transaction.
+pub const TX_CPU_INSTRUCTION_LIMIT: u64 = 100_000_000;
+
+/// Memory limit of a single transaction, in bytes.
+pub const TX_MEMORY_LIMIT: u64 = 41_943_040;
+
+// Cost model. Per-entry costs cover loading, validating and marking a
+// remittance settled including its events; per-transfer costs cover a token
+// transfer and the fee bookkeeping. These are estimates with headroom, not
+// measurements. Netting cost grows linearly with entries and quadratically
+// with distinct parties, since host maps are copied on every update; its
+// constants are fitted to the host budget, and the `bench_*` tests in
+// netting.rs check that they bound the measured cost.
+const BASE_CPU: u64 = 2_000_000;
+const BASE_MEMORY: u64 = 500_000;
+const ENTRY_CPU: u64 = 450_000;
+const ENTRY_MEMORY: u64 = 120_000;
+const TRANSFER_CPU: u64 = 450_000;
+const TRANSFER_MEMORY: u64 = 130_000;
+const BILATERAL_ENTRY_CPU: u64 = 72_000;
+const BILATERAL_ENTRY_MEMORY: u64 = 5_500;
+const BILATERAL_PARTY_PAIR_CPU: u64 = 900;
+const BILATERAL_PARTY_PAIR_MEMORY: u64 = 60;
+const MULTILATERAL_ENTRY_CPU: u64 = 112_000;
+const MULTILATERAL_ENTRY_MEMORY: u64 = 7_500;
+const MULTILATERAL_PARTY_PAIR_CPU: u64 = 1_600;
+const MULTILATERAL_PARTY_PAIR_MEMORY: u64 = 115;
+
+/// Estimated resource use of settling a batch in one transaction.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchBudgetEstimate {
+    /// Entries that would be settled
+    pub entry_count: u32,
+    /// Distinct senders and agents among those entries
+    pub party_count: u32,
+    /// Estimated CPU instructions
+    pub cpu_instructions: u64,
+    /// Estimated memory in bytes
+    pub memory_bytes: u64,
+    /// Largest number of leading entries that can safely be settled in one
+    /// transaction
+    pub max_safe_entries: u32,
+}
+
+/// Lifecycle of a multi-transaction settlement batch.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SettlementBatchStatus {
+    /// Accepting entries
+    Open,
+    /// Net transfers computed; finalization in progress
+    Finalizing,
+    /// Every transfer executed and every remittance settled
+    Completed,
+    /// Aborted by the owner before finalization; every remittance released
+    Aborted,
+}
+
+/// A settlement batch built and settled over several transactions.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementBatch {
+    /// Unique batch identifier
+    pub batch_id: u64,
+    /// Agent or settlement operator that opened the batch
+    pub owner: Address,
+    /// Ledger timestamp the batch was opened at
+    pub opened_at: u64,
+    /// Current lifecycle stage
+    pub status: SettlementBatchStatus,
+    /// Remittances appended to the batch, in append order
+    pub remittance_ids: Vec<u64>,
+    /// Net position of each party on a payout basis, including the fee collector
+    pub positions: Map<Address, i128>,
+    /// Net transfers settling the positions, computed when finalization starts
+    pub transfers: Vec<NetTransfer>,
+    /// Finalization steps completed: transfers first, then remittances
+    pub cursor: u32,
+    /// Merkle tree over the settlement IDs of the remittances settled so far
+    pub merkle: MerkleFrontier,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub(crate) enum BatchKey {
+    /// Counter for generating batch IDs (instance storage)
+    BatchCounter,
+    /// Settlement batch by ID (persistent storage)
+    Batch(u64),
+    /// Batch a remittance is locked in (persistent storage)
+    Member(u64),
+}
+
+/// Estimates the CPU instructions and memory used to settle `entries`
+/// remittances among `parties` distinct senders and agents in one transaction.
+pub fn estimate_settlement_cost(mode: NettingMode, entries: u32, parties: u32) -> (u64, u64) {
+    let (netting_cpu, netting_memory) = estimate_netting_cost(mode, entries, parties);
+
+    // Bilateral netting pays out at most once per entry, multilateral
+    // netting at most once per party, fee collector included
+    let transfers = match mode {
+        NettingMode::Bilateral => entries as u64,
+        NettingMode::Multilateral => parties as u64 + 1,
+    };
+    let entries = entries as u64;
+
+    let cpu = BASE_CPU + entries * ENTRY_CPU + transfers * TRANSFER_CPU + netting_cpu;
+    let memory = BASE_MEMORY + entries * ENTRY_MEMORY + transfers * TRANSFER_MEMORY + netting_memory;
+    (cpu, memory)
+}
+
+/// Estimates the CPU instructions and memory used to net and validate
+/// `entries` remittances among `parties` distinct senders and agents.
+pub fn estimate_netting_cost(mode: NettingMode, entries: u32, parties: u32) -> (u64, u64) {
+    let entries = entries as u64;
+    match mode {
+        NettingMode::Bilateral => {
+            let party_pairs = parties as u64 * parties as u64;
+            (
+                entries * BILATERAL_ENTRY_CPU + party_pairs * BILATERAL_PARTY_PAIR_CPU,
+                entries * BILATERAL_ENTRY_MEMORY + party_pairs * BILATERAL_PARTY_PAIR_MEMORY,
+            )
+        }
+        NettingMode::Multilateral => {
+            // Multilateral netting also keeps a position for the fee collector
+            let parties = parties as u64 + 1;
+            let party_pairs = parties * parties;
+            (
+                entries * MULTILATERAL_ENTRY_CPU + party_pairs * MULTILATERAL_PARTY_PAIR_CPU,
+                entries * MULTILATERAL_ENTRY_MEMORY + party_pairs * MULTILATERAL_PARTY_PAIR_MEMORY,
+            )
+        }
+    }
+}
+
+/// Returns whether an estimated cost fits the safe share of a transaction's
+/// budget. A quarter of each limit is kept back for the model's error and
+/// for authorization.
+pub fn fits_transaction_budget(cpu: u64, memory: u64) -> bool {
+    cpu <= TX_CPU_INSTRUCTION_LIMIT / 4 * 3 && memory <= TX_MEMORY_LIMIT / 4 * 3
+}
+
+/// Allocates the next batch ID. Single-transaction and multi-transaction
+/// batches share one ID sequence.
+pub fn next_batch_id(env: &Env) -> Result<u64, ContractError> {
+    let batch_id = get_batch_counter(env)
+        .checked_add(1)
+        .ok_or(ContractError::Overflow)?;
+    set_batch_counter(env, batch_id);
+    Ok(batch_id)
+}
+
+/// Returns the most recently allocated batch ID, 0 before the first.
+pub fn get_batch_counter(env: &Env) -> u64 {
+    env.storage().instance().get(&BatchKey::BatchCounter).unwrap_or(0)
+}
+
+/// Sets the most recently allocated batch ID.
+pub fn set_batch_counter(env: &Env, batch_id: u64) {
+    env.storage().instance().set(&BatchKey::BatchCounter, &batch_id);
+}
+
+/// Opens a new settlement batch owned by `owner`.
+pub fn open_settlement_batch(env: &Env, owner: &Address) -> Result<SettlementBatch, ContractError> {
+    let batch_id = next_batch_id(env)?;
+
+    let batch = SettlementBatch {
+        batch_id,
+        owner: owner.clone(),
+        opened_at: env.ledger().timestamp(),
+        status: SettlementBatchStatus::Open,
+        remittance_ids: Vec::new(env),
+        positions: Map::new(env),
+        transfers: Vec::new(env),
+        cursor: 0,
+        merkle: MerkleFrontier::new(env),
+    };
+    set_settlement_batch(env, &batch);
+
+    Ok(batch)
+}
+
+/// Gets a settlement batch by ID.
+pub fn get_settlement_batch(env: &Env, batch_id: u64) -> Result<SettlementBatch, ContractError> {
+    env.storage()
+        .persistent()
+        .get(&BatchKey::Batch(batch_id))
+        .ok_or(ContractError::SettlementBatchNotFound)
+}
+
+/// Stores a settlement batch.
+pub fn set_settlement_batch(env: &Env, batch: &SettlementBatch) {
+    env.storage()
+        .persistent()
+        .set(&BatchKey::Batch(batch.batch_id), batch);
+}
+
+/// Gets the batch a remittance is locked in, if any.
+pub fn get_remittance_batch(env: &Env, remittance_id: u64) -> Option<u64> {
+    env.storage().persistent().get(&BatchKey::Member(remittance_id))
+}
+
+/// Locks a remittance in a batch.
+pub fn lock_remittance_in_batch(env: &Env, remittance_id: u64, batch_id: u64) {
+    env.storage()
+        .persistent()
+        .set(&BatchKey::Member(remittance_id), &batch_id);
+}
+
+/// Releases a remittance's batch lock.
+pub fn unlock_remittance_from_batch(env: &Env, remittance_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&BatchKey::Member(remittance_id));
+}
+
+/// Returns whether `BATCH_LOCK_DURATION` has passed since the batch opened.
+pub fn batch_lock_expired(env: &Env, batch: &SettlementBatch) -> bool {
+    env.ledger().timestamp() >= batch.opened_at.saturating_add(BATCH_LOCK_DURATION)
+}
+
+/// Fails if the remittance is locked in a settlement batch.
+pub fn validate_not_batched(env: &Env, remittance_id: u64) -> Result<(), ContractError> {
+    if get_remittance_batch(env, remittance_id).is_some() {
+        return Err(ContractError::RemittanceBatched);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cost_grows_with_entries_and_parties() {
+        let (cpu_small, memory_small) = estimate_settlement_cost(NettingMode::Bilateral, 10, 4);
+        let (cpu_entries, memory_entries) = estimate_settlement_cost(NettingMode::Bilateral, 20, 4);
+        let (cpu_parties, memory_parties) = estimate_settlement_cost(NettingMode::Bilateral, 10, 20);
+
+        assert!(cpu_entries > cpu_small && memory_entries > memory_small);
+        assert!(cpu_parties > cpu_small && memory_parties > memory_small);
+    }
+
+    #[test]
+    fn test_max_batch_size_of_distinct_parties_exceeds_multilateral_budget() {
+        // Every remittance between fresh parties: 50 entries, 100 parties
+        let (cpu, memory) = estimate_settlement_cost(NettingMode::Bilateral, MAX_BATCH_SIZE, 100);
+        assert!(fits_transaction_budget(cpu, memory));
+
+        let (cpu, memory) =
+            estimate_settlement_cost(NettingMode::Multilateral, MAX_BATCH_SIZE, 100);
+        assert!(!fits_transaction_budget(cpu, memory));
+    }
+
+    #[test]
+    fn test_finalize_chunk_fits_budget() {
+        // The first finalization call nets a full batch and runs a chunk of steps
+        let parties = MAX_BATCHED_PARTIES as u64;
+        let chunk = FINALIZE_CHUNK_SIZE as u64;
+
+        let cpu = BASE_CPU
+            + parties * parties * MULTILATERAL_PARTY_PAIR_CPU
+            + chunk * ENTRY_CPU.max(TRANSFER_CPU);
+        let memory = BASE_MEMORY
+            + parties * parties * MULTILATERAL_PARTY_PAIR_MEMORY
+            + chunk * ENTRY_MEMORY.max(TRANSFER_MEMORY);
+
+        assert!(fits_transaction_budget(cpu, memory));
+    }
+}
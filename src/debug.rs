@@ -239,3 +239,18 @@ pub fn log_update_rate_limit(env: &Env, max_requests: u32, window_seconds: u64,
 /// Logs rate limit configuration update - no-op in release.
 #[cfg(not(feature = "debug-log"))]
 pub fn log_update_rate_limit(_env: &Env, _max_requests: u32, _window_seconds: u64, _enabled: bool) {}
+
+/// Logs a handled contract error in debug mode.
+#[cfg(feature = "debug-log")]
+pub fn log_error(env: &Env, severity: &str, code: u32) {
+    soroban_sdk::log!(
+        env,
+        "Error: severity={}, code={}",
+        soroban_sdk::Symbol::new(env, severity),
+        code
+    );
+}
+
+/// Logs a handled contract error - no-op in release.
+#[cfg(not(feature = "debug-log"))]
+pub fn log_error(_env: &Env, _severity: &str, _code: u32) {}
@@ -0,0 +1,139 @@
+//! Settlement delegation for the SwiftRemit contract.
+//!
+//! Batch settlement moves funds on behalf of every agent in the batch, so it
+//! must be authorized by each of them. Agents that settle through a
+//! correspondent or back-office service can instead delegate that right to a
+//! settlement operator, optionally restricted to certain corridors, a
+//! per-remittance amount cap and an expiry time.
+
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+use crate::{ContractError, Corridor, Remittance};
+
+/// Limits on what a settlement operator may settle for an agent.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DelegationScope {
+    /// Corridors the operator may settle; empty means all corridors
+    pub corridors: Vec<Corridor>,
+    /// Largest remittance amount the operator may settle, if capped
+    pub max_amount: Option<i128>,
+    /// Ledger timestamp after which the delegation no longer applies, if any
+    pub expires_at: Option<u64>,
+}
+
+/// A settlement delegation from an agent to an operator.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementDelegation {
+    /// Agent granting the delegation
+    pub agent: Address,
+    /// Operator allowed to authorize batch settlement for the agent
+    pub operator: Address,
+    /// Limits of the delegation
+    pub scope: DelegationScope,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub(crate) enum DelegationKey {
+    /// Delegation per (agent, operator) pair (persistent storage)
+    Delegation(Address, Address),
+    /// (agent, operator) pairs with a delegation, in the order they were
+    /// first granted (persistent storage)
+    Pairs,
+}
+
+/// Gets the delegation from `agent` to `operator`, if one exists.
+pub fn get_settlement_delegation(
+    env: &Env,
+    agent: &Address,
+    operator: &Address,
+) -> Option<SettlementDelegation> {
+    env.storage()
+        .persistent()
+        .get(&DelegationKey::Delegation(agent.clone(), operator.clone()))
+}
+
+/// Stores a delegation, replacing any existing one for the same pair.
+pub fn set_settlement_delegation(env: &Env, delegation: &SettlementDelegation) {
+    env.storage().persistent().set(
+        &DelegationKey::Delegation(delegation.agent.clone(), delegation.operator.clone()),
+        delegation,
+    );
+
+    let mut pairs = get_delegation_pairs(env);
+    let pair = (delegation.agent.clone(), delegation.operator.clone());
+    if !pairs.contains(&pair) {
+        pairs.push_back(pair);
+        env.storage().persistent().set(&DelegationKey::Pairs, &pairs);
+    }
+}
+
+fn get_delegation_pairs(env: &Env) -> Vec<(Address, Address)> {
+    env.storage()
+        .persistent()
+        .get(&DelegationKey::Pairs)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Lists every delegation, in the order the pairs were first granted.
+pub fn list_settlement_delegations(env: &Env) -> Vec<SettlementDelegation> {
+    let mut delegations = Vec::new(env);
+    for (agent, operator) in get_delegation_pairs(env).iter() {
+        if let Some(delegation) = get_settlement_delegation(env, &agent, &operator) {
+            delegations.push_back(delegation);
+        }
+    }
+    delegations
+}
+
+/// Removes the delegation from `agent` to `operator`.
+pub fn remove_settlement_delegation(
+    env: &Env,
+    agent: &Address,
+    operator: &Address,
+) -> Result<(), ContractError> {
+    let key = DelegationKey::Delegation(agent.clone(), operator.clone());
+    if !env.storage().persistent().has(&key) {
+        return Err(ContractError::DelegationNotFound);
+    }
+    env.storage().persistent().remove(&key);
+
+    let mut pairs = get_delegation_pairs(env);
+    if let Some(index) = pairs.first_index_of((agent.clone(), operator.clone())) {
+        pairs.remove(index);
+        env.storage().persistent().set(&DelegationKey::Pairs, &pairs);
+    }
+    Ok(())
+}
+
+/// Checks that `operator` holds a delegation from the remittance's agent
+/// whose scope covers this remittance.
+pub fn validate_delegation_covers(
+    env: &Env,
+    operator: &Address,
+    remittance: &Remittance,
+) -> Result<(), ContractError> {
+    let delegation = get_settlement_delegation(env, &remittance.agent, operator)
+        .ok_or(ContractError::SettlementNotDelegated)?;
+    let scope = delegation.scope;
+
+    if let Some(expires_at) = scope.expires_at {
+        if env.ledger().timestamp() > expires_at {
+            return Err(ContractError::SettlementNotDelegated);
+        }
+    }
+
+    if let Some(max_amount) = scope.max_amount {
+        if remittance.amount > max_amount {
+            return Err(ContractError::SettlementNotDelegated);
+        }
+    }
+
+    if !scope.corridors.is_empty() && !scope.corridors.contains(&remittance.corridor) {
+        return Err(ContractError::SettlementNotDelegated);
+    }
+
+    Ok(())
+}
@@ -1,16 +1,16 @@
+//! Centralized error handling module for the SwiftRemit contract.
+//!
+//! This module provides a single global error handler that:
+//! - Maps contract errors to structured error responses
+//! - Provides consistent error formatting
+//! - Prevents sensitive information leakage
+//! - Logs errors for debugging while keeping client responses clean
+
 #![allow(dead_code)]
 
 use soroban_sdk::{Env, String as SorobanString};
 use crate::ContractError;
 
-/// Centralized error handling module for the SwiftRemit contract.
-/// 
-/// This module provides a single global error handler that:
-/// - Maps contract errors to structured error responses
-/// - Provides consistent error formatting
-/// - Prevents sensitive information leakage
-/// - Logs errors for debugging while keeping client responses clean
-
 /// Error severity levels for logging and monitoring
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum ErrorSeverity {
@@ -111,6 +111,54 @@ impl ErrorHandler {
                 ErrorCategory::Validation,
                 ErrorSeverity::Low,
             ),
+            ContractError::InvalidMigrationHash => (
+                20,
+                SorobanString::from_str(env, "Migration data failed hash verification"),
+                ErrorCategory::Validation,
+                ErrorSeverity::High,
+            ),
+            ContractError::InvalidMigrationBatch => (
+                22,
+                SorobanString::from_str(env, "Migration batch is out of order or invalid"),
+                ErrorCategory::Validation,
+                ErrorSeverity::Medium,
+            ),
+            ContractError::IdempotencyConflict => (
+                24,
+                SorobanString::from_str(env, "Idempotency key was used with a different request"),
+                ErrorCategory::Validation,
+                ErrorSeverity::Medium,
+            ),
+            ContractError::InvalidIdempotencyKey => (
+                25,
+                SorobanString::from_str(env, "Idempotency key is empty or too long"),
+                ErrorCategory::Validation,
+                ErrorSeverity::Low,
+            ),
+            ContractError::TravelRuleCommitmentRequired => (
+                26,
+                SorobanString::from_str(env, "Travel-rule commitment required for this amount"),
+                ErrorCategory::Validation,
+                ErrorSeverity::Low,
+            ),
+            ContractError::FxQuoteMismatch => (
+                34,
+                SorobanString::from_str(env, "FX quote does not match the remittance"),
+                ErrorCategory::Validation,
+                ErrorSeverity::Low,
+            ),
+            ContractError::UnsupportedHashSchema => (
+                46,
+                SorobanString::from_str(env, "Settlement hash schema version is not supported"),
+                ErrorCategory::Validation,
+                ErrorSeverity::Low,
+            ),
+            ContractError::UnsupportedMigrationVersion => (
+                48,
+                SorobanString::from_str(env, "Migration or storage schema version is not supported"),
+                ErrorCategory::Validation,
+                ErrorSeverity::Medium,
+            ),
             
             // Resource Errors
             ContractError::AgentNotRegistered => (
@@ -149,6 +197,42 @@ impl ErrorHandler {
                 ErrorCategory::Resource,
                 ErrorSeverity::Low,
             ),
+            ContractError::TravelRuleRecordNotFound => (
+                28,
+                SorobanString::from_str(env, "Travel-rule record not found"),
+                ErrorCategory::Resource,
+                ErrorSeverity::Low,
+            ),
+            ContractError::FxQuoteNotFound => (
+                32,
+                SorobanString::from_str(env, "FX quote not found"),
+                ErrorCategory::Resource,
+                ErrorSeverity::Low,
+            ),
+            ContractError::DelegationNotFound => (
+                37,
+                SorobanString::from_str(env, "Settlement delegation not found"),
+                ErrorCategory::Resource,
+                ErrorSeverity::Low,
+            ),
+            ContractError::SettlementCycleNotFound => (
+                39,
+                SorobanString::from_str(env, "Corridor has no open settlement cycle"),
+                ErrorCategory::Resource,
+                ErrorSeverity::Low,
+            ),
+            ContractError::SettlementBatchNotFound => (
+                44,
+                SorobanString::from_str(env, "Settlement batch not found"),
+                ErrorCategory::Resource,
+                ErrorSeverity::Low,
+            ),
+            ContractError::BatchCommitmentNotFound => (
+                47,
+                SorobanString::from_str(env, "Batch commitment not found"),
+                ErrorCategory::Resource,
+                ErrorSeverity::Low,
+            ),
             
             // State Errors
             ContractError::InvalidStatus => (
@@ -187,6 +271,102 @@ impl ErrorHandler {
                 ErrorCategory::State,
                 ErrorSeverity::Low,
             ),
+            ContractError::MigrationInProgress => (
+                21,
+                SorobanString::from_str(env, "A migration is in progress"),
+                ErrorCategory::State,
+                ErrorSeverity::Low,
+            ),
+            ContractError::DailySendLimitExceeded => (
+                23,
+                SorobanString::from_str(env, "Daily send limit exceeded"),
+                ErrorCategory::State,
+                ErrorSeverity::Low,
+            ),
+            ContractError::TravelRuleAckRequired => (
+                27,
+                SorobanString::from_str(env, "Travel-rule exchange has not been acknowledged"),
+                ErrorCategory::State,
+                ErrorSeverity::Low,
+            ),
+            ContractError::FxOracleNotConfigured => (
+                29,
+                SorobanString::from_str(env, "FX price feed is not configured"),
+                ErrorCategory::State,
+                ErrorSeverity::Medium,
+            ),
+            ContractError::FxRateUnavailable => (
+                30,
+                SorobanString::from_str(env, "FX rate is unavailable or stale"),
+                ErrorCategory::State,
+                ErrorSeverity::Medium,
+            ),
+            ContractError::FxQuoteExpired => (
+                33,
+                SorobanString::from_str(env, "FX quote has expired"),
+                ErrorCategory::State,
+                ErrorSeverity::Low,
+            ),
+            ContractError::FxRateDeviation => (
+                35,
+                SorobanString::from_str(env, "FX rate moved beyond the allowed deviation"),
+                ErrorCategory::State,
+                ErrorSeverity::Low,
+            ),
+            ContractError::SettlementCycleNotConfigured => (
+                38,
+                SorobanString::from_str(env, "Corridor has no settlement cycle"),
+                ErrorCategory::State,
+                ErrorSeverity::Low,
+            ),
+            ContractError::SettlementCycleNotDue => (
+                40,
+                SorobanString::from_str(env, "Settlement cycle cannot be closed yet"),
+                ErrorCategory::State,
+                ErrorSeverity::Low,
+            ),
+            ContractError::SettlementCycleFull => (
+                41,
+                SorobanString::from_str(env, "Settlement cycle is full"),
+                ErrorCategory::State,
+                ErrorSeverity::Low,
+            ),
+            ContractError::RemittanceQueued => (
+                42,
+                SorobanString::from_str(env, "Remittance is queued in a settlement cycle"),
+                ErrorCategory::State,
+                ErrorSeverity::Low,
+            ),
+            ContractError::RemittanceBatched => (
+                43,
+                SorobanString::from_str(env, "Remittance is locked in a settlement batch"),
+                ErrorCategory::State,
+                ErrorSeverity::Low,
+            ),
+            ContractError::InvalidBatchState => (
+                45,
+                SorobanString::from_str(env, "Settlement batch does not allow this operation"),
+                ErrorCategory::State,
+                ErrorSeverity::Low,
+            ),
+            ContractError::InvalidMigrationState => (
+                49,
+                SorobanString::from_str(env, "Migration session does not allow this operation"),
+                ErrorCategory::State,
+                ErrorSeverity::Medium,
+            ),
+            ContractError::RateLimitExceeded => (
+                54,
+                SorobanString::from_str(env, "Rate limit exceeded, try again later"),
+                ErrorCategory::State,
+                ErrorSeverity::Low,
+            ),
+            ContractError::InvalidTtl => (
+                55,
+                SorobanString::from_str(env, "Time window is zero or too long"),
+                ErrorCategory::Validation,
+                ErrorSeverity::Low,
+            ),
             
             // Authorization Errors
             ContractError::Unauthorized => (
@@ -195,6 +375,12 @@ impl ErrorHandler {
                 ErrorCategory::Authorization,
                 ErrorSeverity::Medium,
             ),
+            ContractError::SettlementNotDelegated => (
+                36,
+                SorobanString::from_str(env, "Operator is not delegated to settle this remittance"),
+                ErrorCategory::Authorization,
+                ErrorSeverity::Medium,
+            ),
             
             // System Errors
             ContractError::Overflow => (
@@ -211,22 +397,12 @@ impl ErrorHandler {
     /// Logs are only available in debug builds and never exposed to clients.
     /// This prevents stack traces and sensitive information from leaking.
     fn log_error(env: &Env, error: ContractError, severity: ErrorSeverity) {
-        #[cfg(any(test, feature = "testutils"))]
-        {
-            use crate::debug::log_error as debug_log;
-            let severity_str = match severity {
-                ErrorSeverity::Low => "LOW",
-                ErrorSeverity::Medium => "MEDIUM",
-                ErrorSeverity::High => "HIGH",
-            };
-            debug_log(env, &format!("[{}] Error: {:?}", severity_str, error));
-        }
-        
-        // In production, errors are not logged to prevent information leakage
-        #[cfg(not(any(test, feature = "testutils")))]
-        {
-            let _ = (env, error, severity); // Suppress unused variable warnings
-        }
+        let severity_str = match severity {
+            ErrorSeverity::Low => "LOW",
+            ErrorSeverity::Medium => "MEDIUM",
+            ErrorSeverity::High => "HIGH",
+        };
+        crate::debug::log_error(env, severity_str, error as u32);
     }
     
     /// Get error category for an error
@@ -234,9 +410,19 @@ impl ErrorHandler {
         match error {
             ContractError::InvalidAmount
             | ContractError::InvalidFeeBps
-            | ContractError::InvalidAddress => ErrorCategory::Validation,
+            | ContractError::InvalidAddress
+            | ContractError::InvalidMigrationHash
+            | ContractError::InvalidMigrationBatch
+            | ContractError::IdempotencyConflict
+            | ContractError::InvalidIdempotencyKey
+            | ContractError::TravelRuleCommitmentRequired
+            | ContractError::FxQuoteMismatch
+            | ContractError::UnsupportedHashSchema
+            | ContractError::UnsupportedMigrationVersion
+            | ContractError::InvalidTtl => ErrorCategory::Validation,
             
-            ContractError::Unauthorized => ErrorCategory::Authorization,
+            ContractError::Unauthorized
+            | ContractError::SettlementNotDelegated => ErrorCategory::Authorization,
             
             ContractError::AlreadyInitialized
             | ContractError::NotInitialized
@@ -245,14 +431,35 @@ impl ErrorHandler {
             | ContractError::DuplicateSettlement
             | ContractError::ContractPaused
             | ContractError::NoFeesToWithdraw
-            | ContractError::CannotRemoveLastAdmin => ErrorCategory::State,
+            | ContractError::CannotRemoveLastAdmin
+            | ContractError::MigrationInProgress
+            | ContractError::DailySendLimitExceeded
+            | ContractError::TravelRuleAckRequired
+            | ContractError::FxOracleNotConfigured
+            | ContractError::FxRateUnavailable
+            | ContractError::FxQuoteExpired
+            | ContractError::FxRateDeviation
+            | ContractError::SettlementCycleNotConfigured
+            | ContractError::SettlementCycleNotDue
+            | ContractError::SettlementCycleFull
+            | ContractError::RemittanceQueued
+            | ContractError::RemittanceBatched
+            | ContractError::InvalidBatchState
+            | ContractError::InvalidMigrationState
+            | ContractError::RateLimitExceeded => ErrorCategory::State,
             
             ContractError::AgentNotRegistered
             | ContractError::RemittanceNotFound
             | ContractError::AdminNotFound
             | ContractError::AdminAlreadyExists
             | ContractError::TokenNotWhitelisted
-            | ContractError::TokenAlreadyWhitelisted => ErrorCategory::Resource,
+            | ContractError::TokenAlreadyWhitelisted
+            | ContractError::TravelRuleRecordNotFound
+            | ContractError::FxQuoteNotFound
+            | ContractError::DelegationNotFound
+            | ContractError::SettlementCycleNotFound
+            | ContractError::SettlementBatchNotFound
+            | ContractError::BatchCommitmentNotFound => ErrorCategory::Resource,
             
             ContractError::Overflow => ErrorCategory::System,
         }
@@ -276,15 +483,46 @@ impl ErrorHandler {
             | ContractError::CannotRemoveLastAdmin
             | ContractError::TokenNotWhitelisted
             | ContractError::TokenAlreadyWhitelisted
-            | ContractError::AlreadyInitialized => ErrorSeverity::Low,
+            | ContractError::AlreadyInitialized
+            | ContractError::MigrationInProgress
+            | ContractError::DailySendLimitExceeded
+            | ContractError::InvalidIdempotencyKey
+            | ContractError::TravelRuleCommitmentRequired
+            | ContractError::TravelRuleAckRequired
+            | ContractError::TravelRuleRecordNotFound
+            | ContractError::FxQuoteNotFound
+            | ContractError::FxQuoteExpired
+            | ContractError::FxQuoteMismatch
+            | ContractError::FxRateDeviation
+            | ContractError::DelegationNotFound
+            | ContractError::SettlementCycleNotConfigured
+            | ContractError::SettlementCycleNotFound
+            | ContractError::SettlementCycleNotDue
+            | ContractError::SettlementCycleFull
+            | ContractError::RemittanceQueued
+            | ContractError::RemittanceBatched
+            | ContractError::SettlementBatchNotFound
+            | ContractError::InvalidBatchState
+            | ContractError::UnsupportedHashSchema
+            | ContractError::BatchCommitmentNotFound
+            | ContractError::RateLimitExceeded
+            | ContractError::InvalidTtl => ErrorSeverity::Low,
             
             // Medium severity - unexpected but recoverable
             ContractError::NotInitialized
             | ContractError::DuplicateSettlement
-            | ContractError::Unauthorized => ErrorSeverity::Medium,
+            | ContractError::Unauthorized
+            | ContractError::InvalidMigrationBatch
+            | ContractError::IdempotencyConflict
+            | ContractError::FxOracleNotConfigured
+            | ContractError::FxRateUnavailable
+            | ContractError::SettlementNotDelegated
+            | ContractError::UnsupportedMigrationVersion
+            | ContractError::InvalidMigrationState => ErrorSeverity::Medium,
             
             // High severity - critical system errors
-            ContractError::Overflow => ErrorSeverity::High,
+            ContractError::Overflow
+            | ContractError::InvalidMigrationHash => ErrorSeverity::High,
         }
     }
     
@@ -292,7 +530,15 @@ impl ErrorHandler {
     pub fn is_retryable(error: ContractError) -> bool {
         match error {
             // Transient errors that might succeed on retry
-            ContractError::ContractPaused => true,
+            ContractError::ContractPaused
+            | ContractError::MigrationInProgress
+            | ContractError::DailySendLimitExceeded
+            | ContractError::TravelRuleAckRequired
+            | ContractError::FxRateUnavailable
+            | ContractError::SettlementCycleNotDue
+            | ContractError::SettlementCycleFull
+            | ContractError::RemittanceBatched
+            | ContractError::RateLimitExceeded => true,
             
             // Permanent errors that won't succeed on retry
             ContractError::AlreadyInitialized
@@ -312,7 +558,30 @@ impl ErrorHandler {
             | ContractError::AdminNotFound
             | ContractError::CannotRemoveLastAdmin
             | ContractError::TokenNotWhitelisted
-            | ContractError::TokenAlreadyWhitelisted => false,
+            | ContractError::TokenAlreadyWhitelisted
+            | ContractError::InvalidMigrationHash
+            | ContractError::InvalidMigrationBatch
+            | ContractError::IdempotencyConflict
+            | ContractError::InvalidIdempotencyKey
+            | ContractError::TravelRuleCommitmentRequired
+            | ContractError::TravelRuleRecordNotFound
+            | ContractError::FxOracleNotConfigured
+            | ContractError::FxQuoteNotFound
+            | ContractError::FxQuoteExpired
+            | ContractError::FxQuoteMismatch
+            | ContractError::FxRateDeviation
+            | ContractError::SettlementNotDelegated
+            | ContractError::DelegationNotFound
+            | ContractError::SettlementCycleNotConfigured
+            | ContractError::SettlementCycleNotFound
+            | ContractError::RemittanceQueued
+            | ContractError::SettlementBatchNotFound
+            | ContractError::InvalidBatchState
+            | ContractError::UnsupportedHashSchema
+            | ContractError::BatchCommitmentNotFound
+            | ContractError::UnsupportedMigrationVersion
+            | ContractError::InvalidMigrationState
+            | ContractError::InvalidTtl => false,
         }
     }
     
@@ -331,7 +600,7 @@ impl ErrorHandler {
 /// Helper macro for consistent error handling in contract functions
 /// 
 /// Usage:
-/// ```
+/// ```ignore
 /// handle_contract_error!(env, operation_result)
 /// ```
 #[macro_export]
@@ -352,6 +621,8 @@ pub type ContractResult<T> = Result<T, ContractError>;
 
 #[cfg(test)]
 mod tests {
+    extern crate std;
+
     use super::*;
     use soroban_sdk::Env;
 
@@ -447,7 +718,7 @@ mod tests {
     #[test]
     fn test_all_errors_have_unique_codes() {
         let env = Env::default();
-        let errors = vec![
+        let errors = [
             ContractError::AlreadyInitialized,
             ContractError::NotInitialized,
             ContractError::InvalidAmount,
@@ -467,6 +738,37 @@ mod tests {
             ContractError::CannotRemoveLastAdmin,
             ContractError::TokenNotWhitelisted,
             ContractError::TokenAlreadyWhitelisted,
+            ContractError::InvalidMigrationHash,
+            ContractError::MigrationInProgress,
+            ContractError::InvalidMigrationBatch,
+            ContractError::DailySendLimitExceeded,
+            ContractError::IdempotencyConflict,
+            ContractError::InvalidIdempotencyKey,
+            ContractError::TravelRuleCommitmentRequired,
+            ContractError::TravelRuleAckRequired,
+            ContractError::TravelRuleRecordNotFound,
+            ContractError::FxOracleNotConfigured,
+            ContractError::FxRateUnavailable,
+            ContractError::FxQuoteNotFound,
+            ContractError::FxQuoteExpired,
+            ContractError::FxQuoteMismatch,
+            ContractError::FxRateDeviation,
+            ContractError::SettlementNotDelegated,
+            ContractError::DelegationNotFound,
+            ContractError::SettlementCycleNotConfigured,
+            ContractError::SettlementCycleNotFound,
+            ContractError::SettlementCycleNotDue,
+            ContractError::SettlementCycleFull,
+            ContractError::RemittanceQueued,
+            ContractError::RemittanceBatched,
+            ContractError::SettlementBatchNotFound,
+            ContractError::InvalidBatchState,
+            ContractError::UnsupportedHashSchema,
+            ContractError::BatchCommitmentNotFound,
+            ContractError::UnsupportedMigrationVersion,
+            ContractError::InvalidMigrationState,
+            ContractError::RateLimitExceeded,
+            ContractError::InvalidTtl,
         ];
 
         let mut codes = std::collections::HashSet::new();
@@ -482,7 +784,10 @@ mod tests {
         
         // Messages should not contain technical jargon or stack traces
         let response = ErrorHandler::handle_error(&env, ContractError::InvalidAmount);
-        let message_str = response.message.to_string();
+        let mut bytes = [0u8; 128];
+        let len = response.message.len() as usize;
+        response.message.copy_into_slice(&mut bytes[..len]);
+        let message_str = core::str::from_utf8(&bytes[..len]).unwrap();
         assert!(!message_str.contains("panic"));
         assert!(!message_str.contains("stack"));
         assert!(!message_str.contains("trace"));
@@ -10,16 +10,58 @@ use soroban_sdk::contracterror;
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
 pub enum ContractError {
+    /// Contract has already been initialized.
+    /// Cause: Calling initialize() more than once.
+    AlreadyInitialized = 1,
 
+    /// Contract has not been initialized yet.
+    /// Cause: Calling a contract function before initialize().
+    NotInitialized = 2,
+
+    /// Amount is zero or negative.
+    /// Cause: Passing a non-positive amount to create_remittance() or lock_quote().
+    InvalidAmount = 3,
+
+    /// Fee basis points are out of range.
+    /// Cause: Setting a fee above 10000 bps (100%).
+    InvalidFeeBps = 4,
+
+    /// Agent is not registered.
+    /// Cause: Creating a remittance for, or delegating from, an unregistered agent.
+    AgentNotRegistered = 5,
+
+    /// Remittance does not exist.
+    /// Cause: Passing a remittance ID that was never created.
+    RemittanceNotFound = 6,
+
+    /// Remittance status does not allow the operation.
+    /// Cause: Confirming, cancelling or finalizing a remittance in the wrong status.
+    InvalidStatus = 7,
+
+    /// Arithmetic overflow.
+    /// Cause: Amounts or fee totals that cannot be represented.
+    Overflow = 8,
+
+    /// No fees are available to withdraw.
+    /// Cause: Calling withdraw_fees() while accumulated fees are zero.
+    NoFeesToWithdraw = 9,
+
+    /// Address failed validation.
+    /// Cause: Passing a malformed address, or an operator equal to the delegating agent.
+    InvalidAddress = 10,
+
+    /// Settlement window has expired.
+    /// Cause: Confirming a remittance after its expiry timestamp.
+    SettlementExpired = 11,
+
+    /// Settlement has already been executed.
+    /// Cause: Confirming a remittance whose settlement hash is already recorded.
     DuplicateSettlement = 12,
 
     /// Contract is paused. Settlements are temporarily disabled.
     /// Cause: Attempting confirm_payout() while contract is in paused state.
     ContractPaused = 13,
-    
-    /// Rate limit exceeded. Sender must wait before submitting another settlement.
-    /// Cause: Attempting confirm_payout() before cooldown period has elapsed.
-    RateLimitExceeded = 14,
+
     /// Caller is not authorized to perform admin operations.
     /// Cause: Non-admin attempting to perform admin-only operations.
     Unauthorized = 14,
@@ -45,18 +87,134 @@ pub enum ContractError {
     TokenAlreadyWhitelisted = 19,
 
     /// Migration hash verification failed.
-    /// Cause: Snapshot hash doesn't match computed hash (data tampering or corruption).
+    /// Cause: Snapshot or batch hash doesn't match computed hash (data tampering or
+    /// corruption), or a batch's proof does not place it under the session's root.
     InvalidMigrationHash = 20,
 
     /// Migration already in progress or completed.
-    /// Cause: Attempting to start migration when one is already active.
+    /// Cause: Attempting to start migration when one is already active, or calling a
+    /// business operation while a migration session is open.
     MigrationInProgress = 21,
 
     /// Migration batch out of order or invalid.
-    /// Cause: Importing batches in wrong order or invalid batch number.
+    /// Cause: Importing batches in wrong order or invalid batch number, or exporting
+    /// a full snapshot of a contract larger than `MAX_SNAPSHOT_ENTRIES`.
     InvalidMigrationBatch = 22,
     
     /// Daily send limit exceeded for this user.
     /// Cause: User's total transfers in the last 24 hours exceed the configured limit.
     DailySendLimitExceeded = 23,
+
+    /// Idempotency key was already used with different request parameters.
+    /// Cause: Retrying create_remittance() with the same key but a changed payload.
+    IdempotencyConflict = 24,
+
+    /// Idempotency key is malformed.
+    /// Cause: Key is empty or longer than MAX_IDEMPOTENCY_KEY_LEN bytes.
+    InvalidIdempotencyKey = 25,
+
+    /// Travel-rule commitment missing for a transfer at or above the corridor threshold.
+    /// Cause: Calling create_remittance() without a travel-rule hash above the threshold.
+    TravelRuleCommitmentRequired = 26,
+
+    /// Travel-rule exchange has not been acknowledged by the agent.
+    /// Cause: Attempting confirm_payout() before acknowledge_travel_rule().
+    TravelRuleAckRequired = 27,
+
+    /// No travel-rule commitment exists for this remittance.
+    /// Cause: Acknowledging a remittance that was created without travel-rule data.
+    TravelRuleRecordNotFound = 28,
+
+    /// FX price feed has not been configured.
+    /// Cause: Calling lock_quote() before set_fx_config().
+    FxOracleNotConfigured = 29,
+
+    /// FX price feed returned no usable rate for the corridor.
+    /// Cause: Oracle does not support the currency pair, returned a non-positive rate, or
+    /// has not updated the pair within max_staleness seconds.
+    FxRateUnavailable = 30,
+
+    /// FX quote does not exist or has already been used.
+    /// Cause: Passing an unknown, consumed or evicted quote ID to create_remittance().
+    FxQuoteNotFound = 32,
+
+    /// FX quote has expired.
+    /// Cause: Using a quote after its TTL has elapsed.
+    FxQuoteExpired = 33,
+
+    /// FX quote does not match the remittance being created.
+    /// Cause: Different sender, corridor or amount than the quote was locked for.
+    FxQuoteMismatch = 34,
+
+    /// Market rate moved beyond the allowed deviation since the quote was locked.
+    /// Cause: Oracle rate differs from the locked rate by more than max_deviation_bps.
+    FxRateDeviation = 35,
+
+    /// Settlement operator is not delegated to settle the remittance.
+    /// Cause: Batch settling through an operator without a delegation from the agent that covers the remittance's corridor, amount or time.
+    SettlementNotDelegated = 36,
+
+    /// Settlement delegation does not exist.
+    /// Cause: Revoking a delegation that was never granted or was already revoked.
+    DelegationNotFound = 37,
+
+    /// Corridor has no settlement cycle configured.
+    /// Cause: Calling confirm_for_cycle() for a remittance whose corridor settles per payout.
+    SettlementCycleNotConfigured = 38,
+
+    /// Corridor has no open settlement cycle.
+    /// Cause: Calling close_cycle() before any remittance was confirmed into a cycle.
+    SettlementCycleNotFound = 39,
+
+    /// Settlement cycle cannot be closed yet.
+    /// Cause: Calling close_cycle() before the cycle's window has elapsed while it still has room.
+    SettlementCycleNotDue = 40,
+
+    /// Settlement cycle has reached MAX_BATCH_SIZE remittances.
+    /// Cause: Calling confirm_for_cycle() on a full cycle that has not been closed yet.
+    SettlementCycleFull = 41,
+
+    /// Remittance is queued in a settlement cycle.
+    /// Cause: Confirming, batch settling or cancelling a remittance already confirmed into a cycle.
+    RemittanceQueued = 42,
+
+    /// Remittance is locked in a multi-transaction settlement batch.
+    /// Cause: Confirming, settling or cancelling a remittance appended to a batch that has not finished settling.
+    RemittanceBatched = 43,
+
+    /// Settlement batch does not exist.
+    /// Cause: Appending to or finalizing a batch ID that was never opened.
+    SettlementBatchNotFound = 44,
+
+    /// Settlement batch or batched remittance is not in a state that allows the operation.
+    /// Cause: Appending to a batch that is being finalized, finalizing a batch that has completed,
+    /// or releasing a remittance that is not batched or whose batch lock has not expired.
+    InvalidBatchState = 45,
+
+    /// Settlement ID hash schema version is not supported.
+    /// Cause: Calling verify_settlement_id() with a schema version other than 1 or 2.
+    UnsupportedHashSchema = 46,
+
+    /// Batch has no Merkle commitment.
+    /// Cause: Calling verify_inclusion() for a batch ID that was never settled.
+    BatchCommitmentNotFound = 47,
+
+    /// Migration snapshot or storage schema version is not supported.
+    /// Cause: Importing a snapshot exported with a different snapshot schema, or calling
+    /// migrate_storage() after the contract was downgraded to older code.
+    UnsupportedMigrationVersion = 48,
+
+    /// Migration session is missing or not ready for the operation.
+    /// Cause: Importing, aborting or finalizing without an open session, or finalizing
+    /// before every declared batch was imported.
+    InvalidMigrationState = 49,
+
+    /// Rate limit exceeded. Sender must wait before submitting another settlement.
+    /// Cause: Attempting confirm_payout() before cooldown period has elapsed.
+    RateLimitExceeded = 54,
+
+    /// Retention or validity window is zero or above its maximum.
+    /// Cause: Setting an idempotency TTL of zero or above MAX_IDEMPOTENCY_TTL, or an FX
+    /// quote TTL of zero or above MAX_QUOTE_TTL or a zero FX staleness limit.
+    InvalidTtl = 55,
 }
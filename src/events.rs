@@ -1,16 +1,686 @@
 //! Event emission functions for the SwiftRemit contract.
 //!
-//! This module provides functions to emit structured events for all significant
-//! contract operations. Events include schema versioning and ledger metadata
-//! for comprehensive audit trails.
+//! Every event publishes a single `ContractEvent` as its data: a common
+//! `EventEnvelope` followed by a typed `EventPayload`. Both are contract
+//! types, so events decode by field name rather than by position, and adding
+//! a field to a payload does not shift the others.
+//!
+//! ## Envelope
+//!
+//! - `schema_version` — `EVENT_SCHEMA_VERSION`
+//! - `sequence` — per-contract event counter, starting at 1 and incremented
+//!   by every event, so a consumer can detect missed events
+//! - `ledger`, `timestamp` — ledger sequence and timestamp of emission
+//! - `actor` — address that caused the event: the admin, sender, agent or
+//!   operator that called the contract, or the contract itself for actions
+//!   anyone can trigger
+//! - `audit_sequence`, `audit_hash` — the audit head when the event was
+//!   emitted, so every event can be tied to the audit chain position of the
+//!   operation that caused it
+//!
+//! ## Topics
+//!
+//! Topics are `(category, action, subject)`. The subject is the remittance ID
+//! for remittance events, the affected address for role, agent and token
+//! events, and the batch, cycle, quote or migration batch number for those
+//! events. Contract-wide events (initialization, pause, configuration,
+//! audit, upgrade, migration) have only `(category, action)`.
+//!
+//! Configuration events carry the value before and after the update, so a
+//! consumer can mirror the configuration without reading contract storage.
+//! A previous value that may be unset is an `Option` for primitives and a
+//! `Vec` of at most one element for contract structs: soroban-sdk 21 converts
+//! `Option<T>` to `ScVal` only when `T` converts infallibly, which contract
+//! structs do not, so an `Option` of a struct fails to build with testutils.
+//!
+//! `get_event_sequence` returns the sequence number of the latest event.
+//!
+//! ## Exactly Once
+//!
+//! Each logical action is announced by one event. Role, token and
+//! configuration events are emitted only when the stored state changes, so
+//! repeating a call that changes nothing emits nothing. Settlements, batch
+//! settlements and cycle closes can be reached from several code paths, so
+//! their events go through `emit_once`, which records each `EventAction` it
+//! has announced and skips it afterwards. The events each entry point emits
+//! are listed in INTEGRATION.md.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Bytes, BytesN, Env, String, Topics, Vec};
+
+use crate::{
+    get_audit_head, has_settlement_event_emitted, set_settlement_event_emitted, Corridor, FxConfig,
+    RateLimitConfig, SettlementCycleConfig,
+};
+
+/// Schema version of the event envelope and payloads
+pub const EVENT_SCHEMA_VERSION: u32 = 4;
+
+/// Metadata carried by every event.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventEnvelope {
+    /// Event schema version
+    pub schema_version: u32,
+    /// Per-contract event sequence number, starting at 1
+    pub sequence: u64,
+    /// Ledger sequence the event was emitted in
+    pub ledger: u32,
+    /// Ledger timestamp the event was emitted at
+    pub timestamp: u64,
+    /// Address that caused the event
+    pub actor: Address,
+    /// Sequence number of the latest audit record at emission
+    pub audit_sequence: u64,
+    /// Audit head hash at emission
+    pub audit_hash: BytesN<32>,
+}
+
+/// Data published with every event.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractEvent {
+    /// Common event metadata
+    pub envelope: EventEnvelope,
+    /// Event-specific data
+    pub payload: EventPayload,
+}
+
+/// Event-specific data, one variant per event.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EventPayload {
+    /// Contract initialized
+    Initialized(InitializedEvent),
+    /// Contract paused
+    Paused,
+    /// Contract unpaused
+    Unpaused,
+    /// Admin role granted to the address
+    AdminAdded(Address),
+    /// Admin role revoked from the address
+    AdminRemoved(Address),
+    /// Remittance created
+    RemittanceCreated(RemittanceCreatedEvent),
+    /// Remittance cancelled and refunded to the sender
+    RemittanceCancelled(RemittanceTransferEvent),
+    /// Settled remittance finalized; carries the remittance ID
+    RemittanceFinalized(u64),
+    /// Memo attached to a pending remittance
+    MemoSet(MemoSetEvent),
+    /// Agent registered
+    AgentRegistered(Address),
+    /// Agent removed
+    AgentRemoved(Address),
+    /// Agent delegated batch settlement to the operator
+    DelegationGranted(Address),
+    /// Agent revoked the operator's delegation
+    DelegationRevoked(Address),
+    /// Platform fee updated
+    FeeUpdated(FeeUpdatedEvent),
+    /// Accumulated fees withdrawn
+    FeesWithdrawn(FeesWithdrawnEvent),
+    /// Integrator fee updated
+    IntegratorFeeUpdated(FeeUpdatedEvent),
+    /// Settlement cooldown updated
+    RateLimitUpdated(RateLimitUpdatedEvent),
+    /// Request rate limit window updated
+    RateLimitConfigUpdated(RateLimitConfigUpdatedEvent),
+    /// Idempotency key retention updated
+    IdempotencyTtlUpdated(IdempotencyTtlUpdatedEvent),
+    /// Daily send limit of a currency-country pair updated
+    DailyLimitUpdated(DailyLimitUpdatedEvent),
+    /// Travel-rule threshold of a corridor updated
+    TravelRuleThresholdUpdated(TravelRuleThresholdUpdatedEvent),
+    /// Settlement cycle configuration of a corridor updated
+    SettlementCycleUpdated(SettlementCycleUpdatedEvent),
+    /// FX configuration updated
+    FxConfigUpdated(FxConfigUpdatedEvent),
+    /// Token added to the whitelist
+    TokenWhitelisted(Address),
+    /// Token removed from the whitelist
+    TokenRemoved(Address),
+    /// Settlement finalized
+    SettlementCompleted(SettlementCompletedEvent),
+    /// Settlement receipt issued
+    ReceiptIssued(ReceiptIssuedEvent),
+    /// Remittance confirmed into a settlement cycle
+    RemittanceQueued(RemittanceQueuedEvent),
+    /// Settlement cycle closed and settled
+    CycleClosed(CycleClosedEvent),
+    /// Multi-transaction settlement batch opened
+    BatchOpened(u64),
+    /// Remittances appended to a multi-transaction settlement batch
+    BatchAppended(BatchAppendedEvent),
+    /// Multi-transaction settlement batch settled
+    BatchSettled(BatchSettledEvent),
+    /// Multi-transaction settlement batch aborted by its owner
+    BatchAborted(BatchReleasedEvent),
+    /// Remittance released by its sender from an expired batch
+    BatchReleased(BatchReleasedEvent),
+    /// Merkle commitment of a settled batch stored
+    BatchCommitted(BatchCommittedEvent),
+    /// Record appended to the audit chain
+    AuditRecord(AuditRecordEvent),
+    /// Contract code replaced; carries the new WASM hash
+    ContractUpgraded(BytesN<32>),
+    /// Remittances rewritten in the current storage layout
+    StorageRewritten(StorageRewrittenEvent),
+    /// Stored data migrated to a newer storage schema
+    StorageMigrated(StorageMigratedEvent),
+    /// Migration snapshot imported
+    MigrationImported(MigrationImportedEvent),
+    /// Migration session opened
+    MigrationSessionOpened(MigrationSessionOpenedEvent),
+    /// Migration batch imported into the open session
+    MigrationBatchImported(MigrationBatchImportedEvent),
+    /// Migration session finalized; carries the restored remittance counter
+    MigrationSessionFinalized(u64),
+    /// Migration session aborted
+    MigrationSessionAborted(MigrationSessionAbortedEvent),
+    /// Travel-rule commitment recorded at creation
+    TravelRuleCommitted(TravelRuleCommittedEvent),
+    /// Travel-rule exchange acknowledged by the agent
+    TravelRuleAcknowledged(TravelRuleAcknowledgedEvent),
+    /// FX quote locked
+    FxQuoteLocked(FxQuoteLockedEvent),
+}
+
+/// Payload of a contract initialization.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InitializedEvent {
+    /// Token remittances are made in
+    pub usdc_token: Address,
+    /// Platform fee rate, in basis points
+    pub fee_bps: u32,
+    /// Settlement cooldown, in seconds
+    pub rate_limit_cooldown: u64,
+}
+
+/// Payload of a remittance creation.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RemittanceCreatedEvent {
+    /// ID of the created remittance
+    pub remittance_id: u64,
+    /// Address of the sender
+    pub sender: Address,
+    /// Address of the assigned agent
+    pub agent: Address,
+    /// Total remittance amount
+    pub amount: i128,
+    /// Platform fee deducted
+    pub fee: i128,
+    /// Integrator fee deducted
+    pub integrator_fee: i128,
+}
+
+/// Payload of a remittance refund.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RemittanceTransferEvent {
+    /// ID of the remittance
+    pub remittance_id: u64,
+    /// Address of the sender
+    pub sender: Address,
+    /// Address of the agent
+    pub agent: Address,
+    /// Token transferred
+    pub asset: Address,
+    /// Amount refunded to the sender
+    pub amount: i128,
+}
+
+/// Payload of a memo attached to a remittance.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MemoSetEvent {
+    /// ID of the remittance
+    pub remittance_id: u64,
+    /// Attached memo
+    pub memo: BytesN<32>,
+}
+
+/// Payload of a platform or integrator fee update.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeUpdatedEvent {
+    /// Fee rate before the update, in basis points
+    pub old_fee_bps: u32,
+    /// Fee rate after the update, in basis points
+    pub new_fee_bps: u32,
+}
 
-use soroban_sdk::{symbol_short, Address, Env};
+/// Payload of a fee withdrawal.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeesWithdrawnEvent {
+    /// Address that received the fees
+    pub to: Address,
+    /// Token withdrawn
+    pub asset: Address,
+    /// Amount withdrawn
+    pub amount: i128,
+}
+
+/// Payload of a settlement cooldown update.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RateLimitUpdatedEvent {
+    /// Cooldown before the update, in seconds
+    pub old_cooldown: u64,
+    /// Cooldown after the update, in seconds
+    pub new_cooldown: u64,
+}
+
+/// Payload of a request rate limit window update.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RateLimitConfigUpdatedEvent {
+    /// Configuration before the update
+    pub old_config: RateLimitConfig,
+    /// Configuration after the update
+    pub new_config: RateLimitConfig,
+}
+
+/// Payload of an idempotency key retention update.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IdempotencyTtlUpdatedEvent {
+    /// Retention before the update, in seconds
+    pub old_ttl: u64,
+    /// Retention after the update, in seconds
+    pub new_ttl: u64,
+}
 
-/// Schema version for event structure compatibility
-const SCHEMA_VERSION: u32 = 1;
+/// Payload of a daily send limit update.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DailyLimitUpdatedEvent {
+    /// Normalized currency code
+    pub currency: String,
+    /// Normalized country code
+    pub country: String,
+    /// Limit before the update, if one was configured
+    pub old_limit: Option<i128>,
+    /// Limit after the update
+    pub new_limit: i128,
+}
+
+/// Payload of a travel-rule threshold update.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TravelRuleThresholdUpdatedEvent {
+    /// Corridor the threshold applies to
+    pub corridor: Corridor,
+    /// Threshold before the update, if one was configured
+    pub old_threshold: Option<i128>,
+    /// Threshold after the update
+    pub new_threshold: i128,
+}
+
+/// Payload of a settlement cycle configuration update.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementCycleUpdatedEvent {
+    /// Corridor the configuration applies to
+    pub corridor: Corridor,
+    /// Configuration before the update; empty if the corridor did not
+    /// settle in cycles
+    pub old_config: Vec<SettlementCycleConfig>,
+    /// Configuration after the update
+    pub new_config: SettlementCycleConfig,
+}
+
+/// Payload of an FX configuration update.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FxConfigUpdatedEvent {
+    /// Configuration before the update; empty if FX was not configured
+    pub old_config: Vec<FxConfig>,
+    /// Configuration after the update
+    pub new_config: FxConfig,
+}
+
+/// Payload of a finalized settlement.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementCompletedEvent {
+    /// ID of the settled remittance
+    pub remittance_id: u64,
+    /// Canonical settlement ID (see `hashing.rs`)
+    pub settlement_id: BytesN<32>,
+    /// Address of the sender
+    pub sender: Address,
+    /// Address of the receiving agent
+    pub receiver: Address,
+    /// Token transferred
+    pub asset: Address,
+    /// Amount paid out
+    pub amount: i128,
+}
+
+/// Payload of an issued settlement receipt.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReceiptIssuedEvent {
+    /// ID of the settled remittance
+    pub remittance_id: u64,
+    /// Hash of the receipt's canonical encoding (see `hashing.rs`)
+    pub receipt_hash: BytesN<32>,
+}
+
+/// Payload of a remittance confirmed into a settlement cycle.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RemittanceQueuedEvent {
+    /// ID of the cycle the remittance joined
+    pub cycle_id: u64,
+    /// ID of the queued remittance
+    pub remittance_id: u64,
+}
+
+/// Payload of a closed settlement cycle.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CycleClosedEvent {
+    /// ID of the closed cycle
+    pub cycle_id: u64,
+    /// Corridor the cycle settled
+    pub corridor: Corridor,
+    /// Tokens paid out without netting (sum of amount minus fees)
+    pub gross_volume: i128,
+    /// Amount transferred on-chain after netting
+    pub net_volume: i128,
+    /// Platform and integrator fees collected by the cycle
+    pub fees: i128,
+}
+
+/// Payload of remittances appended to a multi-transaction batch.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchAppendedEvent {
+    /// ID of the batch
+    pub batch_id: u64,
+    /// IDs of the appended remittances, in order
+    pub remittance_ids: Vec<u64>,
+}
+
+/// Payload of remittances released from a multi-transaction batch.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchReleasedEvent {
+    /// ID of the batch
+    pub batch_id: u64,
+    /// IDs of the released remittances, in order
+    pub remittance_ids: Vec<u64>,
+}
+
+/// Payload of a settled multi-transaction batch.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchSettledEvent {
+    /// ID of the settled batch
+    pub batch_id: u64,
+    /// Number of remittances the batch settled
+    pub remittance_count: u32,
+    /// Number of net transfers the batch executed
+    pub transfer_count: u32,
+}
+
+/// Payload of a stored batch commitment.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchCommittedEvent {
+    /// ID of the committed batch
+    pub batch_id: u64,
+    /// Root of the tree over the batch's settlement IDs
+    pub merkle_root: BytesN<32>,
+    /// Number of settlement IDs in the tree
+    pub leaf_count: u32,
+}
+
+/// Payload of an audit chain record.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuditRecordEvent {
+    /// Sequence number of the record in the audit chain
+    pub sequence: u64,
+    /// Canonical encoding of the operation
+    pub record: Bytes,
+    /// Audit head hash after folding in the record
+    pub head: BytesN<32>,
+}
+
+/// Payload of one step of a storage migration.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StorageRewrittenEvent {
+    /// First remittance ID rewritten
+    pub start_remittance_id: u64,
+    /// Remittance ID after the last one rewritten
+    pub end_remittance_id: u64,
+}
+
+/// Payload of a storage migration.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StorageMigratedEvent {
+    /// Storage schema version before the migration
+    pub from_version: u32,
+    /// Storage schema version after the migration
+    pub to_version: u32,
+}
+
+/// Payload of an imported migration snapshot.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MigrationImportedEvent {
+    /// Verification hash of the imported snapshot
+    pub verification_hash: BytesN<32>,
+    /// Number of remittances imported
+    pub remittance_count: u32,
+}
+
+/// Payload of an opened migration session.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MigrationSessionOpenedEvent {
+    /// Number of batches the session expects
+    pub expected_batches: u32,
+    /// Root over the batch hashes, in batch order
+    pub batches_root: BytesN<32>,
+}
+
+/// Payload of an imported migration batch.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MigrationBatchImportedEvent {
+    /// Number of the imported batch
+    pub batch_number: u32,
+    /// Verified hash of the batch
+    pub batch_hash: BytesN<32>,
+    /// Number of remittances in the batch
+    pub remittance_count: u32,
+}
+
+/// Payload of an aborted migration session.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MigrationSessionAbortedEvent {
+    /// Number of batches imported before the abort
+    pub batches_imported: u32,
+    /// Root the session was opened with
+    pub batches_root: BytesN<32>,
+}
+
+/// Payload of a travel-rule commitment.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TravelRuleCommittedEvent {
+    /// ID of the remittance
+    pub remittance_id: u64,
+    /// Hash of the travel-rule payload sent to the beneficiary VASP
+    pub originator_commitment: BytesN<32>,
+}
+
+/// Payload of a travel-rule acknowledgement.
+///
+/// Carries both hashes so a compliance archive can prove the full exchange
+/// from this single event.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TravelRuleAcknowledgedEvent {
+    /// ID of the remittance
+    pub remittance_id: u64,
+    /// Hash committed by the sender at creation
+    pub originator_commitment: BytesN<32>,
+    /// Hash of the beneficiary VASP's acknowledgement
+    pub beneficiary_ack: BytesN<32>,
+}
+
+/// Payload of a locked FX quote.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FxQuoteLockedEvent {
+    /// ID of the locked quote
+    pub quote_id: u64,
+    /// Destination corridor
+    pub corridor: Corridor,
+    /// Locked rate (7 decimal fixed-point)
+    pub rate: i128,
+    /// Amount the recipient receives in the destination currency
+    pub destination_amount: i128,
+    /// Timestamp after which the quote is no longer valid
+    pub expires_at: u64,
+}
+
+/// Logical action whose event is emitted at most once, however many code
+/// paths or retries reach it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EventAction {
+    /// Remittance settled, individually, in a batch or in a cycle
+    Settlement(u64),
+    /// Multi-transaction settlement batch settled
+    BatchSettlement(u64),
+    /// Settlement cycle closed
+    CycleClose(u64),
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub(crate) enum EventKey {
+    /// Sequence number of the latest event (instance storage)
+    Sequence,
+    /// Whether the action's event has been emitted (persistent storage)
+    Announced(EventAction),
+}
+
+/// Returns the sequence number of the latest event, or 0 if none has been
+/// emitted.
+pub fn get_event_sequence(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&EventKey::Sequence)
+        .unwrap_or(0)
+}
+
+/// Sets the sequence number of the latest event, as when continuing the
+/// event stream of a migrated contract.
+pub fn set_event_sequence(env: &Env, sequence: u64) {
+    env.storage().instance().set(&EventKey::Sequence, &sequence);
+}
+
+/// Returns whether the event announcing `action` has been emitted.
+pub fn is_announced(env: &Env, action: &EventAction) -> bool {
+    match action {
+        // Settlements keep their original flag, which migrations carry over
+        EventAction::Settlement(remittance_id) => has_settlement_event_emitted(env, *remittance_id),
+        _ => env
+            .storage()
+            .persistent()
+            .get(&EventKey::Announced(action.clone()))
+            .unwrap_or(false),
+    }
+}
+
+/// Runs `emit` unless the event announcing `action` has already been
+/// emitted, and records that it has.
+///
+/// Returns whether `emit` ran.
+pub fn emit_once(env: &Env, action: EventAction, emit: impl FnOnce()) -> bool {
+    if is_announced(env, &action) {
+        return false;
+    }
+
+    emit();
+    set_announced(env, action);
+    true
+}
+
+/// Records that the event announcing `action` has been emitted.
+pub fn set_announced(env: &Env, action: EventAction) {
+    match action {
+        EventAction::Settlement(remittance_id) => set_settlement_event_emitted(env, remittance_id),
+        _ => env
+            .storage()
+            .persistent()
+            .set(&EventKey::Announced(action), &true),
+    }
+}
+
+/// Assigns the next sequence number and publishes the event.
+fn publish(env: &Env, topics: impl Topics, actor: Address, payload: EventPayload) {
+    let sequence = get_event_sequence(env) + 1;
+    set_event_sequence(env, sequence);
+    let audit_head = get_audit_head(env);
+
+    let event = ContractEvent {
+        envelope: EventEnvelope {
+            schema_version: EVENT_SCHEMA_VERSION,
+            sequence,
+            ledger: env.ledger().sequence(),
+            timestamp: env.ledger().timestamp(),
+            actor,
+            audit_sequence: audit_head.sequence,
+            audit_hash: audit_head.hash,
+        },
+        payload,
+    };
+    env.events().publish(topics, event);
+}
 
 // ── Admin Events ───────────────────────────────────────────────────
 
+/// Emits an event when the contract is initialized.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `admin` - Address of the initial admin
+/// * `usdc_token` - Token remittances are made in
+/// * `fee_bps` - Platform fee rate, in basis points
+/// * `rate_limit_cooldown` - Settlement cooldown, in seconds
+pub fn emit_initialized(
+    env: &Env,
+    admin: Address,
+    usdc_token: Address,
+    fee_bps: u32,
+    rate_limit_cooldown: u64,
+) {
+    publish(
+        env,
+        (symbol_short!("admin"), symbol_short!("init")),
+        admin,
+        EventPayload::Initialized(InitializedEvent {
+            usdc_token,
+            fee_bps,
+            rate_limit_cooldown,
+        }),
+    );
+}
+
 /// Emits an event when the contract is paused by an admin.
 ///
 /// # Arguments
@@ -18,14 +688,11 @@ const SCHEMA_VERSION: u32 = 1;
 /// * `env` - The contract execution environment
 /// * `admin` - Address of the admin who paused the contract
 pub fn emit_paused(env: &Env, admin: Address) {
-    env.events().publish(
+    publish(
+        env,
         (symbol_short!("admin"), symbol_short!("paused")),
-        (
-            SCHEMA_VERSION,
-            env.ledger().sequence(),
-            env.ledger().timestamp(),
-            admin,
-        ),
+        admin,
+        EventPayload::Paused,
     );
 }
 
@@ -36,27 +703,45 @@ pub fn emit_paused(env: &Env, admin: Address) {
 /// * `env` - The contract execution environment
 /// * `admin` - Address of the admin who unpaused the contract
 pub fn emit_unpaused(env: &Env, admin: Address) {
-    env.events().publish(
+    publish(
+        env,
         (symbol_short!("admin"), symbol_short!("unpaused")),
-        (
-            SCHEMA_VERSION,
-            env.ledger().sequence(),
-            env.ledger().timestamp(),
-            admin,
-        ),
+        admin,
+        EventPayload::Unpaused,
     );
 }
 
-/// Event emission functions for the SwiftRemit contract.
+/// Emits an event when an admin grants the admin role to another address.
 ///
-/// This module provides functions to emit structured events for all significant
-/// contract operations. Events include schema versioning and ledger metadata
-/// for comprehensive audit trails.
-
-use soroban_sdk::{symbol_short, Address, Env};
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `caller` - Address of the admin who added the new admin
+/// * `admin` - Address that received the admin role
+pub fn emit_admin_added(env: &Env, caller: Address, admin: Address) {
+    publish(
+        env,
+        (symbol_short!("admin"), symbol_short!("added"), admin.clone()),
+        caller,
+        EventPayload::AdminAdded(admin),
+    );
+}
 
-/// Schema version for event structure compatibility
-const SCHEMA_VERSION: u32 = 1;
+/// Emits an event when an admin revokes another address's admin role.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `caller` - Address of the admin who removed the admin
+/// * `admin` - Address that lost the admin role
+pub fn emit_admin_removed(env: &Env, caller: Address, admin: Address) {
+    publish(
+        env,
+        (symbol_short!("admin"), symbol_short!("removed"), admin.clone()),
+        caller,
+        EventPayload::AdminRemoved(admin),
+    );
+}
 
 // ── Remittance Events ──────────────────────────────────────────────
 
@@ -70,6 +755,7 @@ const SCHEMA_VERSION: u32 = 1;
 /// * `agent` - Address of the assigned agent
 /// * `amount` - Total remittance amount
 /// * `fee` - Platform fee deducted
+/// * `integrator_fee` - Integrator fee deducted
 pub fn emit_remittance_created(
     env: &Env,
     remittance_id: u64,
@@ -79,73 +765,86 @@ pub fn emit_remittance_created(
     fee: i128,
     integrator_fee: i128,
 ) {
-    env.events().publish(
-        (symbol_short!("remit"), symbol_short!("created")),
-        (
-            SCHEMA_VERSION,
-            env.ledger().sequence(),
-            env.ledger().timestamp(),
+    publish(
+        env,
+        (symbol_short!("remit"), symbol_short!("created"), remittance_id),
+        sender.clone(),
+        EventPayload::RemittanceCreated(RemittanceCreatedEvent {
             remittance_id,
             sender,
             agent,
             amount,
             fee,
             integrator_fee,
-        ),
+        }),
     );
 }
 
-/// Emits an event when a remittance payout is completed.
+/// Emits an event when a remittance is cancelled.
 ///
 /// # Arguments
 ///
 /// * `env` - The contract execution environment
-/// * `remittance_id` - ID of the completed remittance
-/// * `agent` - Address of the agent who received the payout
-/// * `amount` - Payout amount (after fee deduction)
-pub fn emit_remittance_completed(
+/// * `remittance_id` - ID of the cancelled remittance
+/// * `sender` - Address of the sender who received the refund
+/// * `agent` - Address of the agent the remittance was assigned to
+/// * `asset` - Token refunded
+/// * `amount` - Refunded amount
+pub fn emit_remittance_cancelled(
     env: &Env,
     remittance_id: u64,
+    sender: Address,
     agent: Address,
+    asset: Address,
     amount: i128,
 ) {
-    env.events().publish(
-        (symbol_short!("remit"), symbol_short!("complete")),
-        (
-            SCHEMA_VERSION,
-            env.ledger().sequence(),
-            env.ledger().timestamp(),
+    publish(
+        env,
+        (symbol_short!("remit"), symbol_short!("cancel"), remittance_id),
+        sender.clone(),
+        EventPayload::RemittanceCancelled(RemittanceTransferEvent {
             remittance_id,
+            sender,
             agent,
+            asset,
             amount,
-        ),
+        }),
     );
 }
 
-/// Emits an event when a remittance is cancelled.
+/// Emits an event when a settled remittance is finalized.
 ///
 /// # Arguments
 ///
 /// * `env` - The contract execution environment
-/// * `remittance_id` - ID of the cancelled remittance
-/// * `sender` - Address of the sender who received the refund
-/// * `amount` - Refunded amount
-pub fn emit_remittance_cancelled(
-    env: &Env,
-    remittance_id: u64,
-    sender: Address,
-    amount: i128,
-) {
-    env.events().publish(
-        (symbol_short!("remit"), symbol_short!("cancel")),
-        (
-            SCHEMA_VERSION,
-            env.ledger().sequence(),
-            env.ledger().timestamp(),
+/// * `remittance_id` - ID of the finalized remittance
+/// * `admin` - Address of the admin who finalized the remittance
+pub fn emit_remittance_finalized(env: &Env, remittance_id: u64, admin: Address) {
+    publish(
+        env,
+        (symbol_short!("remit"), symbol_short!("finalize"), remittance_id),
+        admin,
+        EventPayload::RemittanceFinalized(remittance_id),
+    );
+}
+
+/// Emits an event when the sender attaches a memo to a remittance.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `remittance_id` - ID of the remittance
+/// * `sender` - Address of the sender
+/// * `memo` - Attached memo
+pub fn emit_memo_set(env: &Env, remittance_id: u64, sender: Address, memo: BytesN<32>) {
+    publish(
+        env,
+        (symbol_short!("remit"), symbol_short!("memo"), remittance_id),
+        sender,
+        EventPayload::MemoSet(MemoSetEvent {
             remittance_id,
-            sender,
-            amount,
-        ),
+            memo,
+        }),
     );
 }
 
@@ -157,15 +856,13 @@ pub fn emit_remittance_cancelled(
 ///
 /// * `env` - The contract execution environment
 /// * `agent` - Address of the registered agent
-pub fn emit_agent_registered(env: &Env, agent: Address) {
-    env.events().publish(
-        (symbol_short!("agent"), symbol_short!("register")),
-        (
-            SCHEMA_VERSION,
-            env.ledger().sequence(),
-            env.ledger().timestamp(),
-            agent,
-        ),
+/// * `admin` - Address of the admin who registered the agent
+pub fn emit_agent_registered(env: &Env, agent: Address, admin: Address) {
+    publish(
+        env,
+        (symbol_short!("agent"), symbol_short!("register"), agent.clone()),
+        admin,
+        EventPayload::AgentRegistered(agent),
     );
 }
 
@@ -175,35 +872,87 @@ pub fn emit_agent_registered(env: &Env, agent: Address) {
 ///
 /// * `env` - The contract execution environment
 /// * `agent` - Address of the removed agent
-pub fn emit_agent_removed(env: &Env, agent: Address) {
-    env.events().publish(
-        (symbol_short!("agent"), symbol_short!("removed")),
-        (
-            SCHEMA_VERSION,
-            env.ledger().sequence(),
-            env.ledger().timestamp(),
-            agent,
-        ),
+/// * `admin` - Address of the admin who removed the agent
+pub fn emit_agent_removed(env: &Env, agent: Address, admin: Address) {
+    publish(
+        env,
+        (symbol_short!("agent"), symbol_short!("removed"), agent.clone()),
+        admin,
+        EventPayload::AgentRemoved(agent),
     );
 }
 
-// ── Fee Events ─────────────────────────────────────────────────────
+/// Emits an event when an agent delegates batch settlement to an operator.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `agent` - Address of the delegating agent
+/// * `operator` - Address of the settlement operator
+pub fn emit_delegation_granted(env: &Env, agent: Address, operator: Address) {
+    publish(
+        env,
+        (symbol_short!("agent"), symbol_short!("delegate"), agent.clone()),
+        agent,
+        EventPayload::DelegationGranted(operator),
+    );
+}
+
+/// Emits an event when an agent revokes a settlement delegation.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `agent` - Address of the delegating agent
+/// * `operator` - Address of the settlement operator
+pub fn emit_delegation_revoked(env: &Env, agent: Address, operator: Address) {
+    publish(
+        env,
+        (symbol_short!("agent"), symbol_short!("revoke"), agent.clone()),
+        agent,
+        EventPayload::DelegationRevoked(operator),
+    );
+}
+
+// ── Configuration Events ───────────────────────────────────────────
 
 /// Emits an event when the platform fee is updated.
 ///
 /// # Arguments
 ///
 /// * `env` - The contract execution environment
-/// * `fee_bps` - New fee rate in basis points
-pub fn emit_fee_updated(env: &Env, fee_bps: u32) {
-    env.events().publish(
+/// * `admin` - Address of the admin who updated the fee
+/// * `old_fee_bps` - Fee rate before the update, in basis points
+/// * `new_fee_bps` - Fee rate after the update, in basis points
+pub fn emit_fee_updated(env: &Env, admin: Address, old_fee_bps: u32, new_fee_bps: u32) {
+    publish(
+        env,
         (symbol_short!("fee"), symbol_short!("updated")),
-        (
-            SCHEMA_VERSION,
-            env.ledger().sequence(),
-            env.ledger().timestamp(),
-            fee_bps,
-        ),
+        admin,
+        EventPayload::FeeUpdated(FeeUpdatedEvent {
+            old_fee_bps,
+            new_fee_bps,
+        }),
+    );
+}
+
+/// Emits an event when the integrator fee is updated.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `admin` - Address of the admin who updated the fee
+/// * `old_fee_bps` - Fee rate before the update, in basis points
+/// * `new_fee_bps` - Fee rate after the update, in basis points
+pub fn emit_integrator_fee_updated(env: &Env, admin: Address, old_fee_bps: u32, new_fee_bps: u32) {
+    publish(
+        env,
+        (symbol_short!("fee"), symbol_short!("integr")),
+        admin,
+        EventPayload::IntegratorFeeUpdated(FeeUpdatedEvent {
+            old_fee_bps,
+            new_fee_bps,
+        }),
     );
 }
 
@@ -212,18 +961,228 @@ pub fn emit_fee_updated(env: &Env, fee_bps: u32) {
 /// # Arguments
 ///
 /// * `env` - The contract execution environment
+/// * `admin` - Address of the admin who withdrew the fees
 /// * `to` - Address that received the withdrawn fees
+/// * `asset` - Token withdrawn
 /// * `amount` - Amount of fees withdrawn
-pub fn emit_fees_withdrawn(env: &Env, to: Address, amount: i128) {
-    env.events().publish(
-        (symbol_short!("fee"), symbol_short!("withdraw")),
-        (
-            SCHEMA_VERSION,
-            env.ledger().sequence(),
-            env.ledger().timestamp(),
-            to,
-            amount,
-        ),
+pub fn emit_fees_withdrawn(env: &Env, admin: Address, to: Address, asset: Address, amount: i128) {
+    publish(
+        env,
+        (symbol_short!("fee"), symbol_short!("withdraw"), to.clone()),
+        admin,
+        EventPayload::FeesWithdrawn(FeesWithdrawnEvent { to, asset, amount }),
+    );
+}
+
+/// Emits an event when the settlement cooldown is updated.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `admin` - Address of the admin who updated the cooldown
+/// * `old_cooldown` - Cooldown before the update, in seconds
+/// * `new_cooldown` - Cooldown after the update, in seconds
+pub fn emit_rate_limit_updated(env: &Env, admin: Address, old_cooldown: u64, new_cooldown: u64) {
+    publish(
+        env,
+        (symbol_short!("config"), symbol_short!("ratelimit")),
+        admin,
+        EventPayload::RateLimitUpdated(RateLimitUpdatedEvent {
+            old_cooldown,
+            new_cooldown,
+        }),
+    );
+}
+
+/// Emits an event when the request rate limit window is updated.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `admin` - Address of the admin who updated the window
+/// * `old_config` - Configuration before the update
+/// * `new_config` - Configuration after the update
+pub fn emit_rate_limit_config_updated(
+    env: &Env,
+    admin: Address,
+    old_config: RateLimitConfig,
+    new_config: RateLimitConfig,
+) {
+    publish(
+        env,
+        (symbol_short!("config"), symbol_short!("window")),
+        admin,
+        EventPayload::RateLimitConfigUpdated(RateLimitConfigUpdatedEvent {
+            old_config,
+            new_config,
+        }),
+    );
+}
+
+/// Emits an event when the idempotency key retention is updated.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `admin` - Address of the admin who updated the retention
+/// * `old_ttl` - Retention before the update, in seconds
+/// * `new_ttl` - Retention after the update, in seconds
+pub fn emit_idempotency_ttl_updated(env: &Env, admin: Address, old_ttl: u64, new_ttl: u64) {
+    publish(
+        env,
+        (symbol_short!("config"), symbol_short!("idemttl")),
+        admin,
+        EventPayload::IdempotencyTtlUpdated(IdempotencyTtlUpdatedEvent { old_ttl, new_ttl }),
+    );
+}
+
+/// Emits an event when the daily send limit of a currency-country pair is
+/// updated.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `admin` - Address of the admin who updated the limit
+/// * `currency` - Normalized currency code
+/// * `country` - Normalized country code
+/// * `old_limit` - Limit before the update, if one was configured
+/// * `new_limit` - Limit after the update
+pub fn emit_daily_limit_updated(
+    env: &Env,
+    admin: Address,
+    currency: String,
+    country: String,
+    old_limit: Option<i128>,
+    new_limit: i128,
+) {
+    publish(
+        env,
+        (symbol_short!("config"), symbol_short!("dailylim")),
+        admin,
+        EventPayload::DailyLimitUpdated(DailyLimitUpdatedEvent {
+            currency,
+            country,
+            old_limit,
+            new_limit,
+        }),
+    );
+}
+
+/// Emits an event when the travel-rule threshold of a corridor is updated.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `admin` - Address of the admin who updated the threshold
+/// * `corridor` - Corridor the threshold applies to
+/// * `old_threshold` - Threshold before the update, if one was configured
+/// * `new_threshold` - Threshold after the update
+pub fn emit_travel_rule_threshold_updated(
+    env: &Env,
+    admin: Address,
+    corridor: Corridor,
+    old_threshold: Option<i128>,
+    new_threshold: i128,
+) {
+    publish(
+        env,
+        (symbol_short!("config"), symbol_short!("travelthr")),
+        admin,
+        EventPayload::TravelRuleThresholdUpdated(TravelRuleThresholdUpdatedEvent {
+            corridor,
+            old_threshold,
+            new_threshold,
+        }),
+    );
+}
+
+/// Emits an event when the settlement cycle configuration of a corridor is
+/// updated.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `admin` - Address of the admin who updated the configuration
+/// * `corridor` - Corridor the configuration applies to
+/// * `old_config` - Configuration before the update, if the corridor settled in cycles
+/// * `new_config` - Configuration after the update
+pub fn emit_settlement_cycle_updated(
+    env: &Env,
+    admin: Address,
+    corridor: Corridor,
+    old_config: Option<SettlementCycleConfig>,
+    new_config: SettlementCycleConfig,
+) {
+    publish(
+        env,
+        (symbol_short!("config"), symbol_short!("cycle")),
+        admin,
+        EventPayload::SettlementCycleUpdated(SettlementCycleUpdatedEvent {
+            corridor,
+            old_config: old_config
+                .map(|config| Vec::from_array(env, [config]))
+                .unwrap_or_else(|| Vec::new(env)),
+            new_config,
+        }),
+    );
+}
+
+/// Emits an event when the FX configuration is updated.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `admin` - Address of the admin who updated the configuration
+/// * `old_config` - Configuration before the update, if FX was configured
+/// * `new_config` - Configuration after the update
+pub fn emit_fx_config_updated(
+    env: &Env,
+    admin: Address,
+    old_config: Option<FxConfig>,
+    new_config: FxConfig,
+) {
+    publish(
+        env,
+        (symbol_short!("config"), symbol_short!("fx")),
+        admin,
+        EventPayload::FxConfigUpdated(FxConfigUpdatedEvent {
+            old_config: old_config
+                .map(|config| Vec::from_array(env, [config]))
+                .unwrap_or_else(|| Vec::new(env)),
+            new_config,
+        }),
+    );
+}
+
+/// Emits an event when a token is added to the whitelist.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `admin` - Address of the admin who whitelisted the token
+/// * `token` - Address of the whitelisted token
+pub fn emit_token_whitelisted(env: &Env, admin: Address, token: Address) {
+    publish(
+        env,
+        (symbol_short!("token"), symbol_short!("whitelist"), token.clone()),
+        admin,
+        EventPayload::TokenWhitelisted(token),
+    );
+}
+
+/// Emits an event when a token is removed from the whitelist.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `admin` - Address of the admin who removed the token
+/// * `token` - Address of the removed token
+pub fn emit_token_removed(env: &Env, admin: Address, token: Address) {
+    publish(
+        env,
+        (symbol_short!("token"), symbol_short!("removed"), token.clone()),
+        admin,
+        EventPayload::TokenRemoved(token),
     );
 }
 
@@ -231,22 +1190,23 @@ pub fn emit_fees_withdrawn(env: &Env, to: Address, amount: i128) {
 
 /// Emits a structured completion event when a settlement is finalized.
 ///
-/// This event is emitted exactly once per completed settlement, after all state
-/// transitions are successfully committed. It includes sufficient identifiers to
+/// This is the only completion event of a remittance, whether it settled
+/// individually, in a batch or in a cycle. It is emitted after all state
+/// transitions are committed and includes sufficient identifiers to
 /// uniquely reference the finalized settlement.
 ///
 /// # Guarantees
 ///
-/// - **Exactly-Once Emission**: Event is emitted once and only once per settlement
+/// - **Exactly-Once Emission**: Emitted through `emit_once` with `EventAction::Settlement`
 /// - **Post-Finalization**: Only emitted after all state changes are committed
 /// - **Unique Identification**: Includes remittance_id for unambiguous reference
 /// - **Deterministic**: Same settlement always produces same event
-/// - **No Re-entry**: Protected against duplicate emission on retries
 ///
 /// # Arguments
 ///
 /// * `env` - The contract execution environment
 /// * `remittance_id` - Unique ID of the finalized settlement
+/// * `settlement_id` - Canonical settlement ID (see `hashing.rs`)
 /// * `sender` - Address of the sender
 /// * `receiver` - Address of the receiver (agent)
 /// * `asset` - Address of the token contract (e.g., USDC)
@@ -254,38 +1214,542 @@ pub fn emit_fees_withdrawn(env: &Env, to: Address, amount: i128) {
 ///
 /// # Event Structure
 ///
-/// Topic: `("settle", "complete")`
-/// Data: `(schema_version, ledger_sequence, timestamp, remittance_id, sender, receiver, asset, amount)`
+/// Topics: `("settle", "complete", remittance_id)`
+/// Payload: `EventPayload::SettlementCompleted`
 ///
 /// # Usage
 ///
-/// This function should only be called from `confirm_payout` after:
+/// This function should only be called through `emit_once` after:
 /// 1. All validations pass
 /// 2. Token transfer completes
 /// 3. Fee accumulation succeeds
 /// 4. Status updated to Settled
 /// 5. Settlement hash set
-/// 6. Event emission flag checked
 pub fn emit_settlement_completed(
     env: &Env,
     remittance_id: u64,
+    settlement_id: BytesN<32>,
     sender: Address,
     receiver: Address,
     asset: Address,
     amount: i128,
 ) {
-    env.events().publish(
-        (symbol_short!("settle"), symbol_short!("complete")),
-        (
-            SCHEMA_VERSION,
-            env.ledger().sequence(),
-            env.ledger().timestamp(),
+    publish(
+        env,
+        (symbol_short!("settle"), symbol_short!("complete"), remittance_id),
+        receiver.clone(),
+        EventPayload::SettlementCompleted(SettlementCompletedEvent {
             remittance_id,
+            settlement_id,
             sender,
             receiver,
             asset,
             amount,
-        ),
+        }),
+    );
+}
+
+/// Emits an event when a settlement receipt is issued.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `remittance_id` - ID of the settled remittance
+/// * `agent` - Address of the agent paid out
+/// * `receipt_hash` - Hash of the receipt's canonical encoding (see `hashing.rs`)
+pub fn emit_receipt_issued(env: &Env, remittance_id: u64, agent: Address, receipt_hash: BytesN<32>) {
+    publish(
+        env,
+        (symbol_short!("settle"), symbol_short!("receipt"), remittance_id),
+        agent,
+        EventPayload::ReceiptIssued(ReceiptIssuedEvent {
+            remittance_id,
+            receipt_hash,
+        }),
+    );
+}
+
+/// Emits an event when an agent confirms a remittance into a settlement cycle.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `cycle_id` - ID of the cycle the remittance joined
+/// * `remittance_id` - ID of the queued remittance
+/// * `agent` - Address of the confirming agent
+pub fn emit_remittance_queued(env: &Env, cycle_id: u64, remittance_id: u64, agent: Address) {
+    publish(
+        env,
+        (symbol_short!("cycle"), symbol_short!("queued"), remittance_id),
+        agent,
+        EventPayload::RemittanceQueued(RemittanceQueuedEvent {
+            cycle_id,
+            remittance_id,
+        }),
+    );
+}
+
+/// Emits an event when a settlement cycle is closed and settled.
+///
+/// Cycles can be closed by anyone, so the actor is the contract itself.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `cycle_id` - ID of the closed cycle
+/// * `corridor` - Corridor the cycle settled
+/// * `gross_volume` - Tokens paid out without netting (sum of amount minus fees)
+/// * `net_volume` - Amount transferred on-chain after netting
+/// * `fees` - Platform and integrator fees collected by the cycle
+pub fn emit_cycle_closed(
+    env: &Env,
+    cycle_id: u64,
+    corridor: Corridor,
+    gross_volume: i128,
+    net_volume: i128,
+    fees: i128,
+) {
+    publish(
+        env,
+        (symbol_short!("cycle"), symbol_short!("closed"), cycle_id),
+        env.current_contract_address(),
+        EventPayload::CycleClosed(CycleClosedEvent {
+            cycle_id,
+            corridor,
+            gross_volume,
+            net_volume,
+            fees,
+        }),
+    );
+}
+
+/// Emits an event when a multi-transaction settlement batch is opened.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `batch_id` - ID of the new batch
+/// * `owner` - Agent or settlement operator that opened the batch
+pub fn emit_batch_opened(env: &Env, batch_id: u64, owner: Address) {
+    publish(
+        env,
+        (symbol_short!("batch"), symbol_short!("opened"), batch_id),
+        owner,
+        EventPayload::BatchOpened(batch_id),
+    );
+}
+
+/// Emits an event when remittances are appended to a multi-transaction
+/// settlement batch.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `batch_id` - ID of the batch
+/// * `owner` - Agent or settlement operator that owns the batch
+/// * `remittance_ids` - IDs of the appended remittances, in order
+pub fn emit_batch_appended(env: &Env, batch_id: u64, owner: Address, remittance_ids: Vec<u64>) {
+    publish(
+        env,
+        (symbol_short!("batch"), symbol_short!("appended"), batch_id),
+        owner,
+        EventPayload::BatchAppended(BatchAppendedEvent {
+            batch_id,
+            remittance_ids,
+        }),
+    );
+}
+
+/// Emits an event when a multi-transaction settlement batch finishes settling.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `batch_id` - ID of the settled batch
+/// * `owner` - Agent or settlement operator that owns the batch
+/// * `remittance_count` - Number of remittances the batch settled
+/// * `transfer_count` - Number of net transfers the batch executed
+pub fn emit_batch_settled(
+    env: &Env,
+    batch_id: u64,
+    owner: Address,
+    remittance_count: u32,
+    transfer_count: u32,
+) {
+    publish(
+        env,
+        (symbol_short!("batch"), symbol_short!("settled"), batch_id),
+        owner,
+        EventPayload::BatchSettled(BatchSettledEvent {
+            batch_id,
+            remittance_count,
+            transfer_count,
+        }),
+    );
+}
+
+/// Emits an event when an owner aborts a multi-transaction settlement batch.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `batch_id` - ID of the aborted batch
+/// * `owner` - Agent or settlement operator that owns the batch
+/// * `remittance_ids` - IDs of the released remittances, in order
+pub fn emit_batch_aborted(env: &Env, batch_id: u64, owner: Address, remittance_ids: Vec<u64>) {
+    publish(
+        env,
+        (symbol_short!("batch"), symbol_short!("aborted"), batch_id),
+        owner,
+        EventPayload::BatchAborted(BatchReleasedEvent {
+            batch_id,
+            remittance_ids,
+        }),
+    );
+}
+
+/// Emits an event when a sender releases a remittance from an expired
+/// multi-transaction settlement batch.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `batch_id` - ID of the batch
+/// * `sender` - Sender of the released remittance
+/// * `remittance_id` - ID of the released remittance
+pub fn emit_batch_released(env: &Env, batch_id: u64, sender: Address, remittance_id: u64) {
+    publish(
+        env,
+        (symbol_short!("batch"), symbol_short!("released"), batch_id),
+        sender,
+        EventPayload::BatchReleased(BatchReleasedEvent {
+            batch_id,
+            remittance_ids: Vec::from_array(env, [remittance_id]),
+        }),
+    );
+}
+
+/// Emits an event when a settled batch's Merkle commitment is stored.
+///
+/// Commitments are stored by the contract as part of settling, so the actor
+/// is the contract itself.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `batch_id` - ID of the committed batch
+/// * `merkle_root` - Root of the tree over the batch's settlement IDs
+/// * `leaf_count` - Number of settlement IDs in the tree
+pub fn emit_batch_committed(env: &Env, batch_id: u64, merkle_root: BytesN<32>, leaf_count: u32) {
+    publish(
+        env,
+        (symbol_short!("batch"), symbol_short!("commit"), batch_id),
+        env.current_contract_address(),
+        EventPayload::BatchCommitted(BatchCommittedEvent {
+            batch_id,
+            merkle_root,
+            leaf_count,
+        }),
+    );
+}
+
+// ── Audit Events ───────────────────────────────────────────────────
+
+/// Emits an event when a record is appended to the audit chain.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `actor` - Address that performed the recorded operation
+/// * `sequence` - Sequence number of the record
+/// * `record` - Canonical encoding of the operation
+/// * `head` - Audit head hash after folding in the record
+pub fn emit_audit_record(env: &Env, actor: Address, sequence: u64, record: Bytes, head: BytesN<32>) {
+    publish(
+        env,
+        (symbol_short!("audit"), symbol_short!("record")),
+        actor,
+        EventPayload::AuditRecord(AuditRecordEvent {
+            sequence,
+            record,
+            head,
+        }),
+    );
+}
+
+// ── Upgrade Events ─────────────────────────────────────────────────
+
+/// Emits an event when the contract's code is replaced.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `caller` - Address of the admin who upgraded the contract
+/// * `new_wasm_hash` - Hash of the installed WASM
+pub fn emit_contract_upgraded(env: &Env, caller: Address, new_wasm_hash: BytesN<32>) {
+    publish(
+        env,
+        (symbol_short!("contract"), symbol_short!("upgraded")),
+        caller,
+        EventPayload::ContractUpgraded(new_wasm_hash),
+    );
+}
+
+/// Emits an event when a storage migration step rewrites remittances in
+/// the current layout.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `caller` - Address of the admin who ran the migration
+/// * `start_remittance_id` - First remittance ID rewritten
+/// * `end_remittance_id` - Remittance ID after the last one rewritten
+pub fn emit_storage_rewritten(
+    env: &Env,
+    caller: Address,
+    start_remittance_id: u64,
+    end_remittance_id: u64,
+) {
+    publish(
+        env,
+        (symbol_short!("storage"), symbol_short!("rewritten")),
+        caller,
+        EventPayload::StorageRewritten(StorageRewrittenEvent {
+            start_remittance_id,
+            end_remittance_id,
+        }),
+    );
+}
+
+/// Emits an event when stored data has been migrated to a newer storage
+/// schema version.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `caller` - Address of the admin who ran the migration
+/// * `from_version` - Storage schema version before the migration
+/// * `to_version` - Storage schema version after the migration
+pub fn emit_storage_migrated(env: &Env, caller: Address, from_version: u32, to_version: u32) {
+    publish(
+        env,
+        (symbol_short!("storage"), symbol_short!("migrated")),
+        caller,
+        EventPayload::StorageMigrated(StorageMigratedEvent {
+            from_version,
+            to_version,
+        }),
+    );
+}
+
+// ── Migration Events ───────────────────────────────────────────────
+
+/// Emits an event when a migration snapshot is imported.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `caller` - Address of the admin who imported the snapshot
+/// * `verification_hash` - Verification hash of the snapshot
+/// * `remittance_count` - Number of remittances imported
+pub fn emit_migration_imported(
+    env: &Env,
+    caller: Address,
+    verification_hash: BytesN<32>,
+    remittance_count: u32,
+) {
+    publish(
+        env,
+        (symbol_short!("migrate"), symbol_short!("imported")),
+        caller,
+        EventPayload::MigrationImported(MigrationImportedEvent {
+            verification_hash,
+            remittance_count,
+        }),
     );
 }
 
+/// Emits an event when a migration session is opened.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `caller` - Address of the admin who opened the session
+/// * `expected_batches` - Number of batches the session expects
+/// * `batches_root` - Root over the batch hashes, in batch order
+pub fn emit_migration_session_opened(
+    env: &Env,
+    caller: Address,
+    expected_batches: u32,
+    batches_root: BytesN<32>,
+) {
+    publish(
+        env,
+        (symbol_short!("migrate"), symbol_short!("begin")),
+        caller,
+        EventPayload::MigrationSessionOpened(MigrationSessionOpenedEvent {
+            expected_batches,
+            batches_root,
+        }),
+    );
+}
+
+/// Emits an event when a batch is imported into the open migration session.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `caller` - Address of the admin who imported the batch
+/// * `batch_number` - Number of the imported batch
+/// * `batch_hash` - Verified hash of the batch
+/// * `remittance_count` - Number of remittances in the batch
+pub fn emit_migration_batch_imported(
+    env: &Env,
+    caller: Address,
+    batch_number: u32,
+    batch_hash: BytesN<32>,
+    remittance_count: u32,
+) {
+    publish(
+        env,
+        (symbol_short!("migrate"), symbol_short!("batch"), batch_number),
+        caller,
+        EventPayload::MigrationBatchImported(MigrationBatchImportedEvent {
+            batch_number,
+            batch_hash,
+            remittance_count,
+        }),
+    );
+}
+
+/// Emits an event when the open migration session is finalized.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `caller` - Address of the admin who finalized the session
+/// * `remittance_counter` - Remittance counter after the import
+pub fn emit_migration_session_finalized(env: &Env, caller: Address, remittance_counter: u64) {
+    publish(
+        env,
+        (symbol_short!("migrate"), symbol_short!("finalize")),
+        caller,
+        EventPayload::MigrationSessionFinalized(remittance_counter),
+    );
+}
+
+/// Emits an event when the open migration session is aborted.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `caller` - Address of the admin who aborted the session
+/// * `batches_imported` - Number of batches imported before the abort
+/// * `batches_root` - Root the session was opened with
+pub fn emit_migration_session_aborted(
+    env: &Env,
+    caller: Address,
+    batches_imported: u32,
+    batches_root: BytesN<32>,
+) {
+    publish(
+        env,
+        (symbol_short!("migrate"), symbol_short!("abort")),
+        caller,
+        EventPayload::MigrationSessionAborted(MigrationSessionAbortedEvent {
+            batches_imported,
+            batches_root,
+        }),
+    );
+}
+
+// ── Compliance Events ──────────────────────────────────────────────
+
+/// Emits an event when a remittance is created with a travel-rule commitment.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `remittance_id` - ID of the remittance
+/// * `sender` - Address of the originator
+/// * `originator_commitment` - Hash of the travel-rule payload sent to the beneficiary VASP
+pub fn emit_travel_rule_committed(
+    env: &Env,
+    remittance_id: u64,
+    sender: Address,
+    originator_commitment: BytesN<32>,
+) {
+    publish(
+        env,
+        (symbol_short!("travel"), symbol_short!("commit"), remittance_id),
+        sender,
+        EventPayload::TravelRuleCommitted(TravelRuleCommittedEvent {
+            remittance_id,
+            originator_commitment,
+        }),
+    );
+}
+
+/// Emits an event when the agent acknowledges the travel-rule exchange.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `remittance_id` - ID of the remittance
+/// * `agent` - Address of the acknowledging agent
+/// * `originator_commitment` - Hash committed by the sender at creation
+/// * `beneficiary_ack` - Hash of the beneficiary VASP's acknowledgement
+pub fn emit_travel_rule_acknowledged(
+    env: &Env,
+    remittance_id: u64,
+    agent: Address,
+    originator_commitment: BytesN<32>,
+    beneficiary_ack: BytesN<32>,
+) {
+    publish(
+        env,
+        (symbol_short!("travel"), symbol_short!("ack"), remittance_id),
+        agent,
+        EventPayload::TravelRuleAcknowledged(TravelRuleAcknowledgedEvent {
+            remittance_id,
+            originator_commitment,
+            beneficiary_ack,
+        }),
+    );
+}
+
+// ── FX Events ──────────────────────────────────────────────────────
+
+/// Emits an event when an FX quote is locked.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `quote_id` - ID of the locked quote
+/// * `sender` - Address the quote was locked for
+/// * `corridor` - Destination corridor
+/// * `rate` - Locked rate (7 decimal fixed-point)
+/// * `destination_amount` - Amount the recipient receives in the destination currency
+/// * `expires_at` - Timestamp after which the quote is no longer valid
+pub fn emit_fx_quote_locked(
+    env: &Env,
+    quote_id: u64,
+    sender: Address,
+    corridor: Corridor,
+    rate: i128,
+    destination_amount: i128,
+    expires_at: u64,
+) {
+    publish(
+        env,
+        (symbol_short!("fx"), symbol_short!("locked"), quote_id),
+        sender,
+        EventPayload::FxQuoteLocked(FxQuoteLockedEvent {
+            quote_id,
+            corridor,
+            rate,
+            destination_amount,
+            expires_at,
+        }),
+    );
+}
@@ -0,0 +1,322 @@
+//! Foreign-exchange quote locking for the SwiftRemit contract.
+//!
+//! Remittances are funded in the stablecoin, but the recipient is paid in the
+//! corridor's local currency. This module reads rates from an external price
+//! feed contract, lets a sender lock a rate for a short TTL, and records the
+//! locked rate and destination-currency amount on the remittance that
+//! consumes the quote.
+//!
+//! Rates are fixed-point integers scaled by `FX_RATE_SCALE` (7 decimals,
+//! matching the stablecoin's precision): a rate of `17_2500000` means one
+//! unit of the stablecoin buys 17.25 units of the destination currency.
+
+use soroban_sdk::{contractclient, contracttype, Address, Env, String};
+
+use crate::{ContractError, Corridor};
+
+/// Fixed-point scale of FX rates (7 decimal places).
+pub const FX_RATE_SCALE: i128 = 10_000_000;
+
+/// Longest time a locked quote may stay valid, in seconds (1 hour).
+pub const MAX_QUOTE_TTL: u64 = 3_600;
+
+/// Price observation returned by a price feed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    /// Units of `quote` per unit of `base`, scaled by `FX_RATE_SCALE`
+    pub rate: i128,
+    /// Ledger timestamp at which the feed last updated this pair
+    pub timestamp: u64,
+}
+
+/// Interface every FX price feed must implement.
+///
+/// The contract only depends on this interface, so any oracle can be plugged
+/// in behind a thin adapter contract.
+#[contractclient(name = "PriceFeedClient")]
+pub trait PriceFeed {
+    /// Returns the latest rate for `base`/`quote`, or `None` if the pair is unsupported.
+    fn get_rate(env: Env, base: String, quote: String) -> Option<PriceData>;
+}
+
+/// FX subsystem configuration.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FxConfig {
+    /// Price feed contract address
+    pub oracle: Address,
+    /// Currency code of the stablecoin as known to the oracle (e.g., "USD")
+    pub base_currency: String,
+    /// Maximum age in seconds of an oracle rate before it is rejected
+    pub max_staleness: u64,
+    /// Maximum move in basis points between lock and use before a quote is rejected
+    pub max_deviation_bps: u32,
+    /// How long a locked quote stays valid, in seconds, at most `MAX_QUOTE_TTL`
+    pub quote_ttl: u64,
+}
+
+/// A locked FX quote.
+///
+/// Once consumed by `create_remittance`, the quote is stored against the
+/// remittance as its record of the locked rate and destination amount.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FxQuote {
+    /// Unique quote identifier
+    pub quote_id: u64,
+    /// Sender the quote was locked for
+    pub sender: Address,
+    /// Destination corridor
+    pub corridor: Corridor,
+    /// Source amount in the stablecoin (before fees)
+    pub amount: i128,
+    /// Locked rate, scaled by `FX_RATE_SCALE`
+    pub rate: i128,
+    /// Amount the recipient receives in the destination currency
+    pub destination_amount: i128,
+    /// Ledger timestamp the quote was locked at
+    pub locked_at: u64,
+    /// Ledger timestamp after which the quote can no longer be used
+    pub expires_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub(crate) enum FxKey {
+    /// FX configuration (instance storage)
+    FxConfig,
+    /// Counter for generating quote IDs (instance storage)
+    QuoteCounter,
+    /// Open quote by ID (temporary storage)
+    Quote(u64),
+    /// Consumed quote recorded against a remittance (persistent storage)
+    RemittanceFx(u64),
+}
+
+/// Sets the FX configuration.
+pub fn set_fx_config(env: &Env, config: &FxConfig) {
+    env.storage().instance().set(&FxKey::FxConfig, config);
+}
+
+/// Gets the FX configuration.
+pub fn get_fx_config(env: &Env) -> Result<FxConfig, ContractError> {
+    env.storage()
+        .instance()
+        .get(&FxKey::FxConfig)
+        .ok_or(ContractError::FxOracleNotConfigured)
+}
+
+/// Reads the current rate for a corridor from the configured oracle and
+/// rejects it if it is older than `max_staleness`.
+pub fn fetch_rate(env: &Env, corridor: &Corridor) -> Result<i128, ContractError> {
+    let config = get_fx_config(env)?;
+    let client = PriceFeedClient::new(env, &config.oracle);
+
+    let price = client
+        .get_rate(&config.base_currency, &corridor.currency)
+        .ok_or(ContractError::FxRateUnavailable)?;
+
+    if price.rate <= 0 {
+        return Err(ContractError::FxRateUnavailable);
+    }
+
+    let age = env.ledger().timestamp().saturating_sub(price.timestamp);
+    if age > config.max_staleness {
+        return Err(ContractError::FxRateUnavailable);
+    }
+
+    Ok(price.rate)
+}
+
+/// Converts a stablecoin payout amount into the destination currency.
+pub fn convert_amount(payout_amount: i128, rate: i128) -> Result<i128, ContractError> {
+    payout_amount
+        .checked_mul(rate)
+        .ok_or(ContractError::Overflow)?
+        .checked_div(FX_RATE_SCALE)
+        .ok_or(ContractError::Overflow)
+}
+
+/// Returns true if `current` has moved more than `max_deviation_bps` from `locked`.
+pub fn exceeds_deviation(locked: i128, current: i128, max_deviation_bps: u32) -> bool {
+    let diff = if current > locked {
+        current - locked
+    } else {
+        locked - current
+    };
+
+    // diff / locked > bps / 10000, rearranged to avoid division
+    match (
+        diff.checked_mul(10000),
+        locked.checked_mul(max_deviation_bps as i128),
+    ) {
+        (Some(lhs), Some(rhs)) => lhs > rhs,
+        _ => true,
+    }
+}
+
+/// Locks a quote for `amount` in `corridor` and stores it until it expires.
+///
+/// `payout_amount` is the stablecoin amount the recipient is owed after fees.
+pub fn lock_quote(
+    env: &Env,
+    sender: &Address,
+    corridor: &Corridor,
+    amount: i128,
+    payout_amount: i128,
+) -> Result<FxQuote, ContractError> {
+    let config = get_fx_config(env)?;
+    let rate = fetch_rate(env, corridor)?;
+    let destination_amount = convert_amount(payout_amount, rate)?;
+
+    let quote_id = get_quote_counter(env)
+        .checked_add(1)
+        .ok_or(ContractError::Overflow)?;
+    set_quote_counter(env, quote_id);
+
+    let locked_at = env.ledger().timestamp();
+    let quote = FxQuote {
+        quote_id,
+        sender: sender.clone(),
+        corridor: corridor.clone(),
+        amount,
+        rate,
+        destination_amount,
+        locked_at,
+        expires_at: locked_at.saturating_add(config.quote_ttl),
+    };
+
+    let key = FxKey::Quote(quote_id);
+    env.storage().temporary().set(&key, &quote);
+    // Ledgers close no faster than once per second, so the TTL in seconds
+    // is a safe upper bound when used as a ledger count. The host rejects
+    // extensions past the maximum entry TTL, so it is cut to that.
+    let ttl = config.quote_ttl.min(env.storage().max_ttl() as u64) as u32;
+    env.storage().temporary().extend_ttl(&key, ttl, ttl);
+
+    Ok(quote)
+}
+
+/// Returns the most recently allocated quote ID, 0 before the first.
+pub fn get_quote_counter(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&FxKey::QuoteCounter)
+        .unwrap_or(0)
+}
+
+/// Sets the most recently allocated quote ID.
+pub fn set_quote_counter(env: &Env, quote_id: u64) {
+    env.storage().instance().set(&FxKey::QuoteCounter, &quote_id);
+}
+
+/// Gets an open quote by ID.
+pub fn get_quote(env: &Env, quote_id: u64) -> Option<FxQuote> {
+    env.storage().temporary().get(&FxKey::Quote(quote_id))
+}
+
+/// Validates a quote against the remittance being created and removes it so
+/// it cannot be used twice.
+///
+/// Checks that the quote belongs to the sender, matches the corridor and
+/// amounts, has not expired, and that the oracle has not moved beyond the
+/// configured deviation since it was locked.
+pub fn consume_quote(
+    env: &Env,
+    quote_id: u64,
+    sender: &Address,
+    corridor: &Corridor,
+    amount: i128,
+    payout_amount: i128,
+) -> Result<FxQuote, ContractError> {
+    let config = get_fx_config(env)?;
+    let quote = get_quote(env, quote_id).ok_or(ContractError::FxQuoteNotFound)?;
+
+    if env.ledger().timestamp() > quote.expires_at {
+        return Err(ContractError::FxQuoteExpired);
+    }
+
+    if quote.sender != *sender
+        || quote.corridor != *corridor
+        || quote.amount != amount
+        || convert_amount(payout_amount, quote.rate)? != quote.destination_amount
+    {
+        return Err(ContractError::FxQuoteMismatch);
+    }
+
+    let current_rate = fetch_rate(env, corridor)?;
+    if exceeds_deviation(quote.rate, current_rate, config.max_deviation_bps) {
+        return Err(ContractError::FxRateDeviation);
+    }
+
+    env.storage().temporary().remove(&FxKey::Quote(quote_id));
+
+    Ok(quote)
+}
+
+/// Records the consumed quote against a remittance.
+pub fn set_remittance_fx(env: &Env, remittance_id: u64, quote: &FxQuote) {
+    env.storage()
+        .persistent()
+        .set(&FxKey::RemittanceFx(remittance_id), quote);
+}
+
+/// Gets the locked quote recorded against a remittance, if any.
+pub fn get_remittance_fx(env: &Env, remittance_id: u64) -> Option<FxQuote> {
+    env.storage()
+        .persistent()
+        .get(&FxKey::RemittanceFx(remittance_id))
+}
+
+/// Mock price feed used by tests.
+#[cfg(test)]
+pub mod mock_oracle {
+    use super::PriceData;
+    use soroban_sdk::{contract, contractimpl, contracttype, Env, String};
+
+    #[contracttype]
+    #[derive(Clone)]
+    enum MockKey {
+        Rate(String, String),
+    }
+
+    #[contract]
+    pub struct MockPriceFeed;
+
+    #[contractimpl]
+    impl MockPriceFeed {
+        pub fn set_rate(env: Env, base: String, quote: String, rate: i128, timestamp: u64) {
+            env.storage()
+                .instance()
+                .set(&MockKey::Rate(base, quote), &PriceData { rate, timestamp });
+        }
+
+        pub fn get_rate(env: Env, base: String, quote: String) -> Option<PriceData> {
+            env.storage().instance().get(&MockKey::Rate(base, quote))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_amount() {
+        // 975 USDC at 17.25 MXN/USDC
+        assert_eq!(convert_amount(975, 172_500_000), Ok(16818));
+        assert_eq!(convert_amount(i128::MAX, 2 * FX_RATE_SCALE), Err(ContractError::Overflow));
+    }
+
+    #[test]
+    fn test_exceeds_deviation() {
+        let locked = 17 * FX_RATE_SCALE;
+
+        // 1% band around the locked rate
+        assert!(!exceeds_deviation(locked, locked, 100));
+        assert!(!exceeds_deviation(locked, locked + locked / 100, 100));
+        assert!(exceeds_deviation(locked, locked + locked / 100 + 1, 100));
+        assert!(exceeds_deviation(locked, locked - locked / 50, 100));
+    }
+}
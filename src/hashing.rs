@@ -18,19 +18,83 @@
 //! Note: `status` is intentionally excluded — it changes over the remittance
 //! lifecycle and must not affect the settlement ID.
 //!
+//! ## Schema Versions
+//!
+//! - **v1**: fields 1–6 above.
+//! - **v2**: fields 1–6, followed by
+//!   7. `token` — Address of the settlement token, as raw bytes
+//!   8. `memo`  — 32 bytes (32 zero bytes if the remittance has no memo)
+//!
+//! New settlements use `HASH_SCHEMA_VERSION`, and the version is stored with
+//! each settled remittance. `verify_settlement_id` checks an externally
+//! computed ID under any supported version.
+//!
+//! ## Remittance Encoding
+//!
+//! A full `Remittance` is encoded as fields 1–6 above, followed by
+//!
+//! 7. `status`         — RemittanceStatus, as XDR bytes
+//! 8. `corridor`       — Corridor, as XDR bytes
+//! 9. `integrator_fee` — i128, big-endian 16 bytes
+//!
+//! Settlement IDs hash only fields 1–6, which never change once a
+//! remittance is created. Migration snapshots and batches hash the full
+//! encoding, so a remittance whose status changed does not match its
+//! exported copy. The full encoding is part of migration snapshot schema 3
+//! (`MIGRATION_SCHEMA_VERSION`); see `migration.rs` for how snapshots and
+//! batches are assembled from it.
+//!
+//! ## Settlement Receipts
+//!
+//! A `SettlementReceipt` is encoded for hashing in this order:
+//!
+//! 1. `RECEIPT_SCHEMA_VERSION` — u32, big-endian 4 bytes
+//! 2. `settlement_id`          — 32 bytes
+//! 3. `settlement_schema_version` — u32, big-endian 4 bytes
+//! 4. `remittance_id`          — u64, big-endian 8 bytes
+//! 5. `sender`                 — Address, as raw bytes
+//! 6. `agent`                  — Address, as raw bytes
+//! 7. `amount`                 — i128, big-endian 16 bytes
+//! 8. `fee`                    — i128, big-endian 16 bytes
+//! 9. `payout_amount`          — i128, big-endian 16 bytes
+//! 10. `expiry`                — u64, big-endian 8 bytes (0 if None)
+//! 11. `corridor`              — Corridor, as XDR bytes
+//! 12. `status`                — RemittanceStatus, as XDR bytes
+//! 13. `ledger_sequence`       — u32, big-endian 4 bytes
+//! 14. `timestamp`             — u64, big-endian 8 bytes
+//!
+//! The receipt hash is SHA-256 over this encoding.
+//!
 //! ## Serialization Rules
 //!
 //! - All integers are big-endian (network byte order)
 //! - Optional fields use 8 zero bytes when None
 //! - No separators between fields — fixed-width encoding eliminates ambiguity
 //! - Hash algorithm: SHA-256 via Soroban env.crypto().sha256()
+//!
+//! ## Storage
+//!
+//! The settlement ID is computed and stored when a remittance settles, and is
+//! included in the `settle.complete` event. It can be queried by remittance
+//! with `get_settlement_id` and resolved back with
+//! `get_remittance_by_settlement_id`.
 
 use soroban_sdk::{Address, Bytes, BytesN, Env};
 
-/// Canonical field ordering version — increment if ordering ever changes.
-/// External systems should record this alongside stored settlement IDs.
+use crate::ContractError;
+
+/// Canonical field ordering version used for new settlements — add a new
+/// version if ordering or fields ever change. External systems should record
+/// this alongside stored settlement IDs.
 pub const HASH_SCHEMA_VERSION: u32 = 1;
 
+/// Latest settlement ID schema version the contract can compute.
+pub const LATEST_HASH_SCHEMA_VERSION: u32 = 2;
+
+/// Settlement receipt encoding version — increment if the receipt encoding
+/// ever changes.
+pub const RECEIPT_SCHEMA_VERSION: u32 = 1;
+
 /// Generate a deterministic settlement ID from remittance fields.
 ///
 /// This is the single canonical implementation. External systems must
@@ -56,6 +120,54 @@ pub fn compute_settlement_id(
     fee: i128,
     expiry: Option<u64>,
 ) -> BytesN<32> {
+    let buf = settlement_id_v1_fields(env, remittance_id, sender, agent, amount, fee, expiry);
+
+    // SHA-256 over the canonical byte sequence
+    env.crypto().sha256(&buf).into()
+}
+
+/// Generate a schema v2 settlement ID, which also commits to the settlement
+/// token and the remittance memo.
+///
+/// # Arguments
+/// Same as `compute_settlement_id`, plus:
+/// * `token`          - Settlement token address
+/// * `memo`           - Optional 32-byte remittance memo, None → 32 zero bytes
+pub fn compute_settlement_id_v2(
+    env: &Env,
+    remittance_id: u64,
+    sender: &Address,
+    agent: &Address,
+    amount: i128,
+    fee: i128,
+    expiry: Option<u64>,
+    token: &Address,
+    memo: &Option<BytesN<32>>,
+) -> BytesN<32> {
+    let mut buf = settlement_id_v1_fields(env, remittance_id, sender, agent, amount, fee, expiry);
+
+    // Field 7: token address bytes
+    buf.append(&address_to_bytes(env, token));
+
+    // Field 8: memo — 32 bytes, zeros if None
+    match memo {
+        Some(memo) => buf.extend_from_array(&memo.to_array()),
+        None => buf.extend_from_array(&[0u8; 32]),
+    }
+
+    env.crypto().sha256(&buf).into()
+}
+
+/// Serializes the schema v1 fields in canonical order.
+fn settlement_id_v1_fields(
+    env: &Env,
+    remittance_id: u64,
+    sender: &Address,
+    agent: &Address,
+    amount: i128,
+    fee: i128,
+    expiry: Option<u64>,
+) -> Bytes {
     let mut buf = Bytes::new(env);
 
     // Field 1: remittance_id — u64 big-endian (8 bytes)
@@ -79,8 +191,36 @@ pub fn compute_settlement_id(
     let expiry_val: u64 = expiry.unwrap_or(0);
     buf.extend_from_array(&expiry_val.to_be_bytes());
 
-    // SHA-256 over the canonical byte sequence
-    env.crypto().sha256(&buf).into()
+    buf
+}
+
+/// Serialize a remittance to its full canonical encoding: the settlement ID
+/// fields followed by status, corridor and integrator fee.
+///
+/// See the module documentation for the field order.
+pub fn encode_remittance(env: &Env, remittance: &crate::Remittance) -> Bytes {
+    use soroban_sdk::xdr::ToXdr;
+
+    let mut buf = settlement_id_v1_fields(
+        env,
+        remittance.id,
+        &remittance.sender,
+        &remittance.agent,
+        remittance.amount,
+        remittance.fee,
+        remittance.expiry,
+    );
+
+    // Field 7: status as XDR
+    buf.append(&remittance.status.clone().to_xdr(env));
+
+    // Field 8: corridor as XDR
+    buf.append(&remittance.corridor.clone().to_xdr(env));
+
+    // Field 9: integrator fee — i128 big-endian (16 bytes)
+    buf.extend_from_array(&remittance.integrator_fee.to_be_bytes());
+
+    buf
 }
 
 /// Compute settlement ID directly from a Remittance struct.
@@ -100,9 +240,111 @@ pub fn compute_settlement_id_from_remittance(
     )
 }
 
+/// Compute a remittance's settlement ID under the given schema version.
+///
+/// `token` and `memo` are only used from schema v2 on.
+///
+/// # Errors
+/// * `UnsupportedHashSchema` - `schema_version` is 0 or above `LATEST_HASH_SCHEMA_VERSION`
+pub fn compute_settlement_id_for_version(
+    env: &Env,
+    remittance: &crate::Remittance,
+    schema_version: u32,
+    token: &Address,
+    memo: &Option<BytesN<32>>,
+) -> Result<BytesN<32>, ContractError> {
+    match schema_version {
+        1 => Ok(compute_settlement_id_from_remittance(env, remittance)),
+        2 => Ok(compute_settlement_id_v2(
+            env,
+            remittance.id,
+            &remittance.sender,
+            &remittance.agent,
+            remittance.amount,
+            remittance.fee,
+            remittance.expiry,
+            token,
+            memo,
+        )),
+        _ => Err(ContractError::UnsupportedHashSchema),
+    }
+}
+
+/// Serialize a settlement receipt to its canonical byte encoding.
+///
+/// See the module documentation for the field order.
+pub fn encode_receipt(env: &Env, receipt: &crate::SettlementReceipt) -> Bytes {
+    use soroban_sdk::xdr::ToXdr;
+
+    let mut buf = Bytes::new(env);
+
+    buf.extend_from_array(&RECEIPT_SCHEMA_VERSION.to_be_bytes());
+    buf.extend_from_array(&receipt.settlement_id.to_array());
+    buf.extend_from_array(&receipt.settlement_schema_version.to_be_bytes());
+    buf.extend_from_array(&receipt.remittance_id.to_be_bytes());
+    buf.append(&address_to_bytes(env, &receipt.sender));
+    buf.append(&address_to_bytes(env, &receipt.agent));
+    buf.extend_from_array(&receipt.amount.to_be_bytes());
+    buf.extend_from_array(&receipt.fee.to_be_bytes());
+    buf.extend_from_array(&receipt.payout_amount.to_be_bytes());
+    buf.extend_from_array(&receipt.expiry.unwrap_or(0).to_be_bytes());
+    buf.append(&receipt.corridor.clone().to_xdr(env));
+    buf.append(&receipt.status.clone().to_xdr(env));
+    buf.extend_from_array(&receipt.ledger_sequence.to_be_bytes());
+    buf.extend_from_array(&receipt.timestamp.to_be_bytes());
+
+    buf
+}
+
+/// Compute the hash of a settlement receipt: SHA-256 over `encode_receipt`.
+pub fn compute_receipt_hash(env: &Env, receipt: &crate::SettlementReceipt) -> BytesN<32> {
+    env.crypto().sha256(&encode_receipt(env, receipt)).into()
+}
+
+/// Generate a deterministic hash of `create_remittance` request parameters.
+///
+/// Used by idempotency protection to detect a retry whose payload differs
+/// from the original request. Fields follow the same fixed-width encoding
+/// as settlement IDs:
+///
+/// 1. `sender`     — Address, as raw bytes
+/// 2. `agent`      — Address, as raw bytes
+/// 3. `amount`     — i128, big-endian 16 bytes
+/// 4. `corridor`   — Corridor, as XDR bytes
+/// 5. `expiry`     — u64,  big-endian 8 bytes (0 if None)
+/// 6. `travel_rule_commitment` — 32 bytes (32 zero bytes if None)
+/// 7. `fx_quote_id` — u64,  big-endian 8 bytes (0 if None)
+pub fn compute_request_hash(
+    env: &Env,
+    sender: &Address,
+    agent: &Address,
+    amount: i128,
+    corridor: &crate::Corridor,
+    expiry: Option<u64>,
+    travel_rule_commitment: &Option<BytesN<32>>,
+    fx_quote_id: Option<u64>,
+) -> BytesN<32> {
+    use soroban_sdk::xdr::ToXdr;
+
+    let mut buf = Bytes::new(env);
+
+    buf.append(&address_to_bytes(env, sender));
+    buf.append(&address_to_bytes(env, agent));
+    buf.extend_from_array(&amount.to_be_bytes());
+    buf.append(&corridor.clone().to_xdr(env));
+    buf.extend_from_array(&expiry.unwrap_or(0).to_be_bytes());
+    match travel_rule_commitment {
+        Some(commitment) => buf.extend_from_array(&commitment.to_array()),
+        None => buf.extend_from_array(&[0u8; 32]),
+    }
+    buf.extend_from_array(&fx_quote_id.unwrap_or(0).to_be_bytes());
+
+    env.crypto().sha256(&buf).into()
+}
+
 /// Serialize an Address to its canonical byte representation.
 /// Uses Soroban's built-in address serialization via to_xdr.
-fn address_to_bytes(env: &Env, address: &Address) -> Bytes {
+pub(crate) fn address_to_bytes(env: &Env, address: &Address) -> Bytes {
     use soroban_sdk::xdr::ToXdr;
     address.to_xdr(env)
 }
@@ -4,35 +4,51 @@
 //! with built-in duplicate settlement protection and expiry mechanisms.
 
 #![no_std]
+#![allow(clippy::too_many_arguments)]
+mod audit;
+mod batching;
 mod debug;
+mod delegation;
 mod error_handler;
 mod errors;
 mod events;
+mod fx;
 mod hashing;
+mod merkle;
 mod migration;
 mod netting;
 mod rate_limit;
+mod reconciliation;
+mod settlement_cycle;
 mod storage;
+mod travel_rule;
 mod types;
+mod upgrade;
 mod validation;
 #[cfg(test)]
 mod test;
 
-#[cfg(test)]
-mod test; 
-
-use soroban_sdk::{contract, contractimpl, token, Address, Env, Vec};
+use soroban_sdk::{contract, contractimpl, token, Address, BytesN, Env, Map, String, Vec};
 
+pub use audit::*;
+pub use batching::*;
 pub use debug::*;
+pub use delegation::*;
 pub use error_handler::*;
 pub use errors::ContractError;
 pub use events::*;
+pub use fx::*;
 pub use hashing::*;
+pub use merkle::*;
 pub use migration::*;
 pub use netting::*;
 pub use rate_limit::*;
+pub use reconciliation::*;
+pub use settlement_cycle::*;
 pub use storage::*;
+pub use travel_rule::*;
 pub use types::*;
+pub use upgrade::*;
 pub use validation::*;
 
 /// The main SwiftRemit contract for managing cross-border remittances.
@@ -94,12 +110,15 @@ impl SwiftRemitContract {
         set_remittance_counter(&env, 0);
         set_accumulated_fees(&env, 0);
         set_rate_limit_cooldown(&env, rate_limit_cooldown);
+        set_storage_schema_version(&env, STORAGE_SCHEMA_VERSION);
 
         // Initialize rate limiting with default configuration
         init_rate_limit(&env);
 
         log_initialize(&env, &admin, &usdc_token, fee_bps);
 
+        emit_initialized(&env, admin, usdc_token, fee_bps, rate_limit_cooldown);
+
         Ok(())
     }
 
@@ -115,7 +134,7 @@ impl SwiftRemitContract {
     ///
     /// # Returns
     ///
-    /// * `Ok(())` - Agent successfully registered
+    /// * `Ok(())` - Agent successfully registered, or was already registered
     /// * `Err(ContractError::NotInitialized)` - Contract not initialized
     ///
     /// # Authorization
@@ -125,15 +144,19 @@ impl SwiftRemitContract {
         let caller = get_admin(&env)?;
         require_admin(&env, &caller)?;
 
-        set_agent_registered(&env, &agent, true);
+        // Registering an agent twice changes nothing, so it announces nothing;
+        // it only lists an agent registered by code predating registries
+        if is_agent_registered(&env, &agent) {
+            backfill_agent_registry(&env, &agent);
+            return Ok(());
+        }
 
-        emit_agent_registered(&env, agent.clone(), caller.clone());
+        set_agent_registered(&env, &agent, true);
+        record_role_op(&env, AuditOp::AgentRegistered, &caller, &agent);
 
-        
         // Event: Agent registered - Fires when admin adds a new agent to the approved list
         // Used by off-chain systems to track which addresses can confirm payouts
-        emit_agent_registered(&env, agent, caller.clone());
-
+        emit_agent_registered(&env, agent, caller);
 
         Ok(())
     }
@@ -150,7 +173,7 @@ impl SwiftRemitContract {
     ///
     /// # Returns
     ///
-    /// * `Ok(())` - Agent successfully removed
+    /// * `Ok(())` - Agent successfully removed, or was not registered
     /// * `Err(ContractError::NotInitialized)` - Contract not initialized
     ///
     /// # Authorization
@@ -160,19 +183,220 @@ impl SwiftRemitContract {
         let caller = get_admin(&env)?;
         require_admin(&env, &caller)?;
 
-        set_agent_registered(&env, &agent, false);
+        if !is_agent_registered(&env, &agent) {
+            return Ok(());
+        }
 
-        emit_agent_removed(&env, agent.clone(), caller.clone());
+        set_agent_registered(&env, &agent, false);
+        record_role_op(&env, AuditOp::AgentRemoved, &caller, &agent);
 
-        
         // Event: Agent removed - Fires when admin removes an agent from the approved list
         // Used by off-chain systems to revoke payout confirmation privileges
-        emit_agent_removed(&env, agent, caller.clone());
+        emit_agent_removed(&env, agent, caller);
+
+        Ok(())
+    }
+
+    /// Grants the admin role to another address.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - The contract execution environment
+    /// * `caller` - Existing admin granting the role
+    /// * `new_admin` - Address to make an admin
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - Admin added
+    /// * `Err(ContractError::Unauthorized)` - Caller is not an admin
+    /// * `Err(ContractError::AdminAlreadyExists)` - Address is already an admin
+    ///
+    /// # Authorization
+    ///
+    /// Requires authentication from the caller.
+    pub fn add_admin(env: Env, caller: Address, new_admin: Address) -> Result<(), ContractError> {
+        require_admin(&env, &caller)?;
+
+        if is_admin(&env, &new_admin) {
+            return Err(ContractError::AdminAlreadyExists);
+        }
+
+        set_admin_role(&env, &new_admin, true);
+        set_admin_count(&env, get_admin_count(&env) + 1);
+        record_role_op(&env, AuditOp::AdminAdded, &caller, &new_admin);
+
+        emit_admin_added(&env, caller, new_admin);
+
+        Ok(())
+    }
+
+    /// Revokes an address's admin role. The last admin cannot be removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - The contract execution environment
+    /// * `caller` - Existing admin revoking the role
+    /// * `admin` - Admin to remove
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - Admin removed
+    /// * `Err(ContractError::Unauthorized)` - Caller is not an admin
+    /// * `Err(ContractError::AdminNotFound)` - Address is not an admin
+    /// * `Err(ContractError::CannotRemoveLastAdmin)` - Address is the only admin
+    ///
+    /// # Authorization
+    ///
+    /// Requires authentication from the caller.
+    pub fn remove_admin(env: Env, caller: Address, admin: Address) -> Result<(), ContractError> {
+        require_admin(&env, &caller)?;
+
+        if !is_admin(&env, &admin) {
+            return Err(ContractError::AdminNotFound);
+        }
+
+        let admin_count = get_admin_count(&env);
+        if admin_count <= 1 {
+            return Err(ContractError::CannotRemoveLastAdmin);
+        }
+
+        set_admin_role(&env, &admin, false);
+        set_admin_count(&env, admin_count - 1);
+        record_role_op(&env, AuditOp::AdminRemoved, &caller, &admin);
+
+        emit_admin_removed(&env, caller, admin);
+
+        Ok(())
+    }
+
+    /// Returns whether an address holds the admin role.
+    pub fn is_admin(env: Env, address: Address) -> bool {
+        is_admin(&env, &address)
+    }
+
+    /// Lists admins at positions `start` to `start + limit`, reading at most
+    /// `MAX_REGISTRY_PAGE_SIZE` positions. Removed admins leave their position
+    /// empty, so a page can be shorter than `limit` and the remaining admins
+    /// keep their positions; the list ends at `get_admin_list_len`.
+    pub fn list_admins(env: Env, start: u32, limit: u32) -> Vec<Address> {
+        list_admins(&env, start, limit.min(MAX_REGISTRY_PAGE_SIZE))
+    }
+
+    /// Returns the number of positions in the admin list, including positions
+    /// left empty by removed admins.
+    pub fn get_admin_list_len(env: Env) -> u32 {
+        admin_list_len(&env)
+    }
+
+    /// Returns the latest link of the audit chain.
+    pub fn get_audit_head(env: Env) -> AuditHead {
+        get_audit_head(&env)
+    }
+
+    /// Returns the sequence number of the latest event, or 0 if none has
+    /// been emitted. Every event carries its sequence number in its
+    /// envelope, so a consumer that has seen fewer events has missed some.
+    pub fn get_event_sequence(env: Env) -> u64 {
+        get_event_sequence(&env)
+    }
+
+    /// Delegates batch settlement of the agent's remittances to an operator.
+    ///
+    /// The operator can then authorize `batch_settle_with_netting` and
+    /// `batch_settle_best_effort` for any of the agent's remittances that fall
+    /// within `scope`, without a signature from the agent. Granting again
+    /// replaces the previous scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - The contract execution environment
+    /// * `agent` - Registered agent granting the delegation
+    /// * `operator` - Settlement operator receiving the delegation
+    /// * `scope` - Corridors, amount cap and expiry the delegation is limited to
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - Delegation stored
+    /// * `Err(ContractError::AgentNotRegistered)` - Agent is not registered
+    /// * `Err(ContractError::InvalidAddress)` - Operator is the agent itself
+    /// * `Err(ContractError::MigrationInProgress)` - A migration session is open
+    ///
+    /// # Authorization
+    ///
+    /// Requires authentication from the agent.
+    pub fn grant_settlement_delegation(
+        env: Env,
+        agent: Address,
+        operator: Address,
+        scope: DelegationScope,
+    ) -> Result<(), ContractError> {
+        validate_no_migration_session(&env)?;
+
+        agent.require_auth();
+
+        validate_agent_registered(&env, &agent)?;
+        validate_address(&operator)?;
+        if operator == agent {
+            return Err(ContractError::InvalidAddress);
+        }
+
+        let mut corridors = Vec::new(&env);
+        for corridor in scope.corridors.iter() {
+            corridors.push_back(normalize_corridor(&env, &corridor));
+        }
+
+        set_settlement_delegation(
+            &env,
+            &SettlementDelegation {
+                agent: agent.clone(),
+                operator: operator.clone(),
+                scope: DelegationScope { corridors, ..scope },
+            },
+        );
+        record_role_op(&env, AuditOp::DelegationGranted, &agent, &operator);
+
+        emit_delegation_granted(&env, agent, operator);
+
+        Ok(())
+    }
+
+    /// Revokes a settlement delegation previously granted by the agent.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - Delegation removed
+    /// * `Err(ContractError::DelegationNotFound)` - No delegation to this operator exists
+    /// * `Err(ContractError::MigrationInProgress)` - A migration session is open
+    ///
+    /// # Authorization
+    ///
+    /// Requires authentication from the agent.
+    pub fn revoke_settlement_delegation(
+        env: Env,
+        agent: Address,
+        operator: Address,
+    ) -> Result<(), ContractError> {
+        validate_no_migration_session(&env)?;
+
+        agent.require_auth();
 
+        remove_settlement_delegation(&env, &agent, &operator)?;
+        record_role_op(&env, AuditOp::DelegationRevoked, &agent, &operator);
+
+        emit_delegation_revoked(&env, agent, operator);
 
         Ok(())
     }
 
+    /// Returns the settlement delegation from `agent` to `operator`, if any.
+    pub fn get_settlement_delegation(
+        env: Env,
+        agent: Address,
+        operator: Address,
+    ) -> Option<SettlementDelegation> {
+        get_settlement_delegation(&env, &agent, &operator)
+    }
+
     /// Updates the platform fee rate.
     ///
     /// Only the contract admin can update the fee. The new fee applies to all
@@ -201,6 +425,7 @@ impl SwiftRemitContract {
 
         let old_fee = get_platform_fee_bps(&env)?;
         set_platform_fee_bps(&env, fee_bps);
+        record_fee_update(&env, AuditOp::FeeUpdated, &caller, old_fee, fee_bps);
         emit_fee_updated(&env, caller.clone(), old_fee, fee_bps);
 
         log_update_fee(&env, fee_bps);
@@ -220,13 +445,27 @@ impl SwiftRemitContract {
     /// * `sender` - Address initiating the remittance
     /// * `agent` - Address of the registered agent who will receive the payout
     /// * `amount` - Amount to remit in USDC (must be positive)
+    /// * `corridor` - Destination currency and country of the payout
     /// * `expiry` - Optional expiry timestamp (seconds since epoch) after which settlement fails
+    /// * `idempotency_key` - Optional client key; a retry with the same key and identical
+    ///   parameters returns the original remittance ID without moving funds again
+    /// * `travel_rule_commitment` - Hash of the travel-rule payload exchanged with the
+    ///   beneficiary VASP; required at or above the corridor's travel-rule threshold
+    /// * `fx_quote_id` - Optional quote from `lock_quote`; its rate and destination amount
+    ///   are recorded against the remittance
     ///
     /// # Returns
     ///
-    /// * `Ok(remittance_id)` - Unique ID of the created remittance
+    /// * `Ok(remittance_id)` - Unique ID of the created (or previously created) remittance
     /// * `Err(ContractError::InvalidAmount)` - Amount is zero or negative
     /// * `Err(ContractError::AgentNotRegistered)` - Specified agent is not registered
+    /// * `Err(ContractError::InvalidIdempotencyKey)` - Key is empty or too long
+    /// * `Err(ContractError::IdempotencyConflict)` - Key was used with different parameters
+    /// * `Err(ContractError::TravelRuleCommitmentRequired)` - Amount requires travel-rule data
+    /// * `Err(ContractError::FxQuoteNotFound)` - Quote does not exist or was already used
+    /// * `Err(ContractError::FxQuoteExpired)` - Quote TTL has elapsed
+    /// * `Err(ContractError::FxQuoteMismatch)` - Quote was locked for different parameters
+    /// * `Err(ContractError::FxRateDeviation)` - Market moved too far since the quote was locked
     /// * `Err(ContractError::Overflow)` - Arithmetic overflow in fee calculation
     /// * `Err(ContractError::NotInitialized)` - Contract not initialized
     ///
@@ -238,18 +477,61 @@ impl SwiftRemitContract {
     sender: Address,
     agent: Address,
     amount: i128,
+    corridor: Corridor,
     expiry: Option<u64>,
+    idempotency_key: Option<String>,
+    travel_rule_commitment: Option<BytesN<32>>,
+    fx_quote_id: Option<u64>,
 ) -> Result<u64, ContractError> {
     validate_create_remittance_request(&env, &sender, &agent, amount)?;
 
     sender.require_auth();
 
-    let fee_bps = get_platform_fee_bps(&env)?;
-    let fee = amount
-        .checked_mul(fee_bps as i128)
-        .ok_or(ContractError::Overflow)?
-        .checked_div(10000)
-        .ok_or(ContractError::Overflow)?;
+    let corridor = normalize_corridor(&env, &corridor);
+
+    // Replay a previous call with the same key instead of debiting again
+    let request_hash = compute_request_hash(
+        &env,
+        &sender,
+        &agent,
+        amount,
+        &corridor,
+        expiry,
+        &travel_rule_commitment,
+        fx_quote_id,
+    );
+    if let Some(key) = &idempotency_key {
+        validate_idempotency_key(key)?;
+
+        if let Some(record) = get_idempotency_record(&env, &sender, key) {
+            if env.ledger().timestamp() <= record.expires_at {
+                if record.request_hash != request_hash {
+                    return Err(ContractError::IdempotencyConflict);
+                }
+                return Ok(record.remittance_id);
+            }
+        }
+    }
+
+    if travel_rule_commitment.is_none() && requires_travel_rule(&env, &corridor, amount) {
+        return Err(ContractError::TravelRuleCommitmentRequired);
+    }
+
+    let transfers = Self::check_daily_limit(&env, &sender, &corridor, amount)?;
+
+    let (fee, integrator_fee) = Self::compute_fees(&env, amount)?;
+
+    let fx_quote = match fx_quote_id {
+        Some(quote_id) => {
+            let payout_amount = amount
+                .checked_sub(fee)
+                .ok_or(ContractError::Overflow)?
+                .checked_sub(integrator_fee)
+                .ok_or(ContractError::Overflow)?;
+            Some(consume_quote(&env, quote_id, &sender, &corridor, amount, payout_amount)?)
+        }
+        None => None,
+    };
 
     let usdc_token = get_usdc_token(&env)?;
     let token_client = token::Client::new(&env, &usdc_token);
@@ -258,20 +540,67 @@ impl SwiftRemitContract {
     let counter = get_remittance_counter(&env)?;
     let remittance_id = counter.checked_add(1).ok_or(ContractError::Overflow)?;
 
+    if let Some(mut transfers) = transfers {
+        transfers.push_back(TransferRecord {
+            timestamp: env.ledger().timestamp(),
+            amount,
+            corridor: corridor.clone(),
+        });
+        set_user_transfers(&env, &sender, &transfers);
+    }
+
     let remittance = Remittance {
         id: remittance_id,
         sender: sender.clone(),
         agent: agent.clone(),
         amount,
         fee,
+        integrator_fee,
         status: RemittanceStatus::Pending,
         expiry,
+        corridor,
     };
 
     set_remittance(&env, remittance_id, &remittance);
     set_remittance_counter(&env, remittance_id);
+    record_remittance_op(&env, AuditOp::Created, &sender, remittance_id, amount);
+    emit_remittance_created(&env, remittance_id, sender.clone(), agent.clone(), amount, fee, integrator_fee);
+
+    if let Some(quote) = fx_quote {
+        set_remittance_fx(&env, remittance_id, &quote);
+    }
+
+    if let Some(originator_commitment) = travel_rule_commitment {
+        set_travel_rule_record(
+            &env,
+            &TravelRuleRecord {
+                remittance_id,
+                originator_commitment: originator_commitment.clone(),
+                acknowledged: false,
+                beneficiary_ack: BytesN::from_array(&env, &[0u8; 32]),
+            },
+        );
+        emit_travel_rule_committed(&env, remittance_id, sender.clone(), originator_commitment);
+    }
 
-    Ok(remittance_id)  // ← capital O
+    if let Some(key) = idempotency_key {
+        let expires_at = env
+            .ledger()
+            .timestamp()
+            .saturating_add(get_idempotency_ttl(&env));
+        set_idempotency_record(
+            &env,
+            &sender,
+            &IdempotencyRecord {
+                key,
+                request_hash,
+                remittance_id,
+                expires_at,
+            },
+        );
+    }
+
+    Ok(remittance_id)
 }
     /// Confirms a remittance payout to the agent.
     ///
@@ -299,7 +628,7 @@ impl SwiftRemitContract {
     /// Requires authentication from the agent address assigned to the remittance.
     pub fn confirm_payout(env: Env, remittance_id: u64) -> Result<(), ContractError> {
         // Centralized validation before business logic
-        let mut remittance = validate_confirm_payout_request(&env, remittance_id)?;
+        let remittance = validate_confirm_payout_request(&env, remittance_id)?;
 
         remittance.agent.require_auth();
 
@@ -320,87 +649,205 @@ impl SwiftRemitContract {
             }
         }
 
-        // Check rate limit for sender
+        // Travel-rule exchange must be acknowledged before funds are released
+        validate_travel_rule_acknowledged(&env, remittance_id)?;
+
+        // Check the settlement cooldown and request rate limit for sender
+        check_rate_limit_cooldown(&env, &remittance.sender)?;
         check_rate_limit(&env, &remittance.sender)?;
 
         // Validate the agent address before transfer
         validate_address(&remittance.agent)?;
 
-        let payout_amount = remittance
-            .amount
-            .checked_sub(remittance.fee)
-            .ok_or(ContractError::Overflow)?
-            .checked_sub(remittance.integrator_fee)
-            .ok_or(ContractError::Overflow)?;
-
         let usdc_token = get_usdc_token(&env)?;
-        let token_client = token::Client::new(&env, &usdc_token);
-        token_client.transfer(
-            &env.current_contract_address(),
-            &remittance.agent,
-            &payout_amount,
-        );
-
-        let current_fees = get_accumulated_fees(&env)?;
-        let new_fees = current_fees
-            .checked_add(remittance.fee)
-            .ok_or(ContractError::Overflow)?;
-        set_accumulated_fees(&env, new_fees);
+        let sender = remittance.sender.clone();
+        let (_, payout_amount) = Self::settle_remittance(&env, remittance, &usdc_token, true)?;
 
-        let current_integrator_fees = get_accumulated_integrator_fees(&env)?;
-        let new_integrator_fees = current_integrator_fees
-            .checked_add(remittance.integrator_fee)
-            .ok_or(ContractError::Overflow)?;
-        set_accumulated_integrator_fees(&env, new_integrator_fees);
-
-        remittance.status = RemittanceStatus::Settled;
-        set_remittance(&env, remittance_id, &remittance);
-
-        // Mark settlement as executed to prevent duplicates
-        set_settlement_hash(&env, remittance_id);
-        
         // Update last settlement time for rate limiting
         let current_time = env.ledger().timestamp();
-        set_last_settlement_time(&env, &remittance.sender, current_time);
-
-        // Emit settlement completion event exactly once
-        // This event is emitted after all state transitions are committed
-        // and includes safeguards to prevent duplicate emission
-        if !has_settlement_event_emitted(&env, remittance_id) {
-            emit_settlement_completed(
-                &env,
-                remittance_id,
-                remittance.sender.clone(),
-                remittance.agent.clone(),
-                usdc_token.clone(),
-                payout_amount
-            );
-            set_settlement_event_emitted(&env, remittance_id);
-        }
-
-        // Event: Remittance completed - Fires when agent confirms fiat payout and USDC is released
-        // Used by off-chain systems to track successful settlements and update transaction status
-        emit_remittance_completed(&env, remittance_id, remittance.sender.clone(), remittance.agent.clone(), usdc_token.clone(), payout_amount);
+        set_last_settlement_time(&env, &sender, current_time);
 
         log_confirm_payout(&env, remittance_id, payout_amount);
 
-        Ok(remittance_id)
+        Ok(())
     }
 
+    /// Finalizes a completed remittance.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - The contract execution environment
+    /// * `caller` - Admin address finalizing the remittance
+    /// * `remittance_id` - ID of the remittance to finalize
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - Remittance finalized
+    /// * `Err(ContractError::RemittanceNotFound)` - Remittance ID does not exist
+    /// * `Err(ContractError::InvalidStatus)` - Remittance is not in Completed status
+    ///
+    /// # Authorization
+    ///
+    /// Requires authentication from an admin.
     pub fn finalize_remittance(env: Env, caller: Address, remittance_id: u64) -> Result<(), ContractError> {
         require_admin(&env, &caller)?;
         let mut remittance = get_remittance(&env, remittance_id)?;
 
-        if !remittance.status.can_transition_to(&RemittanceStatus::Finalized) {
-            return Err(ContractError::InvalidStateTransition);
+        if remittance.status != RemittanceStatus::Completed {
+            return Err(ContractError::InvalidStatus);
         }
 
         remittance.status = RemittanceStatus::Finalized;
         set_remittance(&env, remittance_id, &remittance);
+        record_remittance_op(
+            &env,
+            AuditOp::Finalized,
+            &caller,
+            remittance_id,
+            remittance.amount,
+        );
+
+        emit_remittance_finalized(&env, remittance_id, caller);
+
+        Ok(())
+    }
+
+    /// Records the agent's acknowledgement of a travel-rule exchange.
+    ///
+    /// Remittances created with a travel-rule commitment cannot be paid out
+    /// until the assigned agent submits the hash of the beneficiary VASP's
+    /// acknowledgement.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - The contract execution environment
+    /// * `remittance_id` - ID of the remittance
+    /// * `beneficiary_ack` - Hash of the beneficiary-side acknowledgement payload
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - Acknowledgement recorded
+    /// * `Err(ContractError::RemittanceNotFound)` - Remittance ID does not exist
+    /// * `Err(ContractError::InvalidStatus)` - Remittance is not in Pending status
+    /// * `Err(ContractError::TravelRuleRecordNotFound)` - Remittance has no travel-rule commitment
+    /// * `Err(ContractError::MigrationInProgress)` - A migration session is open
+    ///
+    /// # Authorization
+    ///
+    /// Requires authentication from the agent address assigned to the remittance.
+    pub fn acknowledge_travel_rule(
+        env: Env,
+        remittance_id: u64,
+        beneficiary_ack: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        validate_no_migration_session(&env)?;
+
+        let remittance = get_remittance(&env, remittance_id)?;
+
+        remittance.agent.require_auth();
+
+        validate_remittance_pending(&remittance)?;
+
+        let mut record = get_travel_rule_record(&env, remittance_id)
+            .ok_or(ContractError::TravelRuleRecordNotFound)?;
+        record.acknowledged = true;
+        record.beneficiary_ack = beneficiary_ack.clone();
+        set_travel_rule_record(&env, &record);
+
+        record_remittance_attachment(
+            &env,
+            AuditOp::TravelRuleAcknowledged,
+            &remittance.agent,
+            remittance_id,
+            &beneficiary_ack,
+        );
+        emit_travel_rule_acknowledged(
+            &env,
+            remittance_id,
+            remittance.agent,
+            record.originator_commitment,
+            beneficiary_ack,
+        );
 
         Ok(())
     }
 
+    /// Returns the travel-rule record for a remittance, if it has one.
+    pub fn get_travel_rule_record(env: Env, remittance_id: u64) -> Option<TravelRuleRecord> {
+        get_travel_rule_record(&env, remittance_id)
+    }
+
+    /// Locks an FX rate for a prospective remittance.
+    ///
+    /// Reads the corridor's rate from the configured price feed, computes how
+    /// much the recipient will receive in the destination currency after the
+    /// platform fee, and holds that rate for the configured quote TTL. Pass the
+    /// returned `quote_id` to `create_remittance` to use it.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - The contract execution environment
+    /// * `sender` - Address that will create the remittance
+    /// * `corridor` - Destination currency and country
+    /// * `amount` - Amount to remit in USDC (must be positive)
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(FxQuote)` - The locked quote
+    /// * `Err(ContractError::InvalidAmount)` - Amount is zero or negative
+    /// * `Err(ContractError::FxOracleNotConfigured)` - No price feed configured
+    /// * `Err(ContractError::FxRateUnavailable)` - Price feed has no fresh rate for the corridor
+    /// * `Err(ContractError::MigrationInProgress)` - A migration session is open
+    ///
+    /// # Authorization
+    ///
+    /// Requires authentication from the sender address.
+    pub fn lock_quote(
+        env: Env,
+        sender: Address,
+        corridor: Corridor,
+        amount: i128,
+    ) -> Result<FxQuote, ContractError> {
+        validate_no_migration_session(&env)?;
+
+        validate_amount(amount)?;
+
+        sender.require_auth();
+
+        let corridor = normalize_corridor(&env, &corridor);
+
+        let (fee, integrator_fee) = Self::compute_fees(&env, amount)?;
+        let payout_amount = amount
+            .checked_sub(fee)
+            .ok_or(ContractError::Overflow)?
+            .checked_sub(integrator_fee)
+            .ok_or(ContractError::Overflow)?;
+
+        let quote = fx::lock_quote(&env, &sender, &corridor, amount, payout_amount)?;
+
+        emit_fx_quote_locked(
+            &env,
+            quote.quote_id,
+            sender,
+            quote.corridor.clone(),
+            quote.rate,
+            quote.destination_amount,
+            quote.expires_at,
+        );
+
+        Ok(quote)
+    }
+
+    /// Returns an open FX quote, or `None` once it has been used or evicted.
+    pub fn get_quote(env: Env, quote_id: u64) -> Option<FxQuote> {
+        get_quote(&env, quote_id)
+    }
+
+    /// Returns the locked FX rate and destination amount recorded for a remittance.
+    pub fn get_remittance_fx(env: Env, remittance_id: u64) -> Option<FxQuote> {
+        get_remittance_fx(&env, remittance_id)
+    }
+
     /// Cancels a pending remittance and refunds the sender.
     ///
     /// Returns the full remittance amount to the sender and marks the remittance
@@ -434,8 +881,15 @@ impl SwiftRemitContract {
             &remittance.amount,
         );
 
-        remittance.status = RemittanceStatus::Failed;
+        remittance.status = RemittanceStatus::Cancelled;
         set_remittance(&env, remittance_id, &remittance);
+        record_remittance_op(
+            &env,
+            AuditOp::Cancelled,
+            &remittance.sender,
+            remittance_id,
+            remittance.amount,
+        );
 
         // Event: Remittance cancelled - Fires when sender cancels a pending remittance and receives full refund
         // Used by off-chain systems to track cancellations and update transaction status
@@ -466,18 +920,6 @@ impl SwiftRemitContract {
     /// # Authorization
     ///
     /// Requires authentication from the contract admin.
-    pub fn withdraw_fees(env: Env, to: Address) -> Result<(), ContractError> {
-        let admin = get_admin(&env)?;
-        admin.require_auth();
-
-        remittance.status = RemittanceStatus::Failed;
-        set_remittance(&env, remittance_id, &remittance);
-
-        log_cancel_remittance(&env, remittance_id);
-
-        Ok(())
-    }
-
     pub fn withdraw_fees(env: Env, to: Address) -> Result<(), ContractError> {
         // Centralized validation before business logic
         let fees = validate_withdraw_fees_request(&env, &to)?;
@@ -490,6 +932,7 @@ impl SwiftRemitContract {
         token_client.transfer(&env.current_contract_address(), &to, &fees);
 
         set_accumulated_fees(&env, 0);
+        record_fees_withdrawn(&env, &caller, &to, fees);
 
         // Event: Fees withdrawn - Fires when admin withdraws accumulated platform fees
         // Used by off-chain systems to track revenue collection and maintain financial records
@@ -515,17 +958,148 @@ impl SwiftRemitContract {
         get_remittance(&env, remittance_id)
     }
 
+    /// Returns the settlement ID recorded when a remittance was settled.
+    ///
+    /// The ID is the canonical SHA-256 hash defined in `hashing.rs`, so
+    /// partners can reference a settlement by it without recomputing it.
+    /// Returns `None` if the remittance has not been settled.
+    pub fn get_settlement_id(env: Env, remittance_id: u64) -> Option<BytesN<32>> {
+        get_settlement_id(&env, remittance_id)
+    }
 
-    pub fn get_accumulated_fees(env: Env) -> Result<i128, ContractError> {
-        get_accumulated_fees(&env)
+    /// Returns the hash schema version a remittance's settlement ID was
+    /// computed with, or `None` if no settlement ID is stored for it.
+    pub fn get_settlement_schema_version(env: Env, remittance_id: u64) -> Option<u32> {
+        get_settlement_schema_version(&env, remittance_id)
     }
 
-    /// Checks if an address is registered as an agent.
+    /// Checks an externally computed settlement ID against the contract's
+    /// canonical computation.
     ///
-    /// # Arguments
+    /// Recomputes the remittance's settlement ID under `schema_version` and
+    /// compares it with `candidate`, so integrators can test their own
+    /// implementation of any supported schema. Works for pending and settled
+    /// remittances alike. Schema v2 uses the contract's settlement token and
+    /// the remittance's current memo.
     ///
-    /// * `env` - The contract execution environment
-    /// * `agent` - Address to check
+    /// # Returns
+    ///
+    /// * `Ok(true)` - `candidate` matches
+    /// * `Ok(false)` - `candidate` does not match
+    /// * `Err(ContractError::RemittanceNotFound)` - Remittance ID does not exist
+    /// * `Err(ContractError::UnsupportedHashSchema)` - Schema version is not supported
+    pub fn verify_settlement_id(
+        env: Env,
+        remittance_id: u64,
+        candidate: BytesN<32>,
+        schema_version: u32,
+    ) -> Result<bool, ContractError> {
+        let remittance = get_remittance(&env, remittance_id)?;
+        let usdc_token = get_usdc_token(&env)?;
+        let memo = get_remittance_memo(&env, remittance_id);
+
+        let expected = compute_settlement_id_for_version(
+            &env,
+            &remittance,
+            schema_version,
+            &usdc_token,
+            &memo,
+        )?;
+
+        Ok(candidate == expected)
+    }
+
+    /// Attaches a 32-byte memo to a pending remittance.
+    ///
+    /// The memo carries the sender's payment reference (for example a bank
+    /// reference hash) and is part of settlement IDs from hash schema v2 on.
+    /// It can be replaced until the remittance settles.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - Memo attached
+    /// * `Err(ContractError::RemittanceNotFound)` - Remittance ID does not exist
+    /// * `Err(ContractError::InvalidStatus)` - Remittance is not pending
+    /// * `Err(ContractError::MigrationInProgress)` - A migration session is open
+    ///
+    /// # Authorization
+    ///
+    /// Requires authentication from the remittance's sender.
+    pub fn set_remittance_memo(
+        env: Env,
+        remittance_id: u64,
+        memo: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        validate_no_migration_session(&env)?;
+
+        let remittance = get_remittance(&env, remittance_id)?;
+
+        remittance.sender.require_auth();
+
+        validate_remittance_pending(&remittance)?;
+
+        set_remittance_memo(&env, remittance_id, &memo);
+
+        record_remittance_attachment(&env, AuditOp::MemoSet, &remittance.sender, remittance_id, &memo);
+        emit_memo_set(&env, remittance_id, remittance.sender, memo);
+
+        Ok(())
+    }
+
+    /// Returns the memo attached to a remittance, if any.
+    pub fn get_remittance_memo(env: Env, remittance_id: u64) -> Option<BytesN<32>> {
+        get_remittance_memo(&env, remittance_id)
+    }
+
+    /// Retrieves the settlement receipt of a remittance.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SettlementReceipt)` - Receipt issued when the remittance settled
+    /// * `Err(ContractError::RemittanceNotFound)` - No receipt exists for the remittance
+    pub fn get_receipt(env: Env, remittance_id: u64) -> Result<SettlementReceipt, ContractError> {
+        get_settlement_receipt(&env, remittance_id).ok_or(ContractError::RemittanceNotFound)
+    }
+
+    /// Returns the hash of a remittance's settlement receipt, computed over
+    /// the canonical encoding defined in `hashing.rs`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(BytesN<32>)` - Receipt hash
+    /// * `Err(ContractError::RemittanceNotFound)` - No receipt exists for the remittance
+    pub fn get_receipt_hash(env: Env, remittance_id: u64) -> Result<BytesN<32>, ContractError> {
+        let receipt =
+            get_settlement_receipt(&env, remittance_id).ok_or(ContractError::RemittanceNotFound)?;
+        Ok(compute_receipt_hash(&env, &receipt))
+    }
+
+    /// Retrieves the remittance settled under a settlement ID.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Remittance)` - The settled remittance
+    /// * `Err(ContractError::RemittanceNotFound)` - No settlement has this ID
+    pub fn get_remittance_by_settlement_id(
+        env: Env,
+        settlement_id: BytesN<32>,
+    ) -> Result<Remittance, ContractError> {
+        let remittance_id = get_settlement_remittance_id(&env, &settlement_id)
+            .ok_or(ContractError::RemittanceNotFound)?;
+        get_remittance(&env, remittance_id)
+    }
+
+
+    pub fn get_accumulated_fees(env: Env) -> Result<i128, ContractError> {
+        get_accumulated_fees(&env)
+    }
+
+    /// Checks if an address is registered as an agent.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - The contract execution environment
+    /// * `agent` - Address to check
     ///
     /// # Returns
     ///
@@ -535,6 +1109,20 @@ impl SwiftRemitContract {
         is_agent_registered(&env, &agent)
     }
 
+    /// Lists registered agents at positions `start` to `start + limit`, reading at most
+    /// `MAX_REGISTRY_PAGE_SIZE` positions. Removed agents leave their position
+    /// empty, so a page can be shorter than `limit` and the remaining agents
+    /// keep their positions; the list ends at `get_agent_list_len`.
+    pub fn list_agents(env: Env, start: u32, limit: u32) -> Vec<Address> {
+        list_agents(&env, start, limit.min(MAX_REGISTRY_PAGE_SIZE))
+    }
+
+    /// Returns the number of positions in the agent list, including positions
+    /// left empty by removed agents.
+    pub fn get_agent_list_len(env: Env) -> u32 {
+        agent_list_len(&env)
+    }
+
     /// Retrieves the current platform fee rate.
     ///
     /// # Arguments
@@ -553,6 +1141,28 @@ impl SwiftRemitContract {
         get_integrator_fee_bps(&env)
     }
 
+    /// Updates the integrator fee rate. Only admins can call this.
+    ///
+    /// # Parameters
+    /// - `caller`: Admin address (must be authorized)
+    /// - `fee_bps`: New integrator fee in basis points (max 10000)
+    ///
+    /// # Errors
+    /// - InvalidFeeBps: If `fee_bps` exceeds 10000
+    /// - Unauthorized: If caller is not admin
+    pub fn update_integrator_fee(env: Env, caller: Address, fee_bps: u32) -> Result<(), ContractError> {
+        require_admin(&env, &caller)?;
+        validate_fee_bps(fee_bps)?;
+
+        let old_fee = get_integrator_fee_bps(&env)?;
+        set_integrator_fee_bps(&env, fee_bps);
+        record_fee_update(&env, AuditOp::IntegratorFeeUpdated, &caller, old_fee, fee_bps);
+
+        emit_integrator_fee_updated(&env, caller, old_fee, fee_bps);
+
+        Ok(())
+    }
+
     pub fn get_accumulated_integrator_fees(env: Env) -> Result<i128, ContractError> {
         get_accumulated_integrator_fees(&env)
     }
@@ -562,6 +1172,7 @@ impl SwiftRemitContract {
         require_admin(&env, &caller)?;
 
         set_paused(&env, true);
+        record_pause_op(&env, AuditOp::Paused, &caller);
         emit_paused(&env, caller);
         Ok(())
     }
@@ -571,6 +1182,7 @@ impl SwiftRemitContract {
         require_admin(&env, &caller)?;
 
         set_paused(&env, false);
+        record_pause_op(&env, AuditOp::Unpaused, &caller);
         emit_unpaused(&env, caller);
         Ok(())
     }
@@ -579,12 +1191,13 @@ impl SwiftRemitContract {
         crate::storage::is_paused(&env)
     }
     
-    pub fn update_rate_limit(env: Env, cooldown_seconds: u64) -> Result<(), ContractError> {
+    pub fn update_rate_limit_cooldown(env: Env, cooldown_seconds: u64) -> Result<(), ContractError> {
         let admin = get_admin(&env)?;
         admin.require_auth();
 
         let old_cooldown = get_rate_limit_cooldown(&env)?;
         set_rate_limit_cooldown(&env, cooldown_seconds);
+        record_cooldown_update(&env, &admin, old_cooldown, cooldown_seconds);
         
         emit_rate_limit_updated(&env, admin, old_cooldown, cooldown_seconds);
 
@@ -599,8 +1212,57 @@ impl SwiftRemitContract {
         get_last_settlement_time(&env, &sender)
     }
 
-    pub fn get_version(env: Env) -> soroban_sdk::String {
-        soroban_sdk::String::from_str(&env, env!("CARGO_PKG_VERSION"))
+    /// Sets how long idempotency keys are retained. Only admins can call this.
+    ///
+    /// # Parameters
+    /// - `caller`: Admin address (must be authorized)
+    /// - `ttl_seconds`: Retention window in seconds; keys older than this may be reused
+    ///
+    /// # Errors
+    /// - InvalidTtl: If `ttl_seconds` is zero or exceeds MAX_IDEMPOTENCY_TTL
+    /// - Unauthorized: If caller is not admin
+    pub fn set_idempotency_ttl(
+        env: Env,
+        caller: Address,
+        ttl_seconds: u64,
+    ) -> Result<(), ContractError> {
+        require_admin(&env, &caller)?;
+
+        if ttl_seconds == 0 || ttl_seconds > MAX_IDEMPOTENCY_TTL {
+            return Err(ContractError::InvalidTtl);
+        }
+
+        let old_ttl = get_idempotency_ttl(&env);
+        set_idempotency_ttl(&env, ttl_seconds);
+
+        record_idempotency_ttl_update(&env, &caller, old_ttl, ttl_seconds);
+        emit_idempotency_ttl_updated(&env, caller, old_ttl, ttl_seconds);
+
+        Ok(())
+    }
+
+    /// Returns the idempotency key retention window in seconds.
+    pub fn get_idempotency_ttl(env: Env) -> u64 {
+        get_idempotency_ttl(&env)
+    }
+
+    /// Looks up the retained idempotency record for a sender's key.
+    ///
+    /// # Returns
+    /// - `Some(IdempotencyRecord)`: If the key is still retained
+    /// - `None`: If the key was never used or its record has been evicted
+    pub fn get_idempotency_record(
+        env: Env,
+        sender: Address,
+        key: String,
+    ) -> Option<IdempotencyRecord> {
+        get_idempotency_record(&env, &sender, &key)
+    }
+
+    /// Returns the code version and the storage schema versions of the stored
+    /// data and of the running code.
+    pub fn get_version(env: Env) -> ContractVersion {
+        get_contract_version(&env)
     }
 
     /// Batch settle multiple remittances with net settlement optimization.
@@ -622,167 +1284,1011 @@ impl SwiftRemitContract {
     /// 
     /// Result: Single transfer of 10 USDC from A to B, total fees: 3.8
     /// 
+    /// # Netting Modes
+    /// - `Bilateral`: offsets flows between each pair of parties independently
+    /// - `Multilateral`: offsets every party's flows across the whole batch, so
+    ///   A->B, B->C, C->A of equal amounts moves no tokens at all
+    /// 
     /// # Parameters
     /// - `entries`: Vector of BatchSettlementEntry containing remittance IDs to settle
+    /// - `mode`: Netting strategy to apply
+    /// - `operator`: Settlement operator authorizing on the agents' behalf, or
+    ///   `None` to require authorization from every agent in the batch
     /// 
     /// # Returns
     /// BatchSettlementResult with list of successfully settled remittance IDs
     /// 
+    /// # Authorization
+    /// Requires authentication from each distinct agent in the batch, or from
+    /// `operator` if given, in which case every remittance must fall within a
+    /// delegation granted to it by the remittance's agent.
+    /// 
     /// # Errors
     /// - ContractPaused: Contract is in paused state
     /// - InvalidAmount: Batch size exceeds MAX_BATCH_SIZE or is empty
     /// - RemittanceNotFound: One or more remittance IDs don't exist
     /// - InvalidStatus: One or more remittances are not in Pending status
     /// - DuplicateSettlement: Duplicate remittance IDs in batch
+    /// - SettlementExpired: One or more remittances have expired
+    /// - TravelRuleAckRequired: One or more remittances await travel-rule acknowledgement
+    /// - SettlementNotDelegated: Operator lacks a delegation covering a remittance
     /// - Overflow: Arithmetic overflow in calculations
     pub fn batch_settle_with_netting(
         env: Env,
         entries: Vec<BatchSettlementEntry>,
+        mode: NettingMode,
+        operator: Option<Address>,
+    ) -> Result<BatchSettlementResult, ContractError> {
+        Self::settle_batch(env, entries, mode, operator, false)
+    }
+
+    /// Batch settle multiple remittances, skipping entries that fail validation.
+    ///
+    /// Behaves like `batch_settle_with_netting`, but instead of aborting when a
+    /// remittance is missing, expired, not pending, already settled or
+    /// repeated in the batch, it leaves that remittance untouched and nets
+    /// the remaining ones. Each excluded remittance ID is reported in
+    /// `BatchSettlementResult::skipped` with the code of the error that excluded it.
+    /// When settling through an operator, remittances outside its delegation
+    /// are skipped with `SettlementNotDelegated`.
+    ///
+    /// # Errors
+    /// - ContractPaused: Contract is in paused state
+    /// - InvalidAmount: Batch size exceeds MAX_BATCH_SIZE or is empty
+    /// - Overflow: Arithmetic overflow in calculations
+    pub fn batch_settle_best_effort(
+        env: Env,
+        entries: Vec<BatchSettlementEntry>,
+        mode: NettingMode,
+        operator: Option<Address>,
+    ) -> Result<BatchSettlementResult, ContractError> {
+        Self::settle_batch(env, entries, mode, operator, true)
+    }
+
+    /// Previews a batch settlement without executing it.
+    ///
+    /// Runs the same validation and netting as `batch_settle_with_netting`
+    /// and reports the resulting net transfers, how many payout transfers and
+    /// how much volume netting saves, the fees collected and any entries that
+    /// would fail validation. No state is modified and no authorization is
+    /// required.
+    ///
+    /// # Parameters
+    /// - `entries`: Vector of BatchSettlementEntry containing remittance IDs to preview
+    /// - `mode`: Netting strategy to apply
+    ///
+    /// # Errors
+    /// - ContractPaused: Contract is in paused state
+    /// - InvalidAmount: Batch size exceeds MAX_BATCH_SIZE or is empty
+    /// - Overflow: Arithmetic overflow in calculations
+    pub fn preview_batch_settlement(
+        env: Env,
+        entries: Vec<BatchSettlementEntry>,
+        mode: NettingMode,
+    ) -> Result<BatchSettlementPreview, ContractError> {
+        validate_not_paused(&env)?;
+
+        let (remittances, skipped) = Self::load_batch_entries(&env, &entries, &None, true)?;
+
+        let mut gross_volume: i128 = 0;
+        let mut total_fees: i128 = 0;
+        for remittance in remittances.iter() {
+            gross_volume = gross_volume
+                .checked_add(remittance_payout(&remittance)?)
+                .ok_or(ContractError::Overflow)?;
+            total_fees = total_fees
+                .checked_add(retained_fees(&remittance)?)
+                .ok_or(ContractError::Overflow)?;
+        }
+
+        let fee_collector = env.current_contract_address();
+        let transfers =
+            compute_net_settlements_for_mode(&env, &remittances, mode, &fee_collector)?;
+
+        let mut net_transfer_count: u32 = 0;
+        let mut net_volume: i128 = 0;
+        for transfer in transfers.iter() {
+            if let Some((_, payout_amount)) = net_transfer_payout(&transfer, &fee_collector)? {
+                net_transfer_count += 1;
+                net_volume = net_volume
+                    .checked_add(payout_amount)
+                    .ok_or(ContractError::Overflow)?;
+            }
+        }
+
+        Ok(BatchSettlementPreview {
+            transfers,
+            gross_transfer_count: remittances.len(),
+            net_transfer_count,
+            gross_volume,
+            net_volume,
+            total_fees,
+            skipped,
+        })
+    }
+
+    /// Estimates the resources settling a batch in one transaction would use.
+    ///
+    /// Validates the entries like `preview_batch_settlement`, leaving out any
+    /// that would fail, and applies the contract's cost model to the rest.
+    /// `max_safe_entries` is the largest number of leading valid entries that
+    /// can safely be settled in one transaction; when it is below
+    /// `entry_count`, split the batch or settle it through a multi-transaction
+    /// settlement batch. No state is modified and no authorization is required.
+    ///
+    /// # Parameters
+    /// - `entries`: Vector of BatchSettlementEntry containing remittance IDs to estimate
+    /// - `mode`: Netting strategy to apply
+    ///
+    /// # Errors
+    /// - ContractPaused: Contract is in paused state
+    /// - InvalidAmount: Batch size exceeds MAX_BATCH_SIZE or is empty
+    pub fn estimate_batch_settlement(
+        env: Env,
+        entries: Vec<BatchSettlementEntry>,
+        mode: NettingMode,
+    ) -> Result<BatchBudgetEstimate, ContractError> {
+        validate_not_paused(&env)?;
+
+        let (remittances, _) = Self::load_batch_entries(&env, &entries, &None, true)?;
+
+        let mut parties: Map<Address, ()> = Map::new(&env);
+        let mut max_safe_entries: u32 = 0;
+        let (mut cpu_instructions, mut memory_bytes) = estimate_settlement_cost(mode, 0, 0);
+
+        for (i, remittance) in remittances.iter().enumerate() {
+            let entry_count = i as u32 + 1;
+            parties.set(remittance.sender, ());
+            parties.set(remittance.agent, ());
+
+            (cpu_instructions, memory_bytes) =
+                estimate_settlement_cost(mode, entry_count, parties.len());
+
+            // Cost only grows, so the safe prefix ends at the first entry that does not fit
+            if max_safe_entries == i as u32 && fits_transaction_budget(cpu_instructions, memory_bytes) {
+                max_safe_entries = entry_count;
+            }
+        }
+
+        Ok(BatchBudgetEstimate {
+            entry_count: remittances.len(),
+            party_count: parties.len(),
+            cpu_instructions,
+            memory_bytes,
+            max_safe_entries,
+        })
+    }
+
+    /// Opens a multi-transaction settlement batch.
+    ///
+    /// For settlements too large for one transaction: entries are added with
+    /// `append_settlement_batch` over as many calls as needed, and the batch
+    /// is then settled with `finalize_settlement_batch`. The batch is netted
+    /// multilaterally.
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - Agent or settlement operator that will build and finalize the batch
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(u64)` - ID of the new batch
+    /// * `Err(ContractError::ContractPaused)` - Contract is paused
+    ///
+    /// # Authorization
+    ///
+    /// Requires authentication from `owner`.
+    pub fn open_settlement_batch(env: Env, owner: Address) -> Result<u64, ContractError> {
+        validate_not_paused(&env)?;
+        validate_no_migration_session(&env)?;
+
+        owner.require_auth();
+
+        let batch = open_settlement_batch(&env, &owner)?;
+
+        record_batch_opened(&env, &owner, batch.batch_id);
+        emit_batch_opened(&env, batch.batch_id, owner);
+
+        Ok(batch.batch_id)
+    }
+
+    /// Appends remittances to an open multi-transaction settlement batch.
+    ///
+    /// Each remittance is validated as for `batch_settle_with_netting`, its
+    /// position is added to the batch and it is locked until the batch has
+    /// settled it, so it can no longer be confirmed, cancelled or settled
+    /// elsewhere. A remittance whose agent is not the batch owner must fall
+    /// within a settlement delegation from that agent to the owner. The call
+    /// is atomic: if any entry fails, nothing is appended.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(u32)` - Number of remittances now in the batch
+    /// * `Err(ContractError::SettlementBatchNotFound)` - Batch does not exist
+    /// * `Err(ContractError::InvalidBatchState)` - Batch is being finalized or has completed
+    /// * `Err(ContractError::InvalidAmount)` - No entries, more than MAX_BATCH_SIZE entries, or the
+    ///   batch would exceed MAX_BATCHED_ENTRIES remittances or MAX_BATCHED_PARTIES parties
+    /// * `Err(ContractError::DuplicateSettlement)` - Remittance is already in this batch
+    /// * `Err(ContractError::RemittanceBatched)` - Remittance is locked in another batch
+    /// * `Err(ContractError::SettlementNotDelegated)` - Owner lacks a delegation covering a remittance
+    ///
+    /// # Authorization
+    ///
+    /// Requires authentication from the batch owner.
+    pub fn append_settlement_batch(
+        env: Env,
+        batch_id: u64,
+        entries: Vec<BatchSettlementEntry>,
+    ) -> Result<u32, ContractError> {
+        validate_not_paused(&env)?;
+        validate_no_migration_session(&env)?;
+
+        let mut batch = get_settlement_batch(&env, batch_id)?;
+        batch.owner.require_auth();
+
+        if batch.status != SettlementBatchStatus::Open {
+            return Err(ContractError::InvalidBatchState);
+        }
+
+        if entries.is_empty() || entries.len() > MAX_BATCH_SIZE {
+            return Err(ContractError::InvalidAmount);
+        }
+        if batch.remittance_ids.len() + entries.len() > MAX_BATCHED_ENTRIES {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let fee_collector = env.current_contract_address();
+        let mut appended = Vec::new(&env);
+
+        for entry in entries.iter() {
+            let remittance_id = entry.remittance_id;
+
+            if get_remittance_batch(&env, remittance_id) == Some(batch_id) {
+                return Err(ContractError::DuplicateSettlement);
+            }
+
+            let remittance = validate_batch_settlement_entry(&env, remittance_id)?;
+            if remittance.agent != batch.owner {
+                validate_delegation_covers(&env, &batch.owner, &remittance)?;
+            }
+
+            add_remittance_positions(&mut batch.positions, &remittance, &fee_collector)?;
+            lock_remittance_in_batch(&env, remittance_id, batch_id);
+            batch.remittance_ids.push_back(remittance_id);
+            appended.push_back(remittance_id);
+        }
+
+        if batch.positions.len() > MAX_BATCHED_PARTIES {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        set_settlement_batch(&env, &batch);
+
+        record_batch_membership(&env, AuditOp::BatchAppended, &batch.owner, batch_id, &appended);
+        emit_batch_appended(&env, batch_id, batch.owner.clone(), appended);
+
+        Ok(batch.remittance_ids.len())
+    }
+
+    /// Aborts an open multi-transaction settlement batch.
+    ///
+    /// Releases every remittance in the batch, so each can again be
+    /// confirmed, cancelled or appended to another batch. A batch whose
+    /// finalization has started can no longer be aborted, since some of its
+    /// transfers may already have executed.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - Batch aborted
+    /// * `Err(ContractError::SettlementBatchNotFound)` - Batch does not exist
+    /// * `Err(ContractError::InvalidBatchState)` - Batch is being finalized, has completed or
+    ///   was already aborted
+    ///
+    /// # Authorization
+    ///
+    /// Requires authentication from the batch owner.
+    pub fn abort_settlement_batch(env: Env, batch_id: u64) -> Result<(), ContractError> {
+        validate_no_migration_session(&env)?;
+
+        let mut batch = get_settlement_batch(&env, batch_id)?;
+        batch.owner.require_auth();
+
+        if batch.status != SettlementBatchStatus::Open {
+            return Err(ContractError::InvalidBatchState);
+        }
+
+        for remittance_id in batch.remittance_ids.iter() {
+            unlock_remittance_from_batch(&env, remittance_id);
+        }
+
+        let released = batch.remittance_ids.clone();
+        batch.status = SettlementBatchStatus::Aborted;
+        batch.remittance_ids = Vec::new(&env);
+        batch.positions = Map::new(&env);
+        set_settlement_batch(&env, &batch);
+
+        record_batch_membership(&env, AuditOp::BatchAborted, &batch.owner, batch_id, &released);
+        emit_batch_aborted(&env, batch_id, batch.owner.clone(), released);
+
+        Ok(())
+    }
+
+    /// Settles a multi-transaction settlement batch, one chunk per call.
+    ///
+    /// The first call closes the batch to new entries and computes the net
+    /// transfers for the accumulated positions. Every call then performs up
+    /// to FINALIZE_CHUNK_SIZE steps, executing the net transfers first and
+    /// then marking each remittance settled and releasing its lock, and
+    /// advances the batch's cursor. Call repeatedly until the returned batch
+    /// is `Completed`.
+    ///
+    /// Remittances are validated when appended; expiry is not re-checked
+    /// here, since their funds are already committed to the batch.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SettlementBatch)` - Batch after this call's progress
+    /// * `Err(ContractError::ContractPaused)` - Contract is paused
+    /// * `Err(ContractError::SettlementBatchNotFound)` - Batch does not exist
+    /// * `Err(ContractError::InvalidBatchState)` - Batch has already completed or was aborted
+    /// * `Err(ContractError::InvalidAmount)` - Batch has no entries
+    ///
+    /// # Authorization
+    ///
+    /// Requires authentication from the batch owner, except to continue a
+    /// batch that is being finalized once BATCH_LOCK_DURATION has passed since
+    /// it opened; any caller can then finish it.
+    pub fn finalize_settlement_batch(
+        env: Env,
+        batch_id: u64,
+    ) -> Result<SettlementBatch, ContractError> {
+        validate_not_paused(&env)?;
+        validate_no_migration_session(&env)?;
+
+        let mut batch = get_settlement_batch(&env, batch_id)?;
+        if batch.status != SettlementBatchStatus::Finalizing || !batch_lock_expired(&env, &batch) {
+            batch.owner.require_auth();
+        }
+
+        let fee_collector = env.current_contract_address();
+
+        match batch.status {
+            SettlementBatchStatus::Completed | SettlementBatchStatus::Aborted => {
+                return Err(ContractError::InvalidBatchState)
+            }
+            SettlementBatchStatus::Open => {
+                if batch.remittance_ids.is_empty() {
+                    return Err(ContractError::InvalidAmount);
+                }
+                let transfers = settle_positions(&env, &batch.positions, &fee_collector)?;
+                validate_position_settlement(&env, &batch.positions, &transfers)?;
+                batch.transfers = transfers;
+                batch.status = SettlementBatchStatus::Finalizing;
+            }
+            SettlementBatchStatus::Finalizing => {}
+        }
+
+        let usdc_token = get_usdc_token(&env)?;
+        let token_client = token::Client::new(&env, &usdc_token);
+
+        let transfer_count = batch.transfers.len();
+        let total_steps = transfer_count + batch.remittance_ids.len();
+        let end = total_steps.min(batch.cursor + FINALIZE_CHUNK_SIZE);
+
+        for step in batch.cursor..end {
+            if step < transfer_count {
+                let transfer = batch.transfers.get_unchecked(step);
+                Self::execute_net_transfer(&env, &token_client, &transfer, &fee_collector)?;
+            } else {
+                let remittance_id = batch.remittance_ids.get_unchecked(step - transfer_count);
+                let remittance = get_remittance(&env, remittance_id)?;
+                unlock_remittance_from_batch(&env, remittance_id);
+                let (settlement_id, _) =
+                    Self::settle_remittance(&env, remittance, &usdc_token, false)?;
+                batch.merkle.push(&env, &settlement_id);
+            }
+        }
+        batch.cursor = end;
+
+        if batch.cursor == total_steps {
+            batch.status = SettlementBatchStatus::Completed;
+            Self::commit_batch(&env, batch_id, &batch.merkle)?;
+            record_batch_finalized(
+                &env,
+                &batch.owner,
+                batch_id,
+                batch.remittance_ids.len(),
+                transfer_count,
+            );
+            emit_once(&env, EventAction::BatchSettlement(batch_id), || {
+                emit_batch_settled(
+                    &env,
+                    batch_id,
+                    batch.owner.clone(),
+                    batch.remittance_ids.len(),
+                    transfer_count,
+                )
+            });
+        }
+
+        set_settlement_batch(&env, &batch);
+
+        Ok(batch)
+    }
+
+    /// Releases a remittance from an expired multi-transaction settlement batch.
+    ///
+    /// Once BATCH_LOCK_DURATION has passed since an open batch was opened,
+    /// the sender of each remittance in it can take the remittance back out,
+    /// for example to cancel it when the owner never finalizes the batch. The
+    /// remittance's position is removed from the batch.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - Remittance released
+    /// * `Err(ContractError::RemittanceNotFound)` - Remittance does not exist
+    /// * `Err(ContractError::InvalidBatchState)` - Remittance is not locked in a batch, the
+    ///   batch is being finalized, or it opened less than BATCH_LOCK_DURATION ago
+    ///
+    /// # Authorization
+    ///
+    /// Requires authentication from the remittance sender.
+    pub fn release_batched_remittance(env: Env, remittance_id: u64) -> Result<(), ContractError> {
+        validate_no_migration_session(&env)?;
+
+        let remittance = get_remittance(&env, remittance_id)?;
+        remittance.sender.require_auth();
+
+        let batch_id =
+            get_remittance_batch(&env, remittance_id).ok_or(ContractError::InvalidBatchState)?;
+        let mut batch = get_settlement_batch(&env, batch_id)?;
+
+        if batch.status != SettlementBatchStatus::Open {
+            return Err(ContractError::InvalidBatchState);
+        }
+        if !batch_lock_expired(&env, &batch) {
+            return Err(ContractError::InvalidBatchState);
+        }
+
+        let fee_collector = env.current_contract_address();
+        remove_remittance_positions(&mut batch.positions, &remittance, &fee_collector)?;
+        if let Some(index) = batch.remittance_ids.first_index_of(remittance_id) {
+            batch.remittance_ids.remove(index);
+        }
+        unlock_remittance_from_batch(&env, remittance_id);
+        set_settlement_batch(&env, &batch);
+
+        record_queue_op(
+            &env,
+            AuditOp::BatchReleased,
+            &remittance.sender,
+            batch_id,
+            remittance_id,
+        );
+        emit_batch_released(&env, batch_id, remittance.sender.clone(), remittance_id);
+
+        Ok(())
+    }
+
+    /// Returns a multi-transaction settlement batch, if it exists.
+    pub fn get_settlement_batch(env: Env, batch_id: u64) -> Option<SettlementBatch> {
+        get_settlement_batch(&env, batch_id).ok()
+    }
+
+    /// Returns the Merkle commitment of a settled batch, if any.
+    pub fn get_batch_commitment(env: Env, batch_id: u64) -> Option<BatchCommitment> {
+        get_batch_commitment(&env, batch_id)
+    }
+
+    /// Verifies that a settlement ID was part of a settled batch.
+    ///
+    /// Checks `proof` against the Merkle root committed for `batch_id`.
+    /// Proofs can be built with `compute_merkle_proof` from the batch's
+    /// settlement IDs in settlement order.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(true)` - The settlement ID is included in the batch
+    /// * `Ok(false)` - The proof does not lead to the batch's root
+    /// * `Err(ContractError::BatchCommitmentNotFound)` - Batch has no commitment
+    pub fn verify_inclusion(
+        env: Env,
+        batch_id: u64,
+        settlement_id: BytesN<32>,
+        proof: Vec<MerkleProofStep>,
+    ) -> Result<bool, ContractError> {
+        let commitment = get_batch_commitment(&env, batch_id)
+            .ok_or(ContractError::BatchCommitmentNotFound)?;
+
+        Ok(verify_merkle_proof(&env, &commitment.merkle_root, &settlement_id, &proof))
+    }
+
+    /// Shared implementation of strict and best-effort batch settlement.
+    fn settle_batch(
+        env: Env,
+        entries: Vec<BatchSettlementEntry>,
+        mode: NettingMode,
+        operator: Option<Address>,
+        skip_invalid: bool,
     ) -> Result<BatchSettlementResult, ContractError> {
         if is_paused(&env) {
             return Err(ContractError::ContractPaused);
         }
+        validate_no_migration_session(&env)?;
+
+        if let Some(operator) = &operator {
+            operator.require_auth();
+        }
+
+        let (remittances, skipped) =
+            Self::load_batch_entries(&env, &entries, &operator, skip_invalid)?;
+
+        if remittances.is_empty() {
+            return Ok(BatchSettlementResult {
+                settled_ids: Vec::new(&env),
+                skipped,
+                batch_id: None,
+            });
+        }
+
+        // Without an operator, every agent in the batch must authorize it
+        if operator.is_none() {
+            let mut agents: Vec<Address> = Vec::new(&env);
+            for remittance in remittances.iter() {
+                if !agents.contains(&remittance.agent) {
+                    remittance.agent.require_auth();
+                    agents.push_back(remittance.agent);
+                }
+            }
+        }
+
+        let (settled_ids, settlement_ids, _) =
+            Self::execute_net_settlement(&env, &remittances, mode)?;
+
+        let mut merkle = MerkleFrontier::new(&env);
+        for settlement_id in settlement_ids.iter() {
+            merkle.push(&env, &settlement_id);
+        }
+        let batch_id = next_batch_id(&env)?;
+        Self::commit_batch(&env, batch_id, &merkle)?;
+
+        Ok(BatchSettlementResult {
+            settled_ids,
+            skipped,
+            batch_id: Some(batch_id),
+        })
+    }
+
+    /// Validates a batch's size and entries and loads the remittances to settle.
+    ///
+    /// With `skip_invalid`, entries failing validation are returned as
+    /// skipped instead of aborting.
+    fn load_batch_entries(
+        env: &Env,
+        entries: &Vec<BatchSettlementEntry>,
+        operator: &Option<Address>,
+        skip_invalid: bool,
+    ) -> Result<(Vec<Remittance>, Vec<SkippedSettlement>), ContractError> {
+        // Validate batch size
+        let batch_size = entries.len();
+        if batch_size == 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+        if batch_size > MAX_BATCH_SIZE {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        // Load all remittances and validate
+        let mut remittances = Vec::new(env);
+        let mut seen_ids: Map<u64, ()> = Map::new(env);
+        let mut skipped = Vec::new(env);
+
+        for i in 0..batch_size {
+            let entry = entries.get_unchecked(i);
+            let remittance_id = entry.remittance_id;
+
+            // Check for duplicate IDs in batch
+            let validated = if seen_ids.contains_key(remittance_id) {
+                Err(ContractError::DuplicateSettlement)
+            } else {
+                seen_ids.set(remittance_id, ());
+                validate_batch_settlement_entry(env, remittance_id).and_then(|remittance| {
+                    match operator {
+                        Some(operator) => {
+                            validate_delegation_covers(env, operator, &remittance)?;
+                            Ok(remittance)
+                        }
+                        None => Ok(remittance),
+                    }
+                })
+            };
+
+            match validated {
+                Ok(remittance) => remittances.push_back(remittance),
+                Err(error) if skip_invalid => {
+                    skipped.push_back(SkippedSettlement {
+                        remittance_id,
+                        code: error as u32,
+                    });
+                }
+                Err(error) => return Err(error),
+            }
+        }
+
+        Ok((remittances, skipped))
+    }
+
+    /// Stores and announces the Merkle commitment of a settled batch.
+    fn commit_batch(env: &Env, batch_id: u64, merkle: &MerkleFrontier) -> Result<(), ContractError> {
+        let merkle_root = merkle.root(env).ok_or(ContractError::InvalidAmount)?;
+
+        set_batch_commitment(
+            env,
+            &BatchCommitment {
+                batch_id,
+                merkle_root: merkle_root.clone(),
+                leaf_count: merkle.leaf_count,
+                committed_at: env.ledger().timestamp(),
+            },
+        );
+        emit_batch_committed(env, batch_id, merkle_root, merkle.leaf_count);
+
+        Ok(())
+    }
+
+    /// Nets the given validated remittances, executes the resulting
+    /// transfers and marks every remittance as settled.
+    ///
+    /// Returns the settled IDs, their settlement IDs and the total amount
+    /// paid out on-chain.
+    fn execute_net_settlement(
+        env: &Env,
+        remittances: &Vec<Remittance>,
+        mode: NettingMode,
+    ) -> Result<(Vec<u64>, Vec<BytesN<32>>, i128), ContractError> {
+        // Compute net settlements
+        let fee_collector = env.current_contract_address();
+        let net_transfers =
+            compute_net_settlements_for_mode(env, remittances, mode, &fee_collector)?;
+
+        // Validate net settlement calculations
+        validate_net_settlement(env, remittances, &net_transfers, mode, &fee_collector)?;
+
+        // Execute net transfers
+        let usdc_token = get_usdc_token(env)?;
+        let token_client = token::Client::new(env, &usdc_token);
+
+        let mut net_volume: i128 = 0;
+
+        for i in 0..net_transfers.len() {
+            let transfer = net_transfers.get_unchecked(i);
+            let payout_amount =
+                Self::execute_net_transfer(env, &token_client, &transfer, &fee_collector)?;
+            net_volume = net_volume
+                .checked_add(payout_amount)
+                .ok_or(ContractError::Overflow)?;
+        }
+
+        // Mark all remittances as completed and set settlement hashes
+        let mut settled_ids = Vec::new(env);
+        let mut settlement_ids = Vec::new(env);
+
+        for i in 0..remittances.len() {
+            let remittance = remittances.get_unchecked(i);
+            settled_ids.push_back(remittance.id);
+            let (settlement_id, _) = Self::settle_remittance(env, remittance, &usdc_token, false)?;
+            settlement_ids.push_back(settlement_id);
+        }
+
+        Ok((settled_ids, settlement_ids, net_volume))
+    }
+
+    /// Executes one net transfer from the contract. Its fees accrue when the
+    /// netted remittances are settled.
+    ///
+    /// Returns the amount paid out on-chain.
+    fn execute_net_transfer(
+        env: &Env,
+        token_client: &token::Client,
+        transfer: &NetTransfer,
+        fee_collector: &Address,
+    ) -> Result<i128, ContractError> {
+        // Execute the net transfer from contract to recipient
+        // Note: The sender's funds are already in the contract from create_remittance.
+        let mut paid_out = 0;
+        if let Some((to, payout_amount)) = net_transfer_payout(transfer, fee_collector)? {
+            token_client.transfer(&env.current_contract_address(), &to, &payout_amount);
+            paid_out = payout_amount;
+        }
+
+        Ok(paid_out)
+    }
+
+    /// Computes the platform and integrator fees charged on `amount` at the
+    /// current rates.
+    fn compute_fees(env: &Env, amount: i128) -> Result<(i128, i128), ContractError> {
+        let fee = amount
+            .checked_mul(get_platform_fee_bps(env)? as i128)
+            .ok_or(ContractError::Overflow)?
+            .checked_div(10000)
+            .ok_or(ContractError::Overflow)?;
+        let integrator_fee = amount
+            .checked_mul(get_integrator_fee_bps(env)? as i128)
+            .ok_or(ContractError::Overflow)?
+            .checked_div(10000)
+            .ok_or(ContractError::Overflow)?;
+        if fee.checked_add(integrator_fee).ok_or(ContractError::Overflow)? > amount {
+            return Err(ContractError::InvalidFeeBps);
+        }
+        Ok((fee, integrator_fee))
+    }
 
-        // Validate batch size
-        let batch_size = entries.len();
-        if batch_size == 0 {
-            return Err(ContractError::InvalidAmount);
+    /// Checks `amount` against the daily send limit of `corridor` over the
+    /// trailing 24 hours of the sender's transfers in that corridor.
+    ///
+    /// Returns the sender's transfers still inside the window, or `None` when
+    /// the corridor has no limit and nothing needs recording.
+    fn check_daily_limit(
+        env: &Env,
+        sender: &Address,
+        corridor: &Corridor,
+        amount: i128,
+    ) -> Result<Option<Vec<TransferRecord>>, ContractError> {
+        let Some(daily) = get_daily_limit(env, &corridor.currency, &corridor.country) else {
+            return Ok(None);
+        };
+
+        let now = env.ledger().timestamp();
+        let mut recent = Vec::new(env);
+        let mut sent: i128 = 0;
+        for transfer in get_user_transfers(env, sender).iter() {
+            if now.saturating_sub(transfer.timestamp) >= DAILY_LIMIT_WINDOW_SECS {
+                continue;
+            }
+            if transfer.corridor == *corridor {
+                sent = sent.checked_add(transfer.amount).ok_or(ContractError::Overflow)?;
+            }
+            recent.push_back(transfer);
         }
-        if batch_size > MAX_BATCH_SIZE {
-            return Err(ContractError::InvalidAmount);
+
+        if sent.checked_add(amount).ok_or(ContractError::Overflow)? > daily.limit {
+            return Err(ContractError::DailySendLimitExceeded);
         }
+        Ok(Some(recent))
+    }
 
-        // Load all remittances and validate
-        let mut remittances = Vec::new(&env);
-        let mut seen_ids = Vec::new(&env);
+    /// Records a remittance as settled: computes its settlement ID under the
+    /// current hash schema, stores it, and issues the settlement receipt.
+    ///
+    /// Returns the settlement ID.
+    fn record_settlement(
+        env: &Env,
+        remittance: &Remittance,
+        usdc_token: &Address,
+        payout_amount: i128,
+    ) -> Result<BytesN<32>, ContractError> {
+        let memo = get_remittance_memo(env, remittance.id);
+        let settlement_id = compute_settlement_id_for_version(
+            env,
+            remittance,
+            HASH_SCHEMA_VERSION,
+            usdc_token,
+            &memo,
+        )?;
+        set_settlement_hash(env, remittance.id, &settlement_id, HASH_SCHEMA_VERSION);
+
+        let receipt = SettlementReceipt {
+            settlement_id: settlement_id.clone(),
+            settlement_schema_version: HASH_SCHEMA_VERSION,
+            remittance_id: remittance.id,
+            sender: remittance.sender.clone(),
+            agent: remittance.agent.clone(),
+            amount: remittance.amount,
+            fee: remittance.fee,
+            payout_amount,
+            expiry: remittance.expiry,
+            corridor: remittance.corridor.clone(),
+            status: remittance.status.clone(),
+            ledger_sequence: env.ledger().sequence(),
+            timestamp: env.ledger().timestamp(),
+        };
+        set_settlement_receipt(env, &receipt);
+        record_remittance_op(env, AuditOp::Settled, &remittance.agent, remittance.id, payout_amount);
+        emit_receipt_issued(
+            env,
+            remittance.id,
+            remittance.agent.clone(),
+            compute_receipt_hash(env, &receipt),
+        );
 
-        for i in 0..batch_size {
-            let entry = entries.get_unchecked(i);
-            let remittance_id = entry.remittance_id;
+        Ok(settlement_id)
+    }
 
-            // Check for duplicate IDs in batch
-            for j in 0..seen_ids.len() {
-                if seen_ids.get_unchecked(j) == remittance_id {
-                    return Err(ContractError::DuplicateSettlement);
-                }
-            }
-            seen_ids.push_back(remittance_id);
+    /// Settles a remittance: accrues its platform and integrator fees, marks it
+    /// completed, records its settlement hash and emits its completion events.
+    ///
+    /// With `pay_agent`, the payout is transferred to the agent; netted
+    /// remittances are paid through their batch's net transfers instead.
+    ///
+    /// Returns the remittance's settlement ID and payout amount.
+    fn settle_remittance(
+        env: &Env,
+        mut remittance: Remittance,
+        usdc_token: &Address,
+        pay_agent: bool,
+    ) -> Result<(BytesN<32>, i128), ContractError> {
+        let payout_amount = remittance_payout(&remittance)?;
 
-            // Load and validate remittance
-            let remittance = get_remittance(&env, remittance_id)?;
+        if pay_agent {
+            token::Client::new(env, usdc_token).transfer(
+                &env.current_contract_address(),
+                &remittance.agent,
+                &payout_amount,
+            );
+        }
 
-            // Verify remittance is pending
-            if remittance.status != RemittanceStatus::Pending {
-                return Err(ContractError::InvalidStatus);
-            }
+        let new_fees = get_accumulated_fees(env)?
+            .checked_add(remittance.fee)
+            .ok_or(ContractError::Overflow)?;
+        set_accumulated_fees(env, new_fees);
 
-            // Check for duplicate settlement execution
-            if has_settlement_hash(&env, remittance_id) {
-                return Err(ContractError::DuplicateSettlement);
-            }
+        let new_integrator_fees = get_accumulated_integrator_fees(env)?
+            .checked_add(remittance.integrator_fee)
+            .ok_or(ContractError::Overflow)?;
+        set_accumulated_integrator_fees(env, new_integrator_fees);
 
-            // Check expiry
-            if let Some(expiry_time) = remittance.expiry {
-                let current_time = env.ledger().timestamp();
-                if current_time > expiry_time {
-                    return Err(ContractError::SettlementExpired);
-                }
-            }
+        remittance.status = RemittanceStatus::Completed;
+        set_remittance(env, remittance.id, &remittance);
 
-            // Validate addresses
-            validate_address(&remittance.agent)?;
+        let settlement_id = Self::record_settlement(env, &remittance, usdc_token, payout_amount)?;
 
-            remittances.push_back(remittance);
-        }
+        // Emit settlement completion event exactly once per remittance
+        // This ensures each finalized settlement has exactly one completion event
+        emit_once(env, EventAction::Settlement(remittance.id), || {
+            emit_settlement_completed(
+                env,
+                remittance.id,
+                settlement_id.clone(),
+                remittance.sender,
+                remittance.agent,
+                usdc_token.clone(),
+                payout_amount,
+            )
+        });
 
-        // Compute net settlements
-        let net_transfers = compute_net_settlements(&env, &remittances);
+        Ok((settlement_id, payout_amount))
+    }
 
-        // Validate net settlement calculations
-        validate_net_settlement(&remittances, &net_transfers)?;
+    /// Confirms a payout into the corridor's current settlement cycle.
+    ///
+    /// Instead of paying the agent immediately, the remittance is queued and
+    /// settled together with the rest of the cycle when `close_cycle` runs.
+    /// Opens a new cycle if the corridor has none open.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - The contract execution environment
+    /// * `remittance_id` - ID of the remittance to confirm
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(u64)` - ID of the cycle the remittance was queued in
+    /// * `Err(ContractError::SettlementCycleNotConfigured)` - Corridor has no settlement cycle
+    /// * `Err(ContractError::SettlementCycleFull)` - Open cycle already holds MAX_BATCH_SIZE remittances
+    /// * `Err(ContractError::RemittanceQueued)` - Remittance is already queued
+    ///
+    /// # Authorization
+    ///
+    /// Requires authentication from the agent address assigned to the remittance.
+    pub fn confirm_for_cycle(env: Env, remittance_id: u64) -> Result<u64, ContractError> {
+        let remittance = validate_confirm_payout_request(&env, remittance_id)?;
 
-        // Execute net transfers
-        let usdc_token = get_usdc_token(&env)?;
-        let token_client = token::Client::new(&env, &usdc_token);
+        remittance.agent.require_auth();
 
-        for i in 0..net_transfers.len() {
-            let transfer = net_transfers.get_unchecked(i);
+        // Travel-rule exchange must be acknowledged before funds are committed
+        validate_travel_rule_acknowledged(&env, remittance_id)?;
 
-            // Determine actual sender and recipient based on net_amount sign
-            let (from, to, amount) = if transfer.net_amount > 0 {
-                // Positive: party_a -> party_b
-                (transfer.party_a.clone(), transfer.party_b.clone(), transfer.net_amount)
-            } else if transfer.net_amount < 0 {
-                // Negative: party_b -> party_a
-                (transfer.party_b.clone(), transfer.party_a.clone(), -transfer.net_amount)
-            } else {
-                // Zero: complete offset, no transfer needed
-                continue;
-            };
+        let cycle = enqueue_remittance(&env, &remittance.corridor, remittance_id, MAX_BATCH_SIZE)?;
 
-            // Calculate payout amount (net amount minus fees)
-            let payout_amount = amount
-                .checked_sub(transfer.total_fees)
-                .ok_or(ContractError::Overflow)?;
+        record_queue_op(
+            &env,
+            AuditOp::CycleConfirmed,
+            &remittance.agent,
+            cycle.cycle_id,
+            remittance_id,
+        );
+        emit_remittance_queued(&env, cycle.cycle_id, remittance_id, remittance.agent);
 
-            // Execute the net transfer from contract to recipient
-            // Note: The sender's funds are already in the contract from create_remittance
-            token_client.transfer(
-                &env.current_contract_address(),
-                &to,
-                &payout_amount,
-            );
+        Ok(cycle.cycle_id)
+    }
 
-            // Accumulate fees
-            let current_fees = get_accumulated_fees(&env)?;
-            let new_fees = current_fees
-                .checked_add(transfer.total_fees)
-                .ok_or(ContractError::Overflow)?;
-            set_accumulated_fees(&env, new_fees);
-        }
+    /// Closes a corridor's open settlement cycle and settles it.
+    ///
+    /// Permissionless: anyone can close a cycle once its window has elapsed,
+    /// or earlier if it is full. The queued remittances are netted with the
+    /// corridor's configured netting mode, the net transfers are executed and
+    /// a `CycleSummary` is recorded.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CycleSummary)` - Summary of the closed cycle
+    /// * `Err(ContractError::ContractPaused)` - Contract is paused
+    /// * `Err(ContractError::SettlementCycleNotConfigured)` - Corridor has no settlement cycle
+    /// * `Err(ContractError::SettlementCycleNotFound)` - Corridor has no open cycle
+    /// * `Err(ContractError::SettlementCycleNotDue)` - Cycle window has not elapsed yet
+    pub fn close_cycle(env: Env, corridor: Corridor) -> Result<CycleSummary, ContractError> {
+        validate_not_paused(&env)?;
+        validate_no_migration_session(&env)?;
 
-        // Mark all remittances as completed and set settlement hashes
-        let mut settled_ids = Vec::new(&env);
+        let corridor = normalize_corridor(&env, &corridor);
+        let config = get_cycle_config(&env, &corridor)
+            .ok_or(ContractError::SettlementCycleNotConfigured)?;
+        let cycle = take_due_cycle(&env, &corridor, MAX_BATCH_SIZE)?;
 
-        for i in 0..remittances.len() {
-            let mut remittance = remittances.get_unchecked(i);
-            remittance.status = RemittanceStatus::Settled;
-            set_remittance(&env, remittance.id, &remittance);
-            set_settlement_hash(&env, remittance.id);
-            settled_ids.push_back(remittance.id);
+        let mut remittances = Vec::new(&env);
+        let mut participants: Map<Address, ()> = Map::new(&env);
+        let mut gross_volume: i128 = 0;
+        let mut fees: i128 = 0;
 
-            // Calculate payout amount for this remittance
-            let payout_amount = remittance
-                .amount
-                .checked_sub(remittance.fee)
+        for remittance_id in cycle.remittance_ids.iter() {
+            let remittance = get_remittance(&env, remittance_id)?;
+            validate_remittance_pending(&remittance)?;
+            validate_no_duplicate_settlement(&env, remittance_id)?;
+
+            gross_volume = gross_volume
+                .checked_add(remittance_payout(&remittance)?)
+                .ok_or(ContractError::Overflow)?;
+            fees = fees
+                .checked_add(retained_fees(&remittance)?)
                 .ok_or(ContractError::Overflow)?;
 
-            // Emit settlement completion event exactly once per remittance
-            // This ensures each finalized settlement has exactly one completion event
-            if !has_settlement_event_emitted(&env, remittance.id) {
-                emit_settlement_completed(
-                    &env,
-                    remittance.id,
-                    remittance.sender.clone(),
-                    remittance.agent.clone(),
-                    usdc_token.clone(),
-                    payout_amount,
-                );
-                set_settlement_event_emitted(&env, remittance.id);
-            }
+            participants.set(remittance.sender.clone(), ());
+            participants.set(remittance.agent.clone(), ());
 
-            // Emit individual remittance completion event
-            emit_remittance_completed(
-                &env,
-                remittance.id,
-                remittance.sender.clone(),
-                remittance.agent.clone(),
-                usdc_token.clone(),
-                payout_amount,
-            );
+            remittances.push_back(remittance);
         }
 
-        Ok(BatchSettlementResult { settled_ids })
+        let (remittance_ids, _, net_volume) =
+            Self::execute_net_settlement(&env, &remittances, config.mode)?;
+
+        let summary = CycleSummary {
+            cycle_id: cycle.cycle_id,
+            corridor: corridor.clone(),
+            opened_at: cycle.opened_at,
+            closed_at: env.ledger().timestamp(),
+            remittance_ids,
+            participants: participants.keys(),
+            gross_volume,
+            net_volume,
+            fees,
+        };
+        set_cycle_summary(&env, &summary);
+
+        record_cycle_closed(
+            &env,
+            &env.current_contract_address(),
+            cycle.cycle_id,
+            gross_volume,
+            net_volume,
+            fees,
+        );
+        emit_once(&env, EventAction::CycleClose(cycle.cycle_id), || {
+            emit_cycle_closed(&env, cycle.cycle_id, corridor, gross_volume, net_volume, fees)
+        });
+
+        Ok(summary)
+    }
+
+    /// Returns the open settlement cycle for a corridor, if any.
+    pub fn get_open_cycle(env: Env, corridor: Corridor) -> Option<SettlementCycle> {
+        let corridor = normalize_corridor(&env, &corridor);
+
+        get_open_cycle(&env, &corridor)
+    }
+
+    /// Returns the summary of a closed settlement cycle, if it exists.
+    pub fn get_cycle_summary(env: Env, cycle_id: u64) -> Option<CycleSummary> {
+        get_cycle_summary(&env, cycle_id)
     }
 
     /// Add a token to the whitelist. Only admins can call this.
@@ -795,6 +2301,7 @@ impl SwiftRemitContract {
         }
 
         set_token_whitelisted(&env, &token, true);
+        record_role_op(&env, AuditOp::TokenWhitelisted, &caller, &token);
         
         // Event: Token whitelisted - Fires when admin adds a token to the approved list
         // Used by off-chain systems to track which tokens can be used for remittances
@@ -814,6 +2321,7 @@ impl SwiftRemitContract {
         }
 
         set_token_whitelisted(&env, &token, false);
+        record_role_op(&env, AuditOp::TokenRemoved, &caller, &token);
         
         // Event: Token removed - Fires when admin removes a token from the approved list
         // Used by off-chain systems to track which tokens are no longer accepted for remittances
@@ -828,6 +2336,20 @@ impl SwiftRemitContract {
         is_token_whitelisted(&env, &token)
     }
 
+    /// Lists whitelisted tokens at positions `start` to `start + limit`, reading at most
+    /// `MAX_REGISTRY_PAGE_SIZE` positions. Removed tokens leave their position
+    /// empty, so a page can be shorter than `limit` and the remaining tokens
+    /// keep their positions; the list ends at `get_whitelisted_token_list_len`.
+    pub fn list_whitelisted_tokens(env: Env, start: u32, limit: u32) -> Vec<Address> {
+        list_whitelisted_tokens(&env, start, limit.min(MAX_REGISTRY_PAGE_SIZE))
+    }
+
+    /// Returns the number of positions in the token list, including positions
+    /// left empty by removed tokens.
+    pub fn get_whitelisted_token_list_len(env: Env) -> u32 {
+        whitelisted_token_list_len(&env)
+    }
+
     /// Update rate limit configuration. Only admins can call this.
     /// 
     /// # Parameters
@@ -856,10 +2378,14 @@ impl SwiftRemitContract {
             enabled,
         };
 
-        set_rate_limit_config(&env, config);
+        let old_config = get_rate_limit_config(&env);
+        set_rate_limit_config(&env, config.clone());
+        record_rate_limit_update(&env, &caller, &config);
 
         log_update_rate_limit(&env, max_requests, window_seconds, enabled);
 
+        emit_rate_limit_config_updated(&env, caller, old_config, config);
+
         Ok(())
     }
 
@@ -903,6 +2429,11 @@ impl SwiftRemitContract {
     /// # Returns
     /// MigrationSnapshot containing complete contract state
     /// 
+    /// # Errors
+    /// - InvalidMigrationBatch: More than `MAX_SNAPSHOT_ENTRIES` remittances, registry
+    ///   positions, cycles or batches; migrate the remittances in batches instead
+    /// - Unauthorized: Caller is not admin
+    /// 
     /// # Example
     /// ```ignore
     /// let snapshot = contract.export_migration_state(&admin)?;
@@ -941,6 +2472,7 @@ impl SwiftRemitContract {
     /// Ok(()) if import successful
     /// 
     /// # Errors
+    /// - MigrationInProgress: A batched migration session is open
     /// - AlreadyInitialized: Contract already has data
     /// - InvalidMigrationHash: Hash verification failed
     /// - Unauthorized: Caller is not admin
@@ -957,7 +2489,14 @@ impl SwiftRemitContract {
         snapshot: MigrationSnapshot,
     ) -> Result<(), ContractError> {
         caller.require_auth();
-        migration::import_state(&env, snapshot)
+
+        let verification_hash = snapshot.verification_hash.clone();
+        let remittance_count = snapshot.persistent_data.remittances.len();
+        migration::import_state(&env, snapshot)?;
+
+        emit_migration_imported(&env, caller, verification_hash, remittance_count);
+
+        Ok(())
     }
 
     /// Verify migration snapshot integrity without importing
@@ -1019,34 +2558,208 @@ impl SwiftRemitContract {
         migration::export_batch(&env, batch_number, batch_size)
     }
 
+    /// Open a migration session for a batch import
+    /// 
+    /// Declares the number of batches and the Merkle root over their hashes
+    /// (see `compute_migration_root`). Until the session is finalized the
+    /// contract refuses business operations.
+    /// 
+    /// # Parameters
+    /// - `caller`: Admin address (must be authorized)
+    /// - `expected_batches`: Number of batches the export produced
+    /// - `batches_root`: Root over the batch hashes, in batch order
+    /// 
+    /// # Errors
+    /// - MigrationInProgress: A session is already open
+    /// - InvalidMigrationBatch: No batches declared
+    /// - Unauthorized: Caller is not admin
+    pub fn begin_migration_session(
+        env: Env,
+        caller: Address,
+        expected_batches: u32,
+        batches_root: BytesN<32>,
+    ) -> Result<MigrationSession, ContractError> {
+        require_admin(&env, &caller)?;
+        let session = migration::begin_session(&env, expected_batches, batches_root)?;
+
+        record_migration_step(
+            &env,
+            AuditOp::MigrationSessionOpened,
+            &caller,
+            session.expected_batches,
+            &session.batches_root,
+        );
+        emit_migration_session_opened(
+            &env,
+            caller,
+            session.expected_batches,
+            session.batches_root.clone(),
+        );
+
+        Ok(session)
+    }
+
     /// Import state from batch
     /// 
-    /// Import a single batch of remittances with hash verification.
-    /// Batches should be imported in order (0, 1, 2, ...) for consistency.
+    /// Import the next batch of the open migration session with hash
+    /// verification. Batches must be imported in order (0, 1, 2, ...);
+    /// a repeated or out-of-order batch is rejected, and so is a batch whose
+    /// hash the proof does not place under the session's root.
     /// 
     /// # Parameters
     /// - `caller`: Admin address (must be authorized)
     /// - `batch`: Batch to import with verification hash
+    /// - `proof`: Inclusion proof of the batch hash under the session's root,
+    ///   from `compute_merkle_proof` over the batch hashes
     /// 
     /// # Returns
-    /// Ok(()) if import successful
+    /// The session with its progress updated
     /// 
     /// # Errors
-    /// - InvalidMigrationHash: Batch hash verification failed
+    /// - InvalidMigrationState: No session is open
+    /// - InvalidMigrationBatch: Batch is not the next expected one
+    /// - InvalidMigrationHash: Batch hash or inclusion proof verification failed
     /// - Unauthorized: Caller is not admin
     /// 
     /// # Example
     /// ```ignore
     /// let batch = get_batch_from_old_contract(0);
-    /// contract.import_migration_batch(&admin, batch)?;
+    /// let proof = compute_merkle_proof(&env, &batch_hashes, 0);
+    /// contract.import_migration_batch(&admin, batch, proof)?;
     /// ```
     pub fn import_migration_batch(
         env: Env,
         caller: Address,
         batch: MigrationBatch,
-    ) -> Result<(), ContractError> {
+        proof: Vec<MerkleProofStep>,
+    ) -> Result<MigrationSession, ContractError> {
+        require_admin(&env, &caller)?;
+
+        let batch_number = batch.batch_number;
+        let batch_hash = batch.batch_hash.clone();
+        let remittance_count = batch.remittances.len();
+        let session = migration::import_batch(&env, batch, &proof)?;
+
+        record_migration_step(
+            &env,
+            AuditOp::MigrationBatchImported,
+            &caller,
+            batch_number,
+            &batch_hash,
+        );
+        emit_migration_batch_imported(&env, caller, batch_number, batch_hash, remittance_count);
+
+        Ok(session)
+    }
+
+    /// Finalize the open migration session
+    /// 
+    /// Verifies every declared batch was imported and matches the declared
+    /// root, restores the remittance counter and unlocks business operations.
+    /// 
+    /// # Errors
+    /// - InvalidMigrationState: No session is open, or not every declared batch was imported
+    /// - InvalidMigrationHash: Imported batches do not match the declared root
+    /// - Unauthorized: Caller is not admin
+    pub fn finalize_migration_session(env: Env, caller: Address) -> Result<(), ContractError> {
+        require_admin(&env, &caller)?;
+        migration::finalize_session(&env)?;
+
+        let remittance_counter = get_remittance_counter(&env)?;
+        record_migration_finalized(&env, &caller, remittance_counter);
+        emit_migration_session_finalized(&env, caller, remittance_counter);
+
+        Ok(())
+    }
+
+    /// Abort the open migration session
+    /// 
+    /// Closes the session without finalizing it and unlocks business
+    /// operations. Batches already imported are not rolled back; the
+    /// remittance counter is advanced past them, and a new session re-imports
+    /// from batch 0 over them.
+    /// 
+    /// # Errors
+    /// - InvalidMigrationState: No session is open
+    /// - Unauthorized: Caller is not admin
+    pub fn abort_migration_session(env: Env, caller: Address) -> Result<(), ContractError> {
+        require_admin(&env, &caller)?;
+        let session = migration::abort_session(&env)?;
+
+        record_migration_step(
+            &env,
+            AuditOp::MigrationSessionAborted,
+            &caller,
+            session.next_batch,
+            &session.batches_root,
+        );
+        emit_migration_session_aborted(&env, caller, session.next_batch, session.batches_root);
+
+        Ok(())
+    }
+
+    /// Returns the open migration session and its progress, if any.
+    pub fn get_migration_session(env: Env) -> Option<MigrationSession> {
+        get_migration_session(&env)
+    }
+
+    /// Replaces the contract's code in place, keeping its address and storage.
+    ///
+    /// The new code may expect a newer storage schema; call `migrate_storage`
+    /// afterwards until `get_version` reports the latest schema version.
+    /// When the admin is a multisig account, its signing thresholds apply.
+    ///
+    /// # Parameters
+    /// - `caller`: Admin performing the upgrade
+    /// - `new_wasm_hash`: Hash of the already uploaded WASM to install
+    pub fn upgrade(env: Env, caller: Address, new_wasm_hash: BytesN<32>) -> Result<(), ContractError> {
+        require_admin(&env, &caller)?;
+        record_upgrade(&env, &caller, &new_wasm_hash);
+        upgrade_contract(&env, &caller, &new_wasm_hash);
+        Ok(())
+    }
+
+    /// Rewrites stored data written by older code in the current storage
+    /// layout, up to `limit` remittances per call.
+    ///
+    /// Continues where the previous call stopped. Once every remittance has
+    /// been rewritten the stored schema version is raised to the latest and
+    /// further calls do nothing. Reads decode older layouts in the meantime,
+    /// so the contract stays usable while the migration runs.
+    ///
+    /// # Parameters
+    /// - `caller`: Admin running the migration
+    /// - `limit`: Maximum remittances to rewrite, capped at `MAX_STORAGE_MIGRATION_BATCH`
+    pub fn migrate_storage(
+        env: Env,
+        caller: Address,
+        limit: u32,
+    ) -> Result<StorageMigrationStatus, ContractError> {
         require_admin(&env, &caller)?;
-        migration::import_batch(&env, batch)
+        migrate_storage(&env, &caller, limit)
+    }
+
+    /// Computes the digest of one section of contract state over `[start, end)`.
+    ///
+    /// Run on two deployments, for example before and after a migration,
+    /// matching digests show the range holds the same state on both. On a
+    /// mismatch, split the range and compare the halves to find the
+    /// differing entries. Remittance ranges are over remittance IDs, agent,
+    /// admin, token and limit ranges over positions in sorted order; the
+    /// config section ignores the range. At most `MAX_DIGEST_RANGE` entries
+    /// are covered; the returned digest reports the range it covers.
+    ///
+    /// # Parameters
+    /// - `section`: Section of state to digest
+    /// - `start`: First remittance ID or position
+    /// - `end`: Remittance ID or position after the last one
+    pub fn compute_state_digest(
+        env: Env,
+        section: StateSection,
+        start: u64,
+        end: u64,
+    ) -> Result<StateDigest, ContractError> {
+        compute_state_digest(&env, section, start, end)
     }
 
     /// Sets the daily send limit for a specific currency-country pair.
@@ -1079,8 +2792,11 @@ impl SwiftRemitContract {
         let currency = normalize_symbol(&env, &currency);
         let country = normalize_symbol(&env, &country);
 
-
+        let old_limit = get_daily_limit(&env, &currency, &country).map(|daily| daily.limit);
         set_daily_limit(&env, &currency, &country, limit);
+        record_daily_limit_update(&env, &admin, &currency, &country, limit);
+
+        emit_daily_limit_updated(&env, admin, currency, country, old_limit, limit);
 
         Ok(())
     }
@@ -1101,4 +2817,131 @@ impl SwiftRemitContract {
 
         get_daily_limit(&env, &currency, &country)
     }
+
+    /// Sets the amount at or above which remittances in a corridor must carry
+    /// a travel-rule commitment.
+    ///
+    /// # Parameters
+    /// - `caller`: Admin address (must be authorized)
+    /// - `corridor`: Destination currency and country
+    /// - `threshold`: Minimum amount requiring travel-rule data
+    ///
+    /// # Errors
+    /// - InvalidAmount: If threshold is not positive
+    /// - Unauthorized: If caller is not admin
+    pub fn set_travel_rule_threshold(
+        env: Env,
+        caller: Address,
+        corridor: Corridor,
+        threshold: i128,
+    ) -> Result<(), ContractError> {
+        require_admin(&env, &caller)?;
+        validate_amount(threshold)?;
+
+        let corridor = normalize_corridor(&env, &corridor);
+
+        let old_threshold = get_travel_rule_threshold(&env, &corridor);
+        set_travel_rule_threshold(&env, &corridor, threshold);
+
+        record_travel_rule_threshold_update(&env, &caller, &corridor, threshold);
+        emit_travel_rule_threshold_updated(&env, caller, corridor, old_threshold, threshold);
+
+        Ok(())
+    }
+
+    /// Gets the travel-rule threshold for a corridor.
+    ///
+    /// # Returns
+    /// - `Some(i128)`: If a threshold is configured
+    /// - `None`: If the corridor never requires travel-rule data
+    pub fn get_travel_rule_threshold(env: Env, corridor: Corridor) -> Option<i128> {
+        let corridor = normalize_corridor(&env, &corridor);
+
+        get_travel_rule_threshold(&env, &corridor)
+    }
+
+    /// Puts a corridor on a scheduled settlement cycle. Only admins can call this.
+    ///
+    /// Agents can then use `confirm_for_cycle` for the corridor's remittances,
+    /// which are settled together when the cycle is closed.
+    ///
+    /// # Parameters
+    /// - `caller`: Admin address (must be authorized)
+    /// - `corridor`: Destination currency and country
+    /// - `config`: Cycle length and netting mode
+    ///
+    /// # Errors
+    /// - InvalidAmount: If the cycle duration is zero
+    /// - Unauthorized: If caller is not admin
+    pub fn set_settlement_cycle(
+        env: Env,
+        caller: Address,
+        corridor: Corridor,
+        config: SettlementCycleConfig,
+    ) -> Result<(), ContractError> {
+        require_admin(&env, &caller)?;
+
+        if config.duration == 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let corridor = normalize_corridor(&env, &corridor);
+
+        let old_config = get_cycle_config(&env, &corridor);
+        set_cycle_config(&env, &corridor, &config);
+
+        record_settlement_cycle_update(&env, &caller, &corridor, &config);
+        emit_settlement_cycle_updated(&env, caller, corridor, old_config, config);
+
+        Ok(())
+    }
+
+    /// Gets the settlement cycle configuration for a corridor.
+    ///
+    /// # Returns
+    /// - `Some(SettlementCycleConfig)`: If the corridor settles in cycles
+    /// - `None`: If payouts in the corridor settle individually
+    pub fn get_settlement_cycle(env: Env, corridor: Corridor) -> Option<SettlementCycleConfig> {
+        let corridor = normalize_corridor(&env, &corridor);
+
+        get_cycle_config(&env, &corridor)
+    }
+
+    /// Configures the FX price feed and quote policy. Only admins can call this.
+    ///
+    /// # Parameters
+    /// - `caller`: Admin address (must be authorized)
+    /// - `config`: Oracle address, base currency code, staleness limit,
+    ///   allowed deviation and quote TTL
+    ///
+    /// # Errors
+    /// - InvalidTtl: If the quote TTL or staleness limit is zero, or the
+    ///   quote TTL exceeds MAX_QUOTE_TTL
+    /// - InvalidFeeBps: If max_deviation_bps exceeds 10000
+    /// - Unauthorized: If caller is not admin
+    pub fn set_fx_config(env: Env, caller: Address, config: FxConfig) -> Result<(), ContractError> {
+        require_admin(&env, &caller)?;
+
+        if config.quote_ttl == 0 || config.quote_ttl > MAX_QUOTE_TTL || config.max_staleness == 0 {
+            return Err(ContractError::InvalidTtl);
+        }
+        validate_fee_bps(config.max_deviation_bps)?;
+
+        let config = FxConfig {
+            base_currency: normalize_symbol(&env, &config.base_currency),
+            ..config
+        };
+        let old_config = get_fx_config(&env).ok();
+        set_fx_config(&env, &config);
+
+        record_fx_config_update(&env, &caller, &config);
+        emit_fx_config_updated(&env, caller, old_config, config);
+
+        Ok(())
+    }
+
+    /// Gets the FX configuration.
+    pub fn get_fx_config(env: Env) -> Result<FxConfig, ContractError> {
+        get_fx_config(&env)
+    }
 }
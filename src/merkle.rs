@@ -0,0 +1,271 @@
+//! Merkle commitments over settlement batches.
+//!
+//! When a batch settles, the contract builds a Merkle tree over the
+//! settlement IDs of its remittances, in settlement order, and stores the
+//! root under the batch ID. An auditor holding one settlement ID and its
+//! inclusion proof can then check it was part of the batch without the rest
+//! of the batch.
+//!
+//! ## Tree Shape
+//!
+//! The tree follows RFC 6962 (Certificate Transparency):
+//!
+//! - leaf = SHA-256(0x00 || settlement_id)
+//! - node = SHA-256(0x01 || left || right)
+//! - a node without a sibling is promoted unchanged to the next level
+//!
+//! The distinct leaf and node prefixes prevent a node from being presented
+//! as a leaf.
+
+use soroban_sdk::{contracttype, Bytes, BytesN, Env, Vec};
+
+/// One step of a Merkle inclusion proof, from the leaf towards the root.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MerkleProofStep {
+    /// Hash of the sibling node at this level
+    pub sibling: BytesN<32>,
+    /// Whether the sibling is the left child
+    pub sibling_on_left: bool,
+}
+
+/// Merkle commitment of a settled batch.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchCommitment {
+    /// Batch the commitment belongs to
+    pub batch_id: u64,
+    /// Root of the tree over the batch's settlement IDs
+    pub merkle_root: BytesN<32>,
+    /// Number of settlement IDs in the tree
+    pub leaf_count: u32,
+    /// Ledger timestamp the batch was committed at
+    pub committed_at: u64,
+}
+
+/// Incrementally built Merkle tree, for batches settled over several
+/// transactions. Keeps only the roots of its complete subtrees.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MerkleFrontier {
+    /// Roots of complete subtrees with their heights, largest first
+    pub nodes: Vec<(u32, BytesN<32>)>,
+    /// Number of leaves appended
+    pub leaf_count: u32,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub(crate) enum CommitmentKey {
+    /// Commitment per batch (persistent storage)
+    Commitment(u64),
+}
+
+impl MerkleFrontier {
+    /// Creates an empty tree.
+    pub fn new(env: &Env) -> Self {
+        MerkleFrontier {
+            nodes: Vec::new(env),
+            leaf_count: 0,
+        }
+    }
+
+    /// Appends a settlement ID as the next leaf.
+    pub fn push(&mut self, env: &Env, settlement_id: &BytesN<32>) {
+        let mut height = 0;
+        let mut hash = leaf_hash(env, settlement_id);
+
+        // Merge complete subtrees of equal height
+        while let Some((last_height, last_hash)) = self.nodes.last() {
+            if last_height != height {
+                break;
+            }
+            self.nodes.pop_back();
+            hash = node_hash(env, &last_hash, &hash);
+            height += 1;
+        }
+
+        self.nodes.push_back((height, hash));
+        self.leaf_count += 1;
+    }
+
+    /// Returns the root of the tree, or `None` if it has no leaves.
+    pub fn root(&self, env: &Env) -> Option<BytesN<32>> {
+        let (_, mut root) = self.nodes.last()?;
+        for i in (0..self.nodes.len() - 1).rev() {
+            let (_, left) = self.nodes.get_unchecked(i);
+            root = node_hash(env, &left, &root);
+        }
+        Some(root)
+    }
+}
+
+/// Hashes a settlement ID into a leaf.
+pub fn leaf_hash(env: &Env, settlement_id: &BytesN<32>) -> BytesN<32> {
+    let mut buf = Bytes::new(env);
+    buf.push_back(0x00);
+    buf.extend_from_array(&settlement_id.to_array());
+    env.crypto().sha256(&buf).into()
+}
+
+/// Hashes two child nodes into their parent.
+pub fn node_hash(env: &Env, left: &BytesN<32>, right: &BytesN<32>) -> BytesN<32> {
+    let mut buf = Bytes::new(env);
+    buf.push_back(0x01);
+    buf.extend_from_array(&left.to_array());
+    buf.extend_from_array(&right.to_array());
+    env.crypto().sha256(&buf).into()
+}
+
+/// Computes the root of the tree over `settlement_ids`, or `None` if empty.
+pub fn compute_merkle_root(env: &Env, settlement_ids: &Vec<BytesN<32>>) -> Option<BytesN<32>> {
+    let mut frontier = MerkleFrontier::new(env);
+    for settlement_id in settlement_ids.iter() {
+        frontier.push(env, &settlement_id);
+    }
+    frontier.root(env)
+}
+
+/// Builds the inclusion proof for the settlement ID at `index`.
+///
+/// Not used by the contract itself; provided so integrators and auditors
+/// can produce proofs with the same implementation.
+pub fn compute_merkle_proof(
+    env: &Env,
+    settlement_ids: &Vec<BytesN<32>>,
+    index: u32,
+) -> Vec<MerkleProofStep> {
+    let mut proof = Vec::new(env);
+
+    let mut level: Vec<BytesN<32>> = Vec::new(env);
+    for settlement_id in settlement_ids.iter() {
+        level.push_back(leaf_hash(env, &settlement_id));
+    }
+
+    let mut index = index;
+    while level.len() > 1 {
+        if index % 2 == 1 {
+            proof.push_back(MerkleProofStep {
+                sibling: level.get_unchecked(index - 1),
+                sibling_on_left: true,
+            });
+        } else if index + 1 < level.len() {
+            proof.push_back(MerkleProofStep {
+                sibling: level.get_unchecked(index + 1),
+                sibling_on_left: false,
+            });
+        }
+
+        let mut next = Vec::new(env);
+        let mut i = 0;
+        while i < level.len() {
+            if i + 1 < level.len() {
+                next.push_back(node_hash(env, &level.get_unchecked(i), &level.get_unchecked(i + 1)));
+            } else {
+                next.push_back(level.get_unchecked(i));
+            }
+            i += 2;
+        }
+
+        level = next;
+        index /= 2;
+    }
+
+    proof
+}
+
+/// Checks that `settlement_id` is a leaf of the tree with the given root.
+pub fn verify_merkle_proof(
+    env: &Env,
+    root: &BytesN<32>,
+    settlement_id: &BytesN<32>,
+    proof: &Vec<MerkleProofStep>,
+) -> bool {
+    let mut hash = leaf_hash(env, settlement_id);
+    for step in proof.iter() {
+        hash = if step.sibling_on_left {
+            node_hash(env, &step.sibling, &hash)
+        } else {
+            node_hash(env, &hash, &step.sibling)
+        };
+    }
+    hash == *root
+}
+
+/// Stores a batch commitment.
+pub fn set_batch_commitment(env: &Env, commitment: &BatchCommitment) {
+    env.storage()
+        .persistent()
+        .set(&CommitmentKey::Commitment(commitment.batch_id), commitment);
+}
+
+/// Gets the commitment of a batch, if it has been committed.
+pub fn get_batch_commitment(env: &Env, batch_id: u64) -> Option<BatchCommitment> {
+    env.storage()
+        .persistent()
+        .get(&CommitmentKey::Commitment(batch_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settlement_ids(env: &Env, count: u8) -> Vec<BytesN<32>> {
+        let mut ids = Vec::new(env);
+        for i in 0..count {
+            ids.push_back(BytesN::from_array(env, &[i; 32]));
+        }
+        ids
+    }
+
+    #[test]
+    fn test_single_leaf_root_is_leaf_hash() {
+        let env = Env::default();
+        let ids = settlement_ids(&env, 1);
+
+        let root = compute_merkle_root(&env, &ids).unwrap();
+
+        assert_eq!(root, leaf_hash(&env, &ids.get_unchecked(0)));
+        assert!(compute_merkle_proof(&env, &ids, 0).is_empty());
+    }
+
+    #[test]
+    fn test_unpaired_node_is_promoted() {
+        let env = Env::default();
+        let ids = settlement_ids(&env, 3);
+
+        let a = leaf_hash(&env, &ids.get_unchecked(0));
+        let b = leaf_hash(&env, &ids.get_unchecked(1));
+        let c = leaf_hash(&env, &ids.get_unchecked(2));
+        let expected = node_hash(&env, &node_hash(&env, &a, &b), &c);
+
+        assert_eq!(compute_merkle_root(&env, &ids), Some(expected));
+    }
+
+    #[test]
+    fn test_every_proof_verifies_against_root() {
+        let env = Env::default();
+
+        for count in 1..=9 {
+            let ids = settlement_ids(&env, count);
+            let root = compute_merkle_root(&env, &ids).unwrap();
+
+            for index in 0..ids.len() {
+                let proof = compute_merkle_proof(&env, &ids, index);
+                assert!(verify_merkle_proof(&env, &root, &ids.get_unchecked(index), &proof));
+            }
+        }
+    }
+
+    #[test]
+    fn test_proof_rejects_other_leaf() {
+        let env = Env::default();
+        let ids = settlement_ids(&env, 5);
+        let root = compute_merkle_root(&env, &ids).unwrap();
+
+        let proof = compute_merkle_proof(&env, &ids, 2);
+
+        assert!(!verify_merkle_proof(&env, &root, &ids.get_unchecked(3), &proof));
+        assert!(!verify_merkle_proof(&env, &root, &BytesN::from_array(&env, &[99; 32]), &proof));
+    }
+}
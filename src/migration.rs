@@ -1,11 +1,33 @@
-use soroban_sdk::{contracttype, Address, Bytes, BytesN, Env, Map, Vec};
+use soroban_sdk::{contracttype, xdr::ToXdr, Address, Bytes, BytesN, Env, Map, Vec};
 
-use crate::{ContractError, Remittance, RemittanceStatus};
+use crate::{
+    encode_receipt, encode_remittance, AuditHead, BatchCommitment, ContractError, Corridor,
+    CycleSummary, DailyLimit, EventAction, FxConfig, FxQuote, MerkleFrontier, MerkleProofStep,
+    RateLimitConfig, Remittance, SettlementBatch, SettlementCycle, SettlementCycleConfig,
+    SettlementDelegation, SettlementReceipt, TransferRecord, TravelRuleRecord,
+};
 
 /// Maximum number of items that can be exported/imported in a single batch
 /// to prevent excessive resource consumption
 pub const MAX_MIGRATION_BATCH_SIZE: u32 = 100;
 
+/// Maximum number of remittance IDs, registry positions, cycle IDs and batch
+/// IDs a full snapshot covers, each; `export_state` refuses larger contracts,
+/// whose remittances must be migrated with `export_batch` instead
+pub const MAX_SNAPSHOT_ENTRIES: u32 = MAX_MIGRATION_BATCH_SIZE;
+
+/// Snapshot schema version produced by `export_state` and accepted by
+/// `import_state`
+///
+/// Version 2 carries every key of the contract's core storage: integrator
+/// fees, rate-limit settings, daily limits, per-sender history and full
+/// settlement records were added to version 1. Version 3 hashes remittances
+/// and settlement records with the canonical encoding of `hashing.rs`.
+/// Version 4 adds the storage of the feature modules (`ModuleData`).
+/// Version 5 carries the memos, travel-rule records and FX quotes of a
+/// batch's remittances in `MigrationBatch`.
+pub const MIGRATION_SCHEMA_VERSION: u32 = 5;
+
 /// Migration state snapshot containing all contract data
 /// This structure ensures complete and verifiable state transfer
 #[contracttype]
@@ -26,6 +48,9 @@ pub struct MigrationSnapshot {
     /// Persistent storage data
     pub persistent_data: PersistentData,
 
+    /// Storage of the feature modules
+    pub module_data: ModuleData,
+
     /// Cryptographic hash of all data for integrity verification
     pub verification_hash: BytesN<32>,
 }
@@ -54,6 +79,21 @@ pub struct InstanceData {
 
     /// Number of admins
     pub admin_count: u32,
+
+    /// Integrator fee in basis points
+    pub integrator_fee_bps: u32,
+
+    /// Accumulated integrator fees
+    pub accumulated_integrator_fees: i128,
+
+    /// Cooldown in seconds between settlements per sender
+    pub rate_limit_cooldown: u64,
+
+    /// Request rate limiting configuration
+    pub rate_limit_config: RateLimitConfig,
+
+    /// Retention window in seconds for idempotency records
+    pub idempotency_ttl: u64,
 }
 
 /// Persistent storage data (per-entity data)
@@ -69,14 +109,125 @@ pub struct PersistentData {
     /// Admin roles
     pub admin_roles: Vec<Address>,
 
-    /// Settlement hashes (remittance IDs that have been settled)
-    pub settlement_hashes: Vec<u64>,
+    /// Settlement records of settled remittances
+    pub settlements: Vec<SettlementRecord>,
 
     /// Whitelisted tokens
     pub whitelisted_tokens: Vec<Address>,
+
+    /// Memos attached to remittances, by remittance ID
+    pub memos: Vec<(u64, BytesN<32>)>,
+
+    /// Configured daily send limits
+    pub daily_limits: Vec<DailyLimit>,
+
+    /// Rate-limit and daily-limit state of every sender
+    pub users: Vec<UserState>,
+}
+
+/// Storage of the feature modules: events, audit chain, FX, travel rule,
+/// settlement cycles, settlement batches and delegations
+///
+/// Open FX quotes live in temporary storage and are not carried; they expire
+/// within the quote TTL and senders lock a new quote against the new
+/// contract. Single-value struct fields that may be unset are a `Vec` with at
+/// most one element, for the reason given in the `events` module docs.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ModuleData {
+    /// Sequence number of the latest event, so the event stream continues
+    pub event_sequence: u64,
+
+    /// Latest link of the audit chain
+    pub audit_head: AuditHead,
+
+    /// FX configuration; empty if none is set
+    pub fx_config: Vec<FxConfig>,
+
+    /// Most recently allocated FX quote ID
+    pub fx_quote_counter: u64,
+
+    /// Quotes consumed by remittances, by remittance ID
+    pub remittance_fx: Vec<(u64, FxQuote)>,
+
+    /// Travel-rule thresholds by corridor
+    pub travel_rule_thresholds: Vec<(Corridor, i128)>,
+
+    /// Travel-rule records of remittances
+    pub travel_rule_records: Vec<TravelRuleRecord>,
+
+    /// Most recently allocated settlement cycle ID
+    pub cycle_counter: u64,
+
+    /// Settlement cycle configurations by corridor
+    pub cycle_configs: Vec<(Corridor, SettlementCycleConfig)>,
+
+    /// Open settlement cycles; the remittances queued in them are rebuilt
+    /// from these on import
+    pub open_cycles: Vec<SettlementCycle>,
+
+    /// Summaries of closed settlement cycles
+    pub cycle_summaries: Vec<CycleSummary>,
+
+    /// Most recently allocated batch ID
+    pub batch_counter: u64,
+
+    /// Multi-transaction settlement batches
+    pub settlement_batches: Vec<SettlementBatch>,
+
+    /// Remittances locked in a settlement batch, as (remittance ID, batch ID)
+    pub batch_members: Vec<(u64, u64)>,
+
+    /// Merkle commitments of settled batches
+    pub batch_commitments: Vec<BatchCommitment>,
+
+    /// Settlement delegations
+    pub delegations: Vec<SettlementDelegation>,
+
+    /// Batch settlements and cycle closes whose event has been emitted
+    pub announced: Vec<EventAction>,
+}
+
+/// Settlement state of a settled remittance
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SettlementRecord {
+    /// Settled remittance
+    pub remittance_id: u64,
+
+    /// Settlement ID as stored, not recomputed on import
+    pub settlement_id: BytesN<32>,
+
+    /// Hash schema version the settlement ID was computed with
+    pub schema_version: u32,
+
+    /// Settlement receipt; empty for remittances settled before receipts,
+    /// otherwise one receipt
+    pub receipt: Vec<SettlementReceipt>,
+
+    /// Whether the settlement completion event was emitted
+    pub event_emitted: bool,
+}
+
+/// Per-sender state
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct UserState {
+    /// Sender address
+    pub address: Address,
+
+    /// Timestamp of the sender's last settlement
+    pub last_settlement_time: Option<u64>,
+
+    /// Transfers counted against the sender's daily limit
+    pub transfers: Vec<TransferRecord>,
 }
 
 /// Migration batch for incremental export/import
+///
+/// Carries the remittances of one ID range with the state kept per
+/// remittance. Idempotency records are not carried, as in a full snapshot:
+/// they live in temporary storage and expire on their own.
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct MigrationBatch {
@@ -89,10 +240,55 @@ pub struct MigrationBatch {
     /// Remittances in this batch
     pub remittances: Vec<Remittance>,
 
+    /// Settlement records of the settled remittances in this batch
+    pub settlements: Vec<SettlementRecord>,
+
+    /// Memos attached to remittances in this batch, by remittance ID
+    pub memos: Vec<(u64, BytesN<32>)>,
+
+    /// Travel-rule records of remittances in this batch
+    pub travel_rule_records: Vec<TravelRuleRecord>,
+
+    /// Quotes consumed by remittances in this batch, by remittance ID
+    pub remittance_fx: Vec<(u64, FxQuote)>,
+
     /// Hash of this batch for verification
     pub batch_hash: BytesN<32>,
 }
 
+/// Batch import in progress
+///
+/// While a session is open the contract refuses business operations.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MigrationSession {
+    /// Number of batches declared when the session was opened
+    pub expected_batches: u32,
+
+    /// Declared Merkle root over the hashes of all batches
+    pub batches_root: BytesN<32>,
+
+    /// Batch number the next import must carry; equals the number of
+    /// batches imported so far
+    pub next_batch: u32,
+
+    /// Merkle tree over the hashes of the batches imported so far
+    pub imported: MerkleFrontier,
+
+    /// Highest remittance ID imported, restored as the counter on finalize
+    pub max_remittance_id: u64,
+
+    /// Timestamp the session was opened at
+    pub started_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub(crate) enum MigrationKey {
+    /// Open migration session (instance storage)
+    Session,
+}
+
 /// Migration verification result
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -115,6 +311,7 @@ pub struct MigrationVerification {
 /// This function creates a complete snapshot of all contract data including:
 /// - Instance storage (admin, token, fees, counters)
 /// - Persistent storage (remittances, agents, admins, hashes)
+/// - Feature module storage (events, audit, FX, travel rule, cycles, batches)
 /// - Cryptographic verification hash
 ///
 /// # Security
@@ -124,21 +321,30 @@ pub struct MigrationVerification {
 ///
 /// # Returns
 /// MigrationSnapshot containing all contract state
+///
+/// # Errors
+/// - InvalidMigrationBatch: The remittance counter, a registry, the cycle
+///   counter or the batch counter exceeds `MAX_SNAPSHOT_ENTRIES`
 pub fn export_state(env: &Env) -> Result<MigrationSnapshot, ContractError> {
     // Collect instance data
-    let instance_data = InstanceData {
-        admin: crate::storage::get_admin(env)?,
-        usdc_token: crate::storage::get_usdc_token(env)?,
-        platform_fee_bps: crate::storage::get_platform_fee_bps(env)?,
-        remittance_counter: crate::storage::get_remittance_counter(env)?,
-        accumulated_fees: crate::storage::get_accumulated_fees(env)?,
-        paused: crate::storage::is_paused(env),
-        admin_count: crate::storage::get_admin_count(env),
-    };
+    let instance_data = export_instance_data(env)?;
+
+    let counter = instance_data.remittance_counter;
+    for len in [
+        counter,
+        crate::storage::agent_list_len(env) as u64,
+        crate::storage::admin_list_len(env) as u64,
+        crate::storage::whitelisted_token_list_len(env) as u64,
+        crate::settlement_cycle::get_cycle_counter(env),
+        crate::batching::get_batch_counter(env),
+    ] {
+        if len > MAX_SNAPSHOT_ENTRIES as u64 {
+            return Err(ContractError::InvalidMigrationBatch);
+        }
+    }
 
     // Collect all remittances
     let mut remittances = Vec::new(env);
-    let counter = instance_data.remittance_counter;
     for id in 1..=counter {
         if let Ok(remittance) = crate::storage::get_remittance(env, id) {
             remittances.push_back(remittance);
@@ -146,33 +352,54 @@ pub fn export_state(env: &Env) -> Result<MigrationSnapshot, ContractError> {
     }
 
     // Collect registered agents
-    // Note: In production, you'd need a way to iterate over all agents
-    // For now, we'll use a placeholder that requires agents to be tracked separately
-    let agents = Vec::new(env);
+    let agents = crate::storage::list_agents(env, 0, MAX_SNAPSHOT_ENTRIES);
 
     // Collect admin roles
-    // Note: Similar to agents, would need iteration support
-    let admin_roles = Vec::new(env);
+    let admin_roles = crate::storage::list_admins(env, 0, MAX_SNAPSHOT_ENTRIES);
 
-    // Collect settlement hashes
-    let mut settlement_hashes = Vec::new(env);
+    // Collect settlement records and memos
+    let mut settlements = Vec::new(env);
+    let mut memos = Vec::new(env);
     for id in 1..=counter {
-        if crate::storage::has_settlement_hash(env, id) {
-            settlement_hashes.push_back(id);
+        if let Some(record) = export_settlement_record(env, id) {
+            settlements.push_back(record);
+        }
+        if let Some(memo) = crate::storage::get_remittance_memo(env, id) {
+            memos.push_back((id, memo));
         }
     }
 
     // Collect whitelisted tokens
-    let whitelisted_tokens = Vec::new(env);
+    let whitelisted_tokens = crate::storage::list_whitelisted_tokens(env, 0, MAX_SNAPSHOT_ENTRIES);
+
+    // Collect per-sender state; only senders have rate-limit or transfer history
+    let mut seen: Map<Address, ()> = Map::new(env);
+    let mut users = Vec::new(env);
+    for remittance in remittances.iter() {
+        if seen.contains_key(remittance.sender.clone()) {
+            continue;
+        }
+        seen.set(remittance.sender.clone(), ());
+        users.push_back(UserState {
+            last_settlement_time: crate::storage::get_last_settlement_time(env, &remittance.sender),
+            transfers: crate::storage::get_user_transfers(env, &remittance.sender),
+            address: remittance.sender,
+        });
+    }
 
     let persistent_data = PersistentData {
         remittances,
         agents,
         admin_roles,
-        settlement_hashes,
+        settlements,
         whitelisted_tokens,
+        memos,
+        daily_limits: crate::storage::list_daily_limits(env),
+        users,
     };
 
+    let module_data = export_module_data(env, counter);
+
     // Create snapshot
     let timestamp = env.ledger().timestamp();
     let ledger_sequence = env.ledger().sequence();
@@ -180,22 +407,132 @@ pub fn export_state(env: &Env) -> Result<MigrationSnapshot, ContractError> {
     // Compute verification hash
     let verification_hash = compute_snapshot_hash(
         env,
+        MIGRATION_SCHEMA_VERSION,
         &instance_data,
         &persistent_data,
+        &module_data,
         timestamp,
         ledger_sequence,
     );
 
     Ok(MigrationSnapshot {
-        version: 1,
+        version: MIGRATION_SCHEMA_VERSION,
         timestamp,
         ledger_sequence,
         instance_data,
         persistent_data,
+        module_data,
         verification_hash,
     })
 }
 
+/// Collect the storage of the feature modules for remittances up to
+/// `remittance_counter`
+fn export_module_data(env: &Env, remittance_counter: u64) -> ModuleData {
+    let mut fx_config = Vec::new(env);
+    if let Ok(config) = crate::fx::get_fx_config(env) {
+        fx_config.push_back(config);
+    }
+
+    // Collect per-remittance module state
+    let mut remittance_fx = Vec::new(env);
+    let mut travel_rule_records = Vec::new(env);
+    let mut batch_members = Vec::new(env);
+    for id in 1..=remittance_counter {
+        if let Some(quote) = crate::fx::get_remittance_fx(env, id) {
+            remittance_fx.push_back((id, quote));
+        }
+        if let Some(record) = crate::travel_rule::get_travel_rule_record(env, id) {
+            travel_rule_records.push_back(record);
+        }
+        if let Some(batch_id) = crate::batching::get_remittance_batch(env, id) {
+            batch_members.push_back((id, batch_id));
+        }
+    }
+
+    // Collect cycle configurations and open cycles by corridor
+    let mut cycle_configs = Vec::new(env);
+    let mut open_cycles = Vec::new(env);
+    for corridor in crate::settlement_cycle::list_cycle_corridors(env).iter() {
+        if let Some(cycle) = crate::settlement_cycle::get_open_cycle(env, &corridor) {
+            open_cycles.push_back(cycle);
+        }
+        if let Some(config) = crate::settlement_cycle::get_cycle_config(env, &corridor) {
+            cycle_configs.push_back((corridor, config));
+        }
+    }
+
+    // Collect closed cycles
+    let mut cycle_summaries = Vec::new(env);
+    let mut announced = Vec::new(env);
+    let cycle_counter = crate::settlement_cycle::get_cycle_counter(env);
+    for cycle_id in 1..=cycle_counter {
+        if let Some(summary) = crate::settlement_cycle::get_cycle_summary(env, cycle_id) {
+            cycle_summaries.push_back(summary);
+        }
+        let action = EventAction::CycleClose(cycle_id);
+        if crate::events::is_announced(env, &action) {
+            announced.push_back(action);
+        }
+    }
+
+    // Collect batches; single-transaction batches share the ID sequence but
+    // only leave a commitment behind
+    let mut settlement_batches = Vec::new(env);
+    let mut batch_commitments = Vec::new(env);
+    let batch_counter = crate::batching::get_batch_counter(env);
+    for batch_id in 1..=batch_counter {
+        if let Ok(batch) = crate::batching::get_settlement_batch(env, batch_id) {
+            settlement_batches.push_back(batch);
+        }
+        if let Some(commitment) = crate::merkle::get_batch_commitment(env, batch_id) {
+            batch_commitments.push_back(commitment);
+        }
+        let action = EventAction::BatchSettlement(batch_id);
+        if crate::events::is_announced(env, &action) {
+            announced.push_back(action);
+        }
+    }
+
+    ModuleData {
+        event_sequence: crate::events::get_event_sequence(env),
+        audit_head: crate::audit::get_audit_head(env),
+        fx_config,
+        fx_quote_counter: crate::fx::get_quote_counter(env),
+        remittance_fx,
+        travel_rule_thresholds: crate::travel_rule::list_travel_rule_thresholds(env),
+        travel_rule_records,
+        cycle_counter,
+        cycle_configs,
+        open_cycles,
+        cycle_summaries,
+        batch_counter,
+        settlement_batches,
+        batch_members,
+        batch_commitments,
+        delegations: crate::delegation::list_settlement_delegations(env),
+        announced,
+    }
+}
+
+/// Collect contract-level configuration
+pub(crate) fn export_instance_data(env: &Env) -> Result<InstanceData, ContractError> {
+    Ok(InstanceData {
+        admin: crate::storage::get_admin(env)?,
+        usdc_token: crate::storage::get_usdc_token(env)?,
+        platform_fee_bps: crate::storage::get_platform_fee_bps(env)?,
+        remittance_counter: crate::storage::get_remittance_counter(env)?,
+        accumulated_fees: crate::storage::get_accumulated_fees(env)?,
+        paused: crate::storage::is_paused(env),
+        admin_count: crate::storage::get_admin_count(env),
+        integrator_fee_bps: crate::storage::get_integrator_fee_bps(env)?,
+        accumulated_integrator_fees: crate::storage::get_accumulated_integrator_fees(env)?,
+        rate_limit_cooldown: crate::storage::get_rate_limit_cooldown(env)?,
+        rate_limit_config: crate::rate_limit::get_rate_limit_config(env),
+        idempotency_ttl: crate::storage::get_idempotency_ttl(env),
+    })
+}
+
 /// Import contract state from migration snapshot
 ///
 /// This function restores complete contract state from a snapshot including:
@@ -216,16 +553,25 @@ pub fn export_state(env: &Env) -> Result<MigrationSnapshot, ContractError> {
 /// # Returns
 /// Ok(()) if import successful, Err otherwise
 pub fn import_state(env: &Env, snapshot: MigrationSnapshot) -> Result<(), ContractError> {
+    // A batched import is already under way
+    validate_no_migration_session(env)?;
+
     // Verify contract is not already initialized
     if crate::storage::has_admin(env) {
         return Err(ContractError::AlreadyInitialized);
     }
 
+    if snapshot.version != MIGRATION_SCHEMA_VERSION {
+        return Err(ContractError::UnsupportedMigrationVersion);
+    }
+
     // Verify snapshot hash
     let computed_hash = compute_snapshot_hash(
         env,
+        snapshot.version,
         &snapshot.instance_data,
         &snapshot.persistent_data,
+        &snapshot.module_data,
         snapshot.timestamp,
         snapshot.ledger_sequence,
     );
@@ -242,6 +588,14 @@ pub fn import_state(env: &Env, snapshot: MigrationSnapshot) -> Result<(), Contra
     crate::storage::set_accumulated_fees(env, snapshot.instance_data.accumulated_fees);
     crate::storage::set_paused(env, snapshot.instance_data.paused);
     crate::storage::set_admin_count(env, snapshot.instance_data.admin_count);
+    crate::storage::set_integrator_fee_bps(env, snapshot.instance_data.integrator_fee_bps);
+    crate::storage::set_accumulated_integrator_fees(
+        env,
+        snapshot.instance_data.accumulated_integrator_fees,
+    );
+    crate::storage::set_rate_limit_cooldown(env, snapshot.instance_data.rate_limit_cooldown);
+    crate::rate_limit::set_rate_limit_config(env, snapshot.instance_data.rate_limit_config);
+    crate::storage::set_idempotency_ttl(env, snapshot.instance_data.idempotency_ttl);
 
     // Import persistent data
 
@@ -263,10 +617,14 @@ pub fn import_state(env: &Env, snapshot: MigrationSnapshot) -> Result<(), Contra
         crate::storage::set_admin_role(env, &admin, true);
     }
 
-    // Import settlement hashes
-    for i in 0..snapshot.persistent_data.settlement_hashes.len() {
-        let id = snapshot.persistent_data.settlement_hashes.get_unchecked(i);
-        crate::storage::set_settlement_hash(env, id);
+    // Import settlement records
+    for record in snapshot.persistent_data.settlements.iter() {
+        import_settlement_record(env, &record);
+    }
+
+    // Import memos
+    for (remittance_id, memo) in snapshot.persistent_data.memos.iter() {
+        crate::storage::set_remittance_memo(env, remittance_id, &memo);
     }
 
     // Import whitelisted tokens
@@ -275,9 +633,79 @@ pub fn import_state(env: &Env, snapshot: MigrationSnapshot) -> Result<(), Contra
         crate::storage::set_token_whitelisted(env, &token, true);
     }
 
+    // Import daily limits
+    for limit in snapshot.persistent_data.daily_limits.iter() {
+        crate::storage::set_daily_limit(env, &limit.currency, &limit.country, limit.limit);
+    }
+
+    // Import per-sender state
+    for user in snapshot.persistent_data.users.iter() {
+        if let Some(timestamp) = user.last_settlement_time {
+            crate::storage::set_last_settlement_time(env, &user.address, timestamp);
+        }
+        if !user.transfers.is_empty() {
+            crate::storage::set_user_transfers(env, &user.address, &user.transfers);
+        }
+    }
+
+    import_module_data(env, &snapshot.module_data);
+
+    // Everything imported was written in the current layout
+    crate::upgrade::set_storage_schema_version(env, crate::upgrade::STORAGE_SCHEMA_VERSION);
+
     Ok(())
 }
 
+/// Restore the storage of the feature modules
+fn import_module_data(env: &Env, data: &ModuleData) {
+    crate::events::set_event_sequence(env, data.event_sequence);
+    crate::audit::set_audit_head(env, &data.audit_head);
+
+    if let Some(config) = data.fx_config.first() {
+        crate::fx::set_fx_config(env, &config);
+    }
+    crate::fx::set_quote_counter(env, data.fx_quote_counter);
+    for (remittance_id, quote) in data.remittance_fx.iter() {
+        crate::fx::set_remittance_fx(env, remittance_id, &quote);
+    }
+
+    for (corridor, threshold) in data.travel_rule_thresholds.iter() {
+        crate::travel_rule::set_travel_rule_threshold(env, &corridor, threshold);
+    }
+    for record in data.travel_rule_records.iter() {
+        crate::travel_rule::set_travel_rule_record(env, &record);
+    }
+
+    crate::settlement_cycle::set_cycle_counter(env, data.cycle_counter);
+    for (corridor, config) in data.cycle_configs.iter() {
+        crate::settlement_cycle::set_cycle_config(env, &corridor, &config);
+    }
+    for cycle in data.open_cycles.iter() {
+        crate::settlement_cycle::set_open_cycle(env, &cycle);
+    }
+    for summary in data.cycle_summaries.iter() {
+        crate::settlement_cycle::set_cycle_summary(env, &summary);
+    }
+
+    crate::batching::set_batch_counter(env, data.batch_counter);
+    for batch in data.settlement_batches.iter() {
+        crate::batching::set_settlement_batch(env, &batch);
+    }
+    for (remittance_id, batch_id) in data.batch_members.iter() {
+        crate::batching::lock_remittance_in_batch(env, remittance_id, batch_id);
+    }
+    for commitment in data.batch_commitments.iter() {
+        crate::merkle::set_batch_commitment(env, &commitment);
+    }
+
+    for delegation in data.delegations.iter() {
+        crate::delegation::set_settlement_delegation(env, &delegation);
+    }
+    for action in data.announced.iter() {
+        crate::events::set_announced(env, action);
+    }
+}
+
 /// Compute cryptographic hash of snapshot for verification
 ///
 /// This function creates a deterministic hash of all snapshot data to ensure:
@@ -287,98 +715,46 @@ pub fn import_state(env: &Env, snapshot: MigrationSnapshot) -> Result<(), Contra
 ///
 /// # Algorithm
 /// Uses SHA-256 hash of concatenated serialized data:
-/// 1. Instance data (admin, token, fees, counters)
-/// 2. Persistent data (remittances, agents, etc.)
-/// 3. Timestamp and ledger sequence
+/// 1. Schema version (u32 big-endian)
+/// 2. Instance data (XDR encoding)
+/// 3. Remittances and settlement records (`encode_remittances`,
+///    `encode_settlement_records`)
+/// 4. Agents, admin roles, whitelisted tokens, memos, daily limits and
+///    users, each as XDR encoding, in that order
+/// 5. Module data (XDR encoding)
+/// 6. Timestamp (u64) and ledger sequence (u32), big-endian
 ///
 /// # Returns
 /// 32-byte cryptographic hash
 fn compute_snapshot_hash(
     env: &Env,
+    version: u32,
     instance_data: &InstanceData,
     persistent_data: &PersistentData,
+    module_data: &ModuleData,
     timestamp: u64,
     ledger_sequence: u32,
 ) -> BytesN<32> {
     let mut data = Bytes::new(env);
 
-    // Serialize instance data
-    data.append(&instance_data.admin.to_string().to_bytes());
-    data.append(&instance_data.usdc_token.to_string().to_bytes());
-    data.append(&Bytes::from_array(
-        env,
-        &instance_data.platform_fee_bps.to_be_bytes(),
-    ));
-    data.append(&Bytes::from_array(
-        env,
-        &instance_data.remittance_counter.to_be_bytes(),
-    ));
-    data.append(&Bytes::from_array(
-        env,
-        &instance_data.accumulated_fees.to_be_bytes(),
-    ));
-    data.append(&Bytes::from_array(
-        env,
-        &[if instance_data.paused { 1u8 } else { 0u8 }],
-    ));
-    data.append(&Bytes::from_array(
-        env,
-        &instance_data.admin_count.to_be_bytes(),
-    ));
-
-    // Serialize persistent data
-
-    // Remittances
-    for i in 0..persistent_data.remittances.len() {
-        let r = persistent_data.remittances.get_unchecked(i);
-        data.append(&Bytes::from_array(env, &r.id.to_be_bytes()));
-        data.append(&r.sender.to_string().to_bytes());
-        data.append(&r.agent.to_string().to_bytes());
-        data.append(&Bytes::from_array(env, &r.amount.to_be_bytes()));
-        data.append(&Bytes::from_array(env, &r.fee.to_be_bytes()));
-
-        let status_byte = match r.status {
-            RemittanceStatus::Pending => 0u8,
-            RemittanceStatus::Completed => 1u8,
-            RemittanceStatus::Cancelled => 2u8,
-        };
-        data.append(&Bytes::from_array(env, &[status_byte]));
-
-        if let Some(expiry) = r.expiry {
-            data.append(&Bytes::from_array(env, &expiry.to_be_bytes()));
-        }
-    }
-
-    // Agents
-    for i in 0..persistent_data.agents.len() {
-        let agent = persistent_data.agents.get_unchecked(i);
-        data.append(&agent.to_string().to_bytes());
-    }
-
-    // Admin roles
-    for i in 0..persistent_data.admin_roles.len() {
-        let admin = persistent_data.admin_roles.get_unchecked(i);
-        data.append(&admin.to_string().to_bytes());
-    }
-
-    // Settlement hashes
-    for i in 0..persistent_data.settlement_hashes.len() {
-        let id = persistent_data.settlement_hashes.get_unchecked(i);
-        data.append(&Bytes::from_array(env, &id.to_be_bytes()));
-    }
-
-    // Whitelisted tokens
-    for i in 0..persistent_data.whitelisted_tokens.len() {
-        let token = persistent_data.whitelisted_tokens.get_unchecked(i);
-        data.append(&token.to_string().to_bytes());
-    }
+    data.append(&Bytes::from_array(env, &version.to_be_bytes()));
+    data.append(&instance_data.clone().to_xdr(env));
+    data.append(&encode_remittances(env, &persistent_data.remittances));
+    data.append(&encode_settlement_records(env, &persistent_data.settlements));
+    data.append(&persistent_data.agents.clone().to_xdr(env));
+    data.append(&persistent_data.admin_roles.clone().to_xdr(env));
+    data.append(&persistent_data.whitelisted_tokens.clone().to_xdr(env));
+    data.append(&persistent_data.memos.clone().to_xdr(env));
+    data.append(&persistent_data.daily_limits.clone().to_xdr(env));
+    data.append(&persistent_data.users.clone().to_xdr(env));
+    data.append(&module_data.clone().to_xdr(env));
 
     // Add timestamp and ledger sequence
     data.append(&Bytes::from_array(env, &timestamp.to_be_bytes()));
     data.append(&Bytes::from_array(env, &ledger_sequence.to_be_bytes()));
 
     // Compute SHA-256 hash
-    env.crypto().sha256(&data)
+    env.crypto().sha256(&data).into()
 }
 
 /// Verify migration snapshot integrity
@@ -394,8 +770,10 @@ fn compute_snapshot_hash(
 pub fn verify_snapshot(env: &Env, snapshot: &MigrationSnapshot) -> MigrationVerification {
     let computed_hash = compute_snapshot_hash(
         env,
+        snapshot.version,
         &snapshot.instance_data,
         &snapshot.persistent_data,
+        &snapshot.module_data,
         snapshot.timestamp,
         snapshot.ledger_sequence,
     );
@@ -421,6 +799,11 @@ pub fn verify_snapshot(env: &Env, snapshot: &MigrationSnapshot) -> MigrationVeri
 ///
 /// # Returns
 /// MigrationBatch containing subset of data
+///
+/// # Errors
+/// - InvalidAmount: `batch_size` is 0 or above `MAX_MIGRATION_BATCH_SIZE`,
+///   or `batch_number` is past the last batch
+/// - Overflow: The remittance counter needs more than `u32::MAX` batches
 pub fn export_batch(
     env: &Env,
     batch_number: u32,
@@ -431,89 +814,356 @@ pub fn export_batch(
     }
 
     let counter = crate::storage::get_remittance_counter(env)?;
-    let total_batches = (counter as u32 + batch_size - 1) / batch_size;
+    let total_batches = u32::try_from(counter.div_ceil(batch_size as u64))
+        .map_err(|_| ContractError::Overflow)?;
 
     if batch_number >= total_batches {
         return Err(ContractError::InvalidAmount);
     }
 
-    let start_id = (batch_number * batch_size) as u64 + 1;
-    let end_id = ((batch_number + 1) * batch_size).min(counter as u32) as u64;
+    let start_id = (batch_number as u64)
+        .checked_mul(batch_size as u64)
+        .and_then(|offset| offset.checked_add(1))
+        .ok_or(ContractError::Overflow)?;
+    let end_id = (batch_number as u64)
+        .checked_add(1)
+        .and_then(|next| next.checked_mul(batch_size as u64))
+        .ok_or(ContractError::Overflow)?
+        .min(counter);
 
     let mut remittances = Vec::new(env);
+    let mut settlements = Vec::new(env);
+    let mut memos = Vec::new(env);
+    let mut travel_rule_records = Vec::new(env);
+    let mut remittance_fx = Vec::new(env);
     for id in start_id..=end_id {
         if let Ok(remittance) = crate::storage::get_remittance(env, id) {
             remittances.push_back(remittance);
         }
+        if let Some(record) = export_settlement_record(env, id) {
+            settlements.push_back(record);
+        }
+        if let Some(memo) = crate::storage::get_remittance_memo(env, id) {
+            memos.push_back((id, memo));
+        }
+        if let Some(record) = crate::travel_rule::get_travel_rule_record(env, id) {
+            travel_rule_records.push_back(record);
+        }
+        if let Some(quote) = crate::fx::get_remittance_fx(env, id) {
+            remittance_fx.push_back((id, quote));
+        }
     }
 
     // Compute batch hash
-    let batch_hash = compute_batch_hash(env, &remittances, batch_number);
+    let batch_hash = compute_batch_hash(
+        env,
+        batch_number,
+        total_batches,
+        &remittances,
+        &settlements,
+        &memos,
+        &travel_rule_records,
+        &remittance_fx,
+    );
 
     Ok(MigrationBatch {
         batch_number,
         total_batches,
         remittances,
+        settlements,
+        memos,
+        travel_rule_records,
+        remittance_fx,
         batch_hash,
     })
 }
 
+/// Computes the root a migration session is opened with: the Merkle root
+/// over the hashes of all batches, in batch order.
+pub fn compute_migration_root(env: &Env, batch_hashes: &Vec<BytesN<32>>) -> Option<BytesN<32>> {
+    crate::compute_merkle_root(env, batch_hashes)
+}
+
+/// Open a migration session
+///
+/// Declares how many batches will be imported and the root over their
+/// hashes. Business operations are refused until the session is finalized.
+///
+/// # Parameters
+/// - `expected_batches`: Number of batches the export produced
+/// - `batches_root`: Root from `compute_migration_root` over the batch hashes
+///
+/// # Returns
+/// The new session
+pub fn begin_session(
+    env: &Env,
+    expected_batches: u32,
+    batches_root: BytesN<32>,
+) -> Result<MigrationSession, ContractError> {
+    if get_migration_session(env).is_some() {
+        return Err(ContractError::MigrationInProgress);
+    }
+    if expected_batches == 0 {
+        return Err(ContractError::InvalidMigrationBatch);
+    }
+
+    let session = MigrationSession {
+        expected_batches,
+        batches_root,
+        next_batch: 0,
+        imported: MerkleFrontier::new(env),
+        max_remittance_id: 0,
+        started_at: env.ledger().timestamp(),
+    };
+    set_migration_session(env, &session);
+
+    Ok(session)
+}
+
 /// Import state from batch
 ///
-/// Import a single batch of remittances. Must be called in order
-/// (batch 0, then 1, then 2, etc.) to ensure consistency.
+/// Import a single batch of remittances and their per-remittance state into
+/// the open session. Batches must be imported in order (batch 0, then 1,
+/// then 2, etc.); an out-of-order or repeated batch is rejected. Each batch
+/// is checked against the session's root when it is imported, so a batch
+/// outside the declared export is refused before any of it is written.
 ///
 /// # Parameters
 /// - `batch`: Batch to import
+/// - `proof`: Inclusion proof of the batch hash under the session's root,
+///   from `compute_merkle_proof` over the batch hashes
 ///
 /// # Returns
-/// Ok(()) if import successful
-pub fn import_batch(env: &Env, batch: MigrationBatch) -> Result<(), ContractError> {
+/// The session with its progress updated
+pub fn import_batch(
+    env: &Env,
+    batch: MigrationBatch,
+    proof: &Vec<MerkleProofStep>,
+) -> Result<MigrationSession, ContractError> {
+    let mut session =
+        get_migration_session(env).ok_or(ContractError::InvalidMigrationState)?;
+
+    if batch.total_batches != session.expected_batches || batch.batch_number != session.next_batch {
+        return Err(ContractError::InvalidMigrationBatch);
+    }
+
     // Verify batch hash
-    let computed_hash = compute_batch_hash(env, &batch.remittances, batch.batch_number);
+    let computed_hash = compute_batch_hash(
+        env,
+        batch.batch_number,
+        batch.total_batches,
+        &batch.remittances,
+        &batch.settlements,
+        &batch.memos,
+        &batch.travel_rule_records,
+        &batch.remittance_fx,
+    );
 
     if computed_hash != batch.batch_hash {
         return Err(ContractError::InvalidMigrationHash);
     }
+    if !crate::verify_merkle_proof(env, &session.batches_root, &batch.batch_hash, proof) {
+        return Err(ContractError::InvalidMigrationHash);
+    }
 
     // Import remittances
-    for i in 0..batch.remittances.len() {
-        let remittance = batch.remittances.get_unchecked(i);
+    for remittance in batch.remittances.iter() {
+        session.max_remittance_id = session.max_remittance_id.max(remittance.id);
         crate::storage::set_remittance(env, remittance.id, &remittance);
     }
 
+    // Import settlement records
+    for record in batch.settlements.iter() {
+        import_settlement_record(env, &record);
+    }
+
+    for (remittance_id, memo) in batch.memos.iter() {
+        crate::storage::set_remittance_memo(env, remittance_id, &memo);
+    }
+    for record in batch.travel_rule_records.iter() {
+        crate::travel_rule::set_travel_rule_record(env, &record);
+    }
+    for (remittance_id, quote) in batch.remittance_fx.iter() {
+        crate::fx::set_remittance_fx(env, remittance_id, &quote);
+    }
+
+    session.imported.push(env, &batch.batch_hash);
+    session.next_batch += 1;
+    set_migration_session(env, &session);
+
+    Ok(session)
+}
+
+/// Finalize the open migration session
+///
+/// Checks every declared batch was imported and that the imported batches
+/// match the declared root, restores the remittance counter and closes the
+/// session, which unlocks business operations.
+pub fn finalize_session(env: &Env) -> Result<(), ContractError> {
+    let session =
+        get_migration_session(env).ok_or(ContractError::InvalidMigrationState)?;
+
+    if session.next_batch != session.expected_batches {
+        return Err(ContractError::InvalidMigrationState);
+    }
+    if session.imported.root(env) != Some(session.batches_root.clone()) {
+        return Err(ContractError::InvalidMigrationHash);
+    }
+
+    close_session(env, &session);
+
     Ok(())
 }
 
-/// Compute hash of a batch for verification
-fn compute_batch_hash(env: &Env, remittances: &Vec<Remittance>, batch_number: u32) -> BytesN<32> {
-    let mut data = Bytes::new(env);
+/// Abort the open migration session
+///
+/// Closes the session without checking its progress, which unlocks business
+/// operations, for example to start over after declaring the wrong root.
+/// Batches already imported are not rolled back: their remittances stay in
+/// storage and the remittance counter is advanced past them, so new
+/// remittances cannot reuse their IDs. A new session re-imports from batch 0
+/// and overwrites them.
+///
+/// # Returns
+/// The aborted session
+pub fn abort_session(env: &Env) -> Result<MigrationSession, ContractError> {
+    let session =
+        get_migration_session(env).ok_or(ContractError::InvalidMigrationState)?;
 
-    // Add batch number
-    data.append(&Bytes::from_array(env, &batch_number.to_be_bytes()));
+    close_session(env, &session);
 
-    // Add all remittances
-    for i in 0..remittances.len() {
-        let r = remittances.get_unchecked(i);
-        data.append(&Bytes::from_array(env, &r.id.to_be_bytes()));
-        data.append(&r.sender.to_string().to_bytes());
-        data.append(&r.agent.to_string().to_bytes());
-        data.append(&Bytes::from_array(env, &r.amount.to_be_bytes()));
-        data.append(&Bytes::from_array(env, &r.fee.to_be_bytes()));
-
-        let status_byte = match r.status {
-            RemittanceStatus::Pending => 0u8,
-            RemittanceStatus::Completed => 1u8,
-            RemittanceStatus::Cancelled => 2u8,
-        };
-        data.append(&Bytes::from_array(env, &[status_byte]));
+    Ok(session)
+}
+
+/// Restore the remittance counter past every imported remittance and remove
+/// the session
+fn close_session(env: &Env, session: &MigrationSession) {
+    let counter = crate::storage::get_remittance_counter(env).unwrap_or(0);
+    crate::storage::set_remittance_counter(env, counter.max(session.max_remittance_id));
+
+    env.storage().instance().remove(&MigrationKey::Session);
+}
+
+/// Get the open migration session, if any
+pub fn get_migration_session(env: &Env) -> Option<MigrationSession> {
+    env.storage().instance().get(&MigrationKey::Session)
+}
+
+fn set_migration_session(env: &Env, session: &MigrationSession) {
+    env.storage().instance().set(&MigrationKey::Session, session);
+}
+
+/// Fails while a migration session is open.
+pub fn validate_no_migration_session(env: &Env) -> Result<(), ContractError> {
+    if get_migration_session(env).is_some() {
+        return Err(ContractError::MigrationInProgress);
+    }
+    Ok(())
+}
+
+/// Collect the settlement record of a remittance, if it has settled
+pub(crate) fn export_settlement_record(env: &Env, remittance_id: u64) -> Option<SettlementRecord> {
+    let settlement_id = crate::storage::get_settlement_id(env, remittance_id)?;
+    Some(SettlementRecord {
+        remittance_id,
+        settlement_id,
+        schema_version: crate::storage::get_settlement_schema_version(env, remittance_id)
+            .unwrap_or(1),
+        receipt: crate::storage::get_settlement_receipt(env, remittance_id)
+            .map(|receipt| Vec::from_array(env, [receipt]))
+            .unwrap_or_else(|| Vec::new(env)),
+        event_emitted: crate::storage::has_settlement_event_emitted(env, remittance_id),
+    })
+}
+
+/// Restore a settlement record
+fn import_settlement_record(env: &Env, record: &SettlementRecord) {
+    crate::storage::set_settlement_hash(
+        env,
+        record.remittance_id,
+        &record.settlement_id,
+        record.schema_version,
+    );
+    if let Some(receipt) = record.receipt.first() {
+        crate::storage::set_settlement_receipt(env, &receipt);
+    }
+    if record.event_emitted {
+        crate::storage::set_settlement_event_emitted(env, record.remittance_id);
+    }
+}
 
-        if let Some(expiry) = r.expiry {
-            data.append(&Bytes::from_array(env, &expiry.to_be_bytes()));
+/// Encodes remittances for hashing: the count (u32 big-endian) followed by
+/// the canonical encoding of each remittance (`encode_remittance`).
+pub fn encode_remittances(env: &Env, remittances: &Vec<Remittance>) -> Bytes {
+    let mut data = Bytes::new(env);
+    data.extend_from_array(&remittances.len().to_be_bytes());
+    for remittance in remittances.iter() {
+        data.append(&encode_remittance(env, &remittance));
+    }
+    data
+}
+
+/// Encodes settlement records for hashing: the count (u32 big-endian)
+/// followed by `encode_settlement_record` of each record.
+pub fn encode_settlement_records(env: &Env, settlements: &Vec<SettlementRecord>) -> Bytes {
+    let mut data = Bytes::new(env);
+    data.extend_from_array(&settlements.len().to_be_bytes());
+    for record in settlements.iter() {
+        data.append(&encode_settlement_record(env, &record));
+    }
+    data
+}
+
+/// Encodes one settlement record for hashing:
+///
+/// 1. `remittance_id`  — u64, big-endian 8 bytes
+/// 2. `settlement_id`  — 32 bytes
+/// 3. `schema_version` — u32, big-endian 4 bytes
+/// 4. `receipt`        — 0x00 if empty, else 0x01 followed by `encode_receipt`
+/// 5. `event_emitted`  — 0x00 or 0x01
+pub fn encode_settlement_record(env: &Env, record: &SettlementRecord) -> Bytes {
+    let mut data = Bytes::new(env);
+    data.extend_from_array(&record.remittance_id.to_be_bytes());
+    data.extend_from_array(&record.settlement_id.to_array());
+    data.extend_from_array(&record.schema_version.to_be_bytes());
+    match record.receipt.first() {
+        Some(receipt) => {
+            data.push_back(1);
+            data.append(&encode_receipt(env, &receipt));
         }
+        None => data.push_back(0),
     }
+    data.push_back(record.event_emitted as u8);
+    data
+}
+
+/// Compute hash of a batch for verification
+///
+/// SHA-256 over the schema version, batch number and total (u32 big-endian)
+/// followed by `encode_remittances`, `encode_settlement_records` and the XDR
+/// encoding of the memos, travel-rule records and FX quotes, in that order.
+fn compute_batch_hash(
+    env: &Env,
+    batch_number: u32,
+    total_batches: u32,
+    remittances: &Vec<Remittance>,
+    settlements: &Vec<SettlementRecord>,
+    memos: &Vec<(u64, BytesN<32>)>,
+    travel_rule_records: &Vec<TravelRuleRecord>,
+    remittance_fx: &Vec<(u64, FxQuote)>,
+) -> BytesN<32> {
+    let mut data = Bytes::new(env);
 
-    env.crypto().sha256(&data)
+    data.append(&Bytes::from_array(env, &MIGRATION_SCHEMA_VERSION.to_be_bytes()));
+    data.append(&Bytes::from_array(env, &batch_number.to_be_bytes()));
+    data.append(&Bytes::from_array(env, &total_batches.to_be_bytes()));
+    data.append(&encode_remittances(env, remittances));
+    data.append(&encode_settlement_records(env, settlements));
+    data.append(&memos.clone().to_xdr(env));
+    data.append(&travel_rule_records.clone().to_xdr(env));
+    data.append(&remittance_fx.clone().to_xdr(env));
+
+    env.crypto().sha256(&data).into()
 }
 
 #[cfg(test)]
@@ -521,30 +1171,91 @@ mod tests {
     use super::*;
     use soroban_sdk::{testutils::Address as _, Env};
 
-    #[test]
-    fn test_snapshot_hash_deterministic() {
-        let env = Env::default();
-
-        let instance_data = InstanceData {
-            admin: Address::generate(&env),
-            usdc_token: Address::generate(&env),
+    fn instance_data(env: &Env) -> InstanceData {
+        InstanceData {
+            admin: Address::generate(env),
+            usdc_token: Address::generate(env),
             platform_fee_bps: 250,
             remittance_counter: 10,
             accumulated_fees: 1000,
             paused: false,
             admin_count: 1,
-        };
+            integrator_fee_bps: 0,
+            accumulated_integrator_fees: 0,
+            rate_limit_cooldown: 3600,
+            rate_limit_config: RateLimitConfig {
+                max_requests: 100,
+                window_seconds: 60,
+                enabled: true,
+            },
+            idempotency_ttl: 86_400,
+        }
+    }
 
-        let persistent_data = PersistentData {
-            remittances: Vec::new(&env),
-            agents: Vec::new(&env),
-            admin_roles: Vec::new(&env),
-            settlement_hashes: Vec::new(&env),
-            whitelisted_tokens: Vec::new(&env),
-        };
+    fn persistent_data(env: &Env) -> PersistentData {
+        PersistentData {
+            remittances: Vec::new(env),
+            agents: Vec::new(env),
+            admin_roles: Vec::new(env),
+            settlements: Vec::new(env),
+            whitelisted_tokens: Vec::new(env),
+            memos: Vec::new(env),
+            daily_limits: Vec::new(env),
+            users: Vec::new(env),
+        }
+    }
+
+    fn module_data(env: &Env) -> ModuleData {
+        ModuleData {
+            event_sequence: 12,
+            audit_head: AuditHead {
+                sequence: 0,
+                hash: BytesN::from_array(env, &[0u8; 32]),
+            },
+            fx_config: Vec::new(env),
+            fx_quote_counter: 0,
+            remittance_fx: Vec::new(env),
+            travel_rule_thresholds: Vec::new(env),
+            travel_rule_records: Vec::new(env),
+            cycle_counter: 0,
+            cycle_configs: Vec::new(env),
+            open_cycles: Vec::new(env),
+            cycle_summaries: Vec::new(env),
+            batch_counter: 0,
+            settlement_batches: Vec::new(env),
+            batch_members: Vec::new(env),
+            batch_commitments: Vec::new(env),
+            delegations: Vec::new(env),
+            announced: Vec::new(env),
+        }
+    }
 
-        let hash1 = compute_snapshot_hash(&env, &instance_data, &persistent_data, 1000, 100);
-        let hash2 = compute_snapshot_hash(&env, &instance_data, &persistent_data, 1000, 100);
+    #[test]
+    fn test_snapshot_hash_deterministic() {
+        let env = Env::default();
+
+        let instance_data = instance_data(&env);
+        let persistent_data = persistent_data(&env);
+        let module_data = module_data(&env);
+
+        let hash1 = compute_snapshot_hash(
+            &env,
+            4,
+            &instance_data,
+            &persistent_data,
+            &module_data,
+            1000,
+            100,
+        );
+        let hash2 = compute_snapshot_hash(
+            &env,
+            4,
+            &instance_data,
+            &persistent_data,
+            &module_data,
+            1000,
+            100,
+        );
 
         assert_eq!(hash1, hash2);
     }
@@ -553,37 +1264,190 @@ mod tests {
     fn test_snapshot_hash_changes_with_data() {
         let env = Env::default();
 
-        let instance_data1 = InstanceData {
-            admin: Address::generate(&env),
-            usdc_token: Address::generate(&env),
-            platform_fee_bps: 250,
-            remittance_counter: 10,
-            accumulated_fees: 1000,
-            paused: false,
-            admin_count: 1,
-        };
-
+        let instance_data1 = instance_data(&env);
         let instance_data2 = InstanceData {
-            admin: instance_data1.admin.clone(),
-            usdc_token: instance_data1.usdc_token.clone(),
             platform_fee_bps: 300, // Different fee
-            remittance_counter: 10,
-            accumulated_fees: 1000,
-            paused: false,
-            admin_count: 1,
+            ..instance_data1.clone()
         };
+        let instance_data3 = InstanceData {
+            rate_limit_cooldown: 0, // Different cooldown
+            ..instance_data1.clone()
+        };
+
+        let persistent_data = persistent_data(&env);
+        let module_data = module_data(&env);
+
+        let hash1 = compute_snapshot_hash(
+            &env,
+            4,
+            &instance_data1,
+            &persistent_data,
+            &module_data,
+            1000,
+            100,
+        );
+        let hash2 = compute_snapshot_hash(
+            &env,
+            4,
+            &instance_data2,
+            &persistent_data,
+            &module_data,
+            1000,
+            100,
+        );
+        let hash3 = compute_snapshot_hash(
+            &env,
+            4,
+            &instance_data3,
+            &persistent_data,
+            &module_data,
+            1000,
+            100,
+        );
+
+        assert_ne!(hash1, hash2);
+        assert_ne!(hash1, hash3);
+    }
+
+    #[test]
+    fn test_snapshot_hash_covers_version() {
+        let env = Env::default();
+
+        let instance_data = instance_data(&env);
+        let persistent_data = persistent_data(&env);
+        let module_data = module_data(&env);
+
+        let hash1 = compute_snapshot_hash(
+            &env,
+            3,
+            &instance_data,
+            &persistent_data,
+            &module_data,
+            1000,
+            100,
+        );
+        let hash2 = compute_snapshot_hash(
+            &env,
+            4,
+            &instance_data,
+            &persistent_data,
+            &module_data,
+            1000,
+            100,
+        );
+
+        assert_ne!(hash1, hash2);
+    }
+
+    #[test]
+    fn test_snapshot_hash_covers_module_data() {
+        let env = Env::default();
 
-        let persistent_data = PersistentData {
-            remittances: Vec::new(&env),
-            agents: Vec::new(&env),
-            admin_roles: Vec::new(&env),
-            settlement_hashes: Vec::new(&env),
-            whitelisted_tokens: Vec::new(&env),
+        let instance_data = instance_data(&env);
+        let persistent_data = persistent_data(&env);
+        let module_data1 = module_data(&env);
+        let module_data2 = ModuleData {
+            event_sequence: 13, // Different event sequence
+            ..module_data1.clone()
         };
 
-        let hash1 = compute_snapshot_hash(&env, &instance_data1, &persistent_data, 1000, 100);
-        let hash2 = compute_snapshot_hash(&env, &instance_data2, &persistent_data, 1000, 100);
+        let hash1 = compute_snapshot_hash(
+            &env,
+            4,
+            &instance_data,
+            &persistent_data,
+            &module_data1,
+            1000,
+            100,
+        );
+        let hash2 = compute_snapshot_hash(
+            &env,
+            4,
+            &instance_data,
+            &persistent_data,
+            &module_data2,
+            1000,
+            100,
+        );
 
         assert_ne!(hash1, hash2);
     }
+
+    fn remittance(env: &Env, status: crate::RemittanceStatus) -> Remittance {
+        Remittance {
+            id: 1,
+            sender: Address::generate(env),
+            agent: Address::generate(env),
+            amount: 1000,
+            fee: 25,
+            integrator_fee: 0,
+            status,
+            expiry: Some(5000),
+            corridor: crate::Corridor {
+                currency: soroban_sdk::String::from_str(env, "MXN"),
+                country: soroban_sdk::String::from_str(env, "MX"),
+            },
+        }
+    }
+
+    #[test]
+    fn test_remittance_encoding_extends_settlement_id_fields() {
+        let env = Env::default();
+        let remittance = remittance(&env, crate::RemittanceStatus::Pending);
+
+        let encoded = encode_remittance(&env, &remittance);
+        let tail = remittance.status.clone().to_xdr(&env).len()
+            + remittance.corridor.clone().to_xdr(&env).len()
+            + 16; // integrator_fee
+        let id_fields = encoded.slice(0..encoded.len() - tail);
+
+        assert_eq!(
+            BytesN::from(env.crypto().sha256(&id_fields)),
+            crate::compute_settlement_id_from_remittance(&env, &remittance)
+        );
+    }
+
+    #[test]
+    fn test_batch_hash_covers_remittance_status() {
+        let env = Env::default();
+        let pending = remittance(&env, crate::RemittanceStatus::Pending);
+        let completed = Remittance {
+            status: crate::RemittanceStatus::Completed,
+            ..pending.clone()
+        };
+        let hash = |remittance: Remittance| {
+            compute_batch_hash(
+                &env,
+                0,
+                1,
+                &Vec::from_array(&env, [remittance]),
+                &Vec::new(&env),
+                &Vec::new(&env),
+                &Vec::new(&env),
+                &Vec::new(&env),
+            )
+        };
+
+        assert_ne!(hash(pending), hash(completed));
+    }
+
+    #[test]
+    fn test_batch_hash_covers_memos() {
+        let env = Env::default();
+        let remittances = Vec::from_array(&env, [remittance(&env, crate::RemittanceStatus::Pending)]);
+        let hash = |memo: [u8; 32]| {
+            compute_batch_hash(
+                &env,
+                0,
+                1,
+                &remittances,
+                &Vec::new(&env),
+                &Vec::from_array(&env, [(1, BytesN::from_array(&env, &memo))]),
+                &Vec::new(&env),
+                &Vec::new(&env),
+            )
+        };
+
+        assert_ne!(hash([1; 32]), hash([2; 32]));
+    }
 }
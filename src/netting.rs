@@ -1,16 +1,19 @@
-use soroban_sdk::{contracttype, Address, Env, Map, Vec};
+use soroban_sdk::{contracttype, Address, Bytes, Env, Map, Vec};
 
-use crate::{ContractError, Remittance, RemittanceStatus};
+use crate::{address_to_bytes, ContractError, Remittance, RemittanceStatus};
+
+/// Maximum number of remittances settled in a single batch.
+pub const MAX_BATCH_SIZE: u32 = 50;
 
 /// Represents a net transfer between two parties after offsetting opposing flows.
 /// This structure ensures deterministic ordering by always placing the party
-/// with the lexicographically smaller address as party_a.
+/// whose canonical XDR encoding sorts first as party_a.
 #[contracttype]
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct NetTransfer {
-    /// The party with the lexicographically smaller address (deterministic ordering)
+    /// The party whose XDR encoding sorts first (deterministic ordering)
     pub party_a: Address,
-    /// The party with the lexicographically larger address (deterministic ordering)
+    /// The party whose XDR encoding sorts last (deterministic ordering)
     pub party_b: Address,
     /// Net amount to transfer. Positive means A -> B, negative means B -> A
     pub net_amount: i128,
@@ -18,14 +21,89 @@ pub struct NetTransfer {
     pub total_fees: i128,
 }
 
-/// Represents a directional flow between two parties before netting.
+/// Netting strategy used when settling a batch.
 #[contracttype]
-#[derive(Clone, Debug)]
-struct DirectionalFlow {
-    from: Address,
-    to: Address,
-    amount: i128,
-    fee: i128,
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NettingMode {
+    /// Offset opposing flows between each pair of parties independently
+    Bilateral,
+    /// Offset every party's flows across the whole batch and settle net positions
+    Multilateral,
+}
+
+/// Canonical ordering of the parties of a batch.
+///
+/// Every party is encoded to XDR exactly once and ranked by the encoded
+/// bytes, so the hot paths of netting compare small integers instead of
+/// re-serializing addresses on every comparison.
+struct PartyOrder {
+    /// Parties sorted by their canonical XDR encoding
+    parties: Vec<Address>,
+    /// Position of each party in `parties`
+    ranks: Map<Address, u32>,
+}
+
+impl PartyOrder {
+    /// Ranks the senders and agents of all pending remittances.
+    fn from_remittances(env: &Env, remittances: &Vec<Remittance>) -> Self {
+        // Host maps keep their keys sorted, so inserting by encoding sorts the
+        // parties. Repeated parties overwrite their own entry.
+        let mut encoded: Map<Bytes, Address> = Map::new(env);
+
+        let mut add = |party: &Address| {
+            encoded.set(address_to_bytes(env, party), party.clone());
+        };
+
+        for remittance in remittances.iter() {
+            if remittance.status != RemittanceStatus::Pending {
+                continue;
+            }
+            add(&remittance.sender);
+            add(&remittance.agent);
+        }
+
+        Self::from_encoded(env, encoded)
+    }
+
+    /// Ranks the given parties.
+    fn from_parties(env: &Env, parties: &Vec<Address>) -> Self {
+        let mut encoded: Map<Bytes, Address> = Map::new(env);
+        for party in parties.iter() {
+            encoded.set(address_to_bytes(env, &party), party);
+        }
+        Self::from_encoded(env, encoded)
+    }
+
+    fn from_encoded(env: &Env, encoded: Map<Bytes, Address>) -> Self {
+        let parties = encoded.values();
+        let mut ranks = Map::new(env);
+        for (rank, party) in parties.iter().enumerate() {
+            ranks.set(party, rank as u32);
+        }
+
+        PartyOrder { parties, ranks }
+    }
+
+    fn rank(&self, party: &Address) -> u32 {
+        self.ranks.get_unchecked(party.clone())
+    }
+
+    fn party(&self, rank: u32) -> Address {
+        self.parties.get_unchecked(rank)
+    }
+
+    /// Normalizes a directed pair to ranks in canonical order.
+    /// Returns (smaller_rank, larger_rank, direction_multiplier)
+    /// where direction_multiplier is 1 if from sorts first, else -1.
+    fn normalize_pair(&self, from: &Address, to: &Address) -> (u32, u32, i128) {
+        let from_rank = self.rank(from);
+        let to_rank = self.rank(to);
+        if from_rank < to_rank {
+            (from_rank, to_rank, 1)
+        } else {
+            (to_rank, from_rank, -1)
+        }
+    }
 }
 
 /// Computes net settlements by offsetting opposing transfers between the same parties.
@@ -53,11 +131,18 @@ struct DirectionalFlow {
 /// - `remittances`: Vector of remittances to net
 ///
 /// # Returns
-/// Vector of NetTransfer structs representing the minimal set of transfers needed
-pub fn compute_net_settlements(env: &Env, remittances: &Vec<Remittance>) -> Vec<NetTransfer> {
-    let mut flows: Vec<DirectionalFlow> = Vec::new(env);
-    
-    // Extract all directional flows from remittances
+/// Vector of NetTransfer structs representing the minimal set of transfers needed,
+/// or Overflow if a net amount or fee total cannot be represented
+pub fn compute_net_settlements(
+    env: &Env,
+    remittances: &Vec<Remittance>,
+) -> Result<Vec<NetTransfer>, ContractError> {
+    let order = PartyOrder::from_remittances(env, remittances);
+
+    // Group flows by party pairs and compute net balances.
+    // Keys are canonical ranks, so iteration follows address order.
+    let mut net_map: Map<(u32, u32), (i128, i128)> = Map::new(env);
+
     for i in 0..remittances.len() {
         let remittance = remittances.get_unchecked(i);
 
@@ -66,164 +151,325 @@ pub fn compute_net_settlements(env: &Env, remittances: &Vec<Remittance>) -> Vec<
             continue;
         }
 
-        flows.push_back(DirectionalFlow {
-            from: remittance.sender.clone(),
-            to: remittance.agent.clone(),
-            amount: remittance.amount,
-            fee: remittance.fee,
-        });
-    }
-
-    // Group flows by party pairs and compute net balances
-    let mut net_map: Map<(Address, Address), (i128, i128)> = Map::new();
+        let (party_a, party_b, direction) =
+            order.normalize_pair(&remittance.sender, &remittance.agent);
 
-    for i in 0..flows.len() {
-        let flow = flows.get_unchecked(i);
-        let (party_a, party_b, direction) = normalize_pair(&flow.from, &flow.to);
-
-        let key = (party_a.clone(), party_b.clone());
-        let (current_net, current_fees) = net_map.get(key.clone()).unwrap_or((0, 0));
+        let key = (party_a, party_b);
+        let (current_net, current_fees) = net_map.get(key).unwrap_or((0, 0));
 
         // Apply the flow in the normalized direction
         // direction = 1 means flow is A -> B (add to net)
         // direction = -1 means flow is B -> A (subtract from net)
-        let new_net = current_net + (flow.amount * direction);
-        let new_fees = current_fees + flow.fee;
+        let new_net = remittance
+            .amount
+            .checked_mul(direction)
+            .and_then(|flow| current_net.checked_add(flow))
+            .ok_or(ContractError::Overflow)?;
+        let new_fees = current_fees
+            .checked_add(retained_fees(&remittance)?)
+            .ok_or(ContractError::Overflow)?;
 
         net_map.set(key, (new_net, new_fees));
     }
 
     // Convert map to vector of NetTransfer structs
     let mut result: Vec<NetTransfer> = Vec::new(env);
-    let keys = net_map.keys();
-
-    for i in 0..keys.len() {
-        let key = keys.get_unchecked(i);
-        let (net_amount, total_fees) = net_map.get(key.clone()).unwrap();
 
-        // Only include non-zero net transfers
-        if net_amount != 0 {
+    for ((party_a, party_b), (net_amount, total_fees)) in net_map.iter() {
+        // A complete offset still carries the pair's fees to the contract
+        if net_amount != 0 || total_fees != 0 {
             result.push_back(NetTransfer {
-                party_a: key.0.clone(),
-                party_b: key.1.clone(),
+                party_a: order.party(party_a),
+                party_b: order.party(party_b),
                 net_amount,
                 total_fees,
             });
         }
     }
 
-    result
+    Ok(result)
 }
 
-/// Normalizes a pair of addresses to ensure deterministic ordering.
-/// Returns (smaller_address, larger_address, direction_multiplier)
-/// where direction_multiplier is 1 if from < to, else -1.
-fn normalize_pair(from: &Address, to: &Address) -> (Address, Address, i128) {
-    // Compare addresses lexicographically
-    if compare_addresses(from, to) < 0 {
-        // from < to, so from is party_a, to is party_b
-        // Flow is A -> B, direction = +1
-        (from.clone(), to.clone(), 1)
-    } else {
-        // to < from, so to is party_a, from is party_b
-        // Flow is B -> A, direction = -1
-        (to.clone(), from.clone(), -1)
+/// Computes net settlements by offsetting every party's flows across the whole batch.
+///
+/// Where bilateral netting only offsets A->B against B->A, multilateral netting
+/// first reduces each participant to a single net position and then settles the
+/// positions with as few transfers as the greedy matching allows. A cycle such as
+/// A->B, B->C, C->A of equal amounts collapses to no token movement at all.
+///
+/// # Accounting Model
+/// Positions are computed on a payout basis:
+/// - The agent of each remittance is credited `amount - fee - integrator_fee`
+/// - The sender of each remittance is debited `amount`
+/// - `fee_collector` is credited `fee + integrator_fee`, so that all positions
+///   sum to zero
+///
+/// Transfers into `fee_collector` carry `total_fees` equal to their amount: they
+/// represent fees retained by the platform rather than tokens leaving the contract.
+///
+/// # Algorithm
+/// 1. Aggregate net positions per party
+/// 2. Split parties into debtors and creditors, each sorted by size (largest first)
+///    with canonical address order as the tie-breaker
+/// 3. Repeatedly match the largest remaining debtor with the largest remaining
+///    creditor for the smaller of their two balances
+///
+/// Each step settles at least one party, so `n` participants produce at most
+/// `n - 1` transfers.
+///
+/// # Parameters
+/// - `env`: Environment reference
+/// - `remittances`: Vector of remittances to net
+/// - `fee_collector`: Address credited with platform fees (the contract itself)
+///
+/// # Returns
+/// Vector of NetTransfer structs, or Overflow if a position cannot be represented
+pub fn compute_multilateral_net_settlements(
+    env: &Env,
+    remittances: &Vec<Remittance>,
+    fee_collector: &Address,
+) -> Result<Vec<NetTransfer>, ContractError> {
+    let positions = compute_party_positions(env, remittances, fee_collector)?;
+    settle_positions(env, &positions, fee_collector)
+}
+
+/// Computes the transfers that settle a set of net party positions.
+///
+/// This is the settlement half of `compute_multilateral_net_settlements`,
+/// for callers that accumulate positions themselves (see `batching`).
+/// Positions must sum to zero; every party with a position, including the
+/// fee collector, must be a key of `positions`.
+pub fn settle_positions(
+    env: &Env,
+    positions: &Map<Address, i128>,
+    fee_collector: &Address,
+) -> Result<Vec<NetTransfer>, ContractError> {
+    let order = PartyOrder::from_parties(env, &positions.keys());
+    let fee_collector_rank = order.ranks.get(fee_collector.clone());
+
+    let mut debtors: Vec<(u32, i128)> = Vec::new(env);
+    let mut creditors: Vec<(u32, i128)> = Vec::new(env);
+
+    for (party, position) in positions.iter() {
+        if position < 0 {
+            debtors.push_back((order.rank(&party), -position));
+        } else if position > 0 {
+            creditors.push_back((order.rank(&party), position));
+        }
     }
+
+    let debtors = sort_balances(env, &debtors);
+    let creditors = sort_balances(env, &creditors);
+
+    let mut result: Vec<NetTransfer> = Vec::new(env);
+    let mut d = 0;
+    let mut c = 0;
+    let mut debtor_remaining = 0i128;
+    let mut creditor_remaining = 0i128;
+
+    while d < debtors.len() && c < creditors.len() {
+        let (debtor, debtor_balance) = debtors.get_unchecked(d);
+        let (creditor, creditor_balance) = creditors.get_unchecked(c);
+
+        if debtor_remaining == 0 {
+            debtor_remaining = debtor_balance;
+        }
+        if creditor_remaining == 0 {
+            creditor_remaining = creditor_balance;
+        }
+
+        let amount = if debtor_remaining < creditor_remaining {
+            debtor_remaining
+        } else {
+            creditor_remaining
+        };
+
+        let (party_a, party_b, direction) = if debtor < creditor {
+            (debtor, creditor, 1)
+        } else {
+            (creditor, debtor, -1)
+        };
+        let total_fees = if Some(creditor) == fee_collector_rank { amount } else { 0 };
+
+        result.push_back(NetTransfer {
+            party_a: order.party(party_a),
+            party_b: order.party(party_b),
+            net_amount: amount * direction,
+            total_fees,
+        });
+
+        debtor_remaining -= amount;
+        creditor_remaining -= amount;
+
+        if debtor_remaining == 0 {
+            d += 1;
+        }
+        if creditor_remaining == 0 {
+            c += 1;
+        }
+    }
+
+    Ok(result)
 }
 
-/// Compares two addresses lexicographically.
-/// Returns: -1 if a < b, 0 if a == b, 1 if a > b
-fn compare_addresses(a: &Address, b: &Address) -> i32 {
-    // Soroban SDK doesn't provide direct comparison, so we use a workaround
-    // We serialize both addresses and compare their byte representations
-    let a_bytes = a.to_string();
-    let b_bytes = b.to_string();
-
-    // Compare character by character
-    let a_len = a_bytes.len();
-    let b_len = b_bytes.len();
-    let min_len = if a_len < b_len { a_len } else { b_len };
-
-    for i in 0..min_len {
-        let a_char = a_bytes.get(i).unwrap();
-        let b_char = b_bytes.get(i).unwrap();
-
-        if a_char < b_char {
-            return -1;
-        } else if a_char > b_char {
-            return 1;
+/// Computes each party's net position over the pending remittances of a batch.
+///
+/// Positions are signed: positive means the party is owed funds, negative
+/// means it owes funds. See `compute_multilateral_net_settlements` for the
+/// accounting model.
+fn compute_party_positions(
+    env: &Env,
+    remittances: &Vec<Remittance>,
+    fee_collector: &Address,
+) -> Result<Map<Address, i128>, ContractError> {
+    let mut positions: Map<Address, i128> = Map::new(env);
+
+    for i in 0..remittances.len() {
+        let remittance = remittances.get_unchecked(i);
+
+        if remittance.status != RemittanceStatus::Pending {
+            continue;
         }
+
+        add_remittance_positions(&mut positions, &remittance, fee_collector)?;
     }
 
-    // If all compared characters are equal, compare lengths
-    if a_len < b_len {
-        -1
-    } else if a_len > b_len {
-        1
+    Ok(positions)
+}
+
+/// Returns the fees a remittance leaves in the contract: the platform fee
+/// plus the integrator fee.
+pub fn retained_fees(remittance: &Remittance) -> Result<i128, ContractError> {
+    remittance
+        .fee
+        .checked_add(remittance.integrator_fee)
+        .ok_or(ContractError::Overflow)
+}
+
+/// Returns the amount a remittance pays its agent: the amount minus the
+/// retained fees.
+pub fn remittance_payout(remittance: &Remittance) -> Result<i128, ContractError> {
+    remittance
+        .amount
+        .checked_sub(retained_fees(remittance)?)
+        .ok_or(ContractError::Overflow)
+}
+
+/// Adds one remittance's effect to a set of party positions: the agent is
+/// owed the payout, the fee collector the fees, and the sender owes the amount.
+pub fn add_remittance_positions(
+    positions: &mut Map<Address, i128>,
+    remittance: &Remittance,
+    fee_collector: &Address,
+) -> Result<(), ContractError> {
+    add_position(positions, &remittance.agent, remittance_payout(remittance)?)?;
+    add_position(positions, &remittance.sender, -remittance.amount)?;
+    add_position(positions, fee_collector, retained_fees(remittance)?)
+}
+
+/// Removes one remittance's effect from a set of party positions, undoing
+/// `add_remittance_positions`. Parties left with a zero position are dropped.
+pub fn remove_remittance_positions(
+    positions: &mut Map<Address, i128>,
+    remittance: &Remittance,
+    fee_collector: &Address,
+) -> Result<(), ContractError> {
+    add_position(positions, &remittance.agent, -remittance_payout(remittance)?)?;
+    add_position(positions, &remittance.sender, remittance.amount)?;
+    add_position(positions, fee_collector, -retained_fees(remittance)?)
+}
+
+fn add_position(
+    positions: &mut Map<Address, i128>,
+    party: &Address,
+    delta: i128,
+) -> Result<(), ContractError> {
+    let current = positions.get(party.clone()).unwrap_or(0);
+    let updated = current.checked_add(delta).ok_or(ContractError::Overflow)?;
+    if updated == 0 {
+        positions.remove(party.clone());
     } else {
-        0
+        positions.set(party.clone(), updated);
     }
+    Ok(())
+}
+
+/// Orders balances by amount descending, breaking ties by canonical party rank.
+///
+/// Keys of a host map are kept sorted, so inserting `(-amount, rank)` sorts
+/// the balances host-side; ranks are unique, so no entry is overwritten.
+fn sort_balances(env: &Env, balances: &Vec<(u32, i128)>) -> Vec<(u32, i128)> {
+    let mut sorted: Map<(i128, u32), ()> = Map::new(env);
+    for (rank, amount) in balances.iter() {
+        sorted.set((-amount, rank), ());
+    }
+
+    let mut result = Vec::new(env);
+    for (amount, rank) in sorted.keys().iter() {
+        result.push_back((rank, -amount));
+    }
+    result
 }
 
 /// Validates that net settlement calculations are mathematically correct.
 ///
 /// Verifies:
-/// 1. Total input amounts equal total output amounts (conservation)
-/// 2. Total fees are preserved
-/// 3. No rounding errors introduced
+/// 1. Total fees are preserved
+/// 2. Every party's net flow across the transfers equals its position in the
+///    original remittances (per-party conservation)
+/// 3. No arithmetic overflow
+///
+/// Per-party positions depend on the netting mode:
+/// - `Bilateral`: gross basis (amounts received minus amounts sent); fees travel
+///   with each transfer in `total_fees`
+/// - `Multilateral`: payout basis, with `fee_collector` credited all fees
 ///
 /// # Parameters
+/// - `env`: Environment reference
 /// - `original_remittances`: Original remittances before netting
 /// - `net_transfers`: Computed net transfers after netting
+/// - `mode`: Netting mode the transfers were computed with
+/// - `fee_collector`: Address credited with fees in multilateral mode
 ///
 /// # Returns
 /// Ok(()) if validation passes, Err(ContractError) otherwise
 pub fn validate_net_settlement(
+    env: &Env,
     original_remittances: &Vec<Remittance>,
     net_transfers: &Vec<NetTransfer>,
+    mode: NettingMode,
+    fee_collector: &Address,
 ) -> Result<(), ContractError> {
-    // Calculate total amounts and fees from original remittances
-    let mut total_original_amount: i128 = 0;
+    // Calculate total fees and expected per-party positions from original remittances
     let mut total_original_fees: i128 = 0;
+    let mut expected: Map<Address, i128> = match mode {
+        NettingMode::Bilateral => Map::new(env),
+        NettingMode::Multilateral => compute_party_positions(env, original_remittances, fee_collector)?,
+    };
 
     for i in 0..original_remittances.len() {
         let remittance = original_remittances.get_unchecked(i);
         if remittance.status == RemittanceStatus::Pending {
-            total_original_amount = total_original_amount
-                .checked_add(remittance.amount)
-                .ok_or(ContractError::Overflow)?;
             total_original_fees = total_original_fees
-                .checked_add(remittance.fee)
+                .checked_add(retained_fees(&remittance)?)
                 .ok_or(ContractError::Overflow)?;
+
+            if mode == NettingMode::Bilateral {
+                add_position(&mut expected, &remittance.agent, remittance.amount)?;
+                add_position(&mut expected, &remittance.sender, -remittance.amount)?;
+            }
         }
     }
 
-    // Calculate total amounts and fees from net transfers
-    let mut total_net_amount: i128 = 0;
-    let mut total_net_fees: i128 = 0;
-
-    for i in 0..net_transfers.len() {
-        let transfer = net_transfers.get_unchecked(i);
-        // Use absolute value since net_amount can be negative
-        let abs_amount = if transfer.net_amount < 0 {
-            -transfer.net_amount
-        } else {
-            transfer.net_amount
-        };
-
-        total_net_amount = total_net_amount
-            .checked_add(abs_amount)
-            .ok_or(ContractError::Overflow)?;
-        total_net_fees = total_net_fees
-            .checked_add(transfer.total_fees)
-            .ok_or(ContractError::Overflow)?;
-    }
+    let (actual, total_net_fees) = net_transfer_flows(env, net_transfers)?;
 
     // Verify fees are preserved exactly
     if total_original_fees != total_net_fees {
         return Err(ContractError::Overflow); // Using Overflow as a generic math error
     }
 
+    check_positions_match(&expected, &actual)?;
+
     // Note: We don't verify total amounts are equal because netting reduces
     // the total transfer volume by offsetting opposing flows. This is the
     // intended behavior and a key benefit of netting.
@@ -231,10 +477,122 @@ pub fn validate_net_settlement(
     Ok(())
 }
 
+/// Validates that net transfers settle exactly the given party positions.
+///
+/// Used for positions accumulated outside a single remittance list, where
+/// `validate_net_settlement` cannot recompute them. Fees need no separate
+/// check: they are part of the fee collector's position.
+pub fn validate_position_settlement(
+    env: &Env,
+    positions: &Map<Address, i128>,
+    net_transfers: &Vec<NetTransfer>,
+) -> Result<(), ContractError> {
+    let (actual, _) = net_transfer_flows(env, net_transfers)?;
+    check_positions_match(positions, &actual)
+}
+
+/// Sums per-party flows and fees over a set of net transfers.
+fn net_transfer_flows(
+    env: &Env,
+    net_transfers: &Vec<NetTransfer>,
+) -> Result<(Map<Address, i128>, i128), ContractError> {
+    let mut total_fees: i128 = 0;
+    let mut flows: Map<Address, i128> = Map::new(env);
+
+    for i in 0..net_transfers.len() {
+        let transfer = net_transfers.get_unchecked(i);
+
+        total_fees = total_fees
+            .checked_add(transfer.total_fees)
+            .ok_or(ContractError::Overflow)?;
+
+        // net_amount > 0 means A -> B, so B gains and A loses
+        add_position(&mut flows, &transfer.party_b, transfer.net_amount)?;
+        add_position(&mut flows, &transfer.party_a, -transfer.net_amount)?;
+    }
+
+    Ok((flows, total_fees))
+}
+
+/// Verifies every party ends up exactly where its remittances put it.
+/// Parties with a zero position may be absent from either side.
+fn check_positions_match(
+    expected: &Map<Address, i128>,
+    actual: &Map<Address, i128>,
+) -> Result<(), ContractError> {
+    for (party, position) in expected.iter() {
+        if actual.get(party).unwrap_or(0) != position {
+            return Err(ContractError::Overflow);
+        }
+    }
+    for (party, flow) in actual.iter() {
+        if expected.get(party).unwrap_or(0) != flow {
+            return Err(ContractError::Overflow);
+        }
+    }
+    Ok(())
+}
+
+/// Computes net transfers for a batch using the given netting mode.
+///
+/// # Parameters
+/// - `env`: Environment reference
+/// - `remittances`: Vector of remittances to net
+/// - `mode`: Netting strategy to apply
+/// - `fee_collector`: Address credited with fees in multilateral mode
+pub fn compute_net_settlements_for_mode(
+    env: &Env,
+    remittances: &Vec<Remittance>,
+    mode: NettingMode,
+    fee_collector: &Address,
+) -> Result<Vec<NetTransfer>, ContractError> {
+    match mode {
+        NettingMode::Bilateral => compute_net_settlements(env, remittances),
+        NettingMode::Multilateral => {
+            compute_multilateral_net_settlements(env, remittances, fee_collector)
+        }
+    }
+}
+
+/// Returns the recipient and payout amount of a net transfer, or `None` if
+/// the transfer moves no tokens.
+///
+/// The payout is the net amount minus the fees retained by the contract.
+/// Complete offsets and multilateral fee retentions move no tokens.
+pub fn net_transfer_payout(
+    transfer: &NetTransfer,
+    fee_collector: &Address,
+) -> Result<Option<(Address, i128)>, ContractError> {
+    let (to, amount) = if transfer.net_amount > 0 {
+        (transfer.party_b.clone(), transfer.net_amount)
+    } else if transfer.net_amount < 0 {
+        (transfer.party_a.clone(), -transfer.net_amount)
+    } else {
+        return Ok(None);
+    };
+
+    let payout_amount = amount
+        .checked_sub(transfer.total_fees)
+        .ok_or(ContractError::Overflow)?;
+
+    if payout_amount > 0 && to != *fee_collector {
+        Ok(Some((to, payout_amount)))
+    } else {
+        Ok(None)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use soroban_sdk::{testutils::Address as _, Env};
+    use soroban_sdk::{testutils::Address as _, Env, String};
+
+    fn test_corridor(env: &Env) -> crate::Corridor {
+        crate::Corridor {
+            currency: String::from_str(env, "USD"),
+            country: String::from_str(env, "US"),
+        }
+    }
 
     #[test]
     fn test_simple_netting() {
@@ -251,8 +609,10 @@ mod tests {
             agent: addr_b.clone(),
             amount: 100,
             fee: 2,
+            integrator_fee: 0,
             status: RemittanceStatus::Pending,
             expiry: None,
+            corridor: test_corridor(&env),
         });
 
         // B -> A: 90
@@ -262,23 +622,25 @@ mod tests {
             agent: addr_a.clone(),
             amount: 90,
             fee: 1,
+            integrator_fee: 0,
             status: RemittanceStatus::Pending,
             expiry: None,
+            corridor: test_corridor(&env),
         });
 
-        let net_transfers = compute_net_settlements(&remittances);
+        let net_transfers = compute_net_settlements(&env, &remittances).unwrap();
 
         assert_eq!(net_transfers.len(), 1);
         let transfer = net_transfers.get_unchecked(0);
 
         // Net should be 10 (100 - 90)
-        let expected_net = if compare_addresses(&addr_a, &addr_b) < 0 {
+        let expected_net = if address_to_bytes(&env, &addr_a) < address_to_bytes(&env, &addr_b) {
             10 // A -> B
         } else {
             -10 // B -> A
         };
 
-        assert_eq!(transfer.net_amount.abs(), 10);
+        assert_eq!(transfer.net_amount, expected_net);
         assert_eq!(transfer.total_fees, 3); // 2 + 1
     }
 
@@ -297,8 +659,10 @@ mod tests {
             agent: addr_b.clone(),
             amount: 100,
             fee: 2,
+            integrator_fee: 0,
             status: RemittanceStatus::Pending,
             expiry: None,
+            corridor: test_corridor(&env),
         });
 
         // B -> A: 100
@@ -308,14 +672,20 @@ mod tests {
             agent: addr_a.clone(),
             amount: 100,
             fee: 2,
+            integrator_fee: 0,
             status: RemittanceStatus::Pending,
             expiry: None,
+            corridor: test_corridor(&env),
         });
 
-        let net_transfers = compute_net_settlements(&remittances);
+        let net_transfers = compute_net_settlements(&env, &remittances).unwrap();
 
-        // Complete offset should result in no transfers
-        assert_eq!(net_transfers.len(), 0);
+        // Complete offset moves no tokens but keeps the fees
+        assert_eq!(net_transfers.len(), 1);
+        let transfer = net_transfers.get_unchecked(0);
+        assert_eq!(transfer.net_amount, 0);
+        assert_eq!(transfer.total_fees, 4);
+        assert_eq!(net_transfer_payout(&transfer, &addr_a).unwrap(), None);
     }
 
     #[test]
@@ -334,8 +704,10 @@ mod tests {
             agent: addr_b.clone(),
             amount: 100,
             fee: 2,
+            integrator_fee: 0,
             status: RemittanceStatus::Pending,
             expiry: None,
+            corridor: test_corridor(&env),
         });
 
         // B -> C: 50
@@ -345,8 +717,10 @@ mod tests {
             agent: addr_c.clone(),
             amount: 50,
             fee: 1,
+            integrator_fee: 0,
             status: RemittanceStatus::Pending,
             expiry: None,
+            corridor: test_corridor(&env),
         });
 
         // C -> A: 30
@@ -356,11 +730,13 @@ mod tests {
             agent: addr_a.clone(),
             amount: 30,
             fee: 1,
+            integrator_fee: 0,
             status: RemittanceStatus::Pending,
             expiry: None,
+            corridor: test_corridor(&env),
         });
 
-        let net_transfers = compute_net_settlements(&remittances);
+        let net_transfers = compute_net_settlements(&env, &remittances).unwrap();
 
         // Should have 3 net transfers (one for each pair)
         assert_eq!(net_transfers.len(), 3);
@@ -387,8 +763,10 @@ mod tests {
             agent: addr_b.clone(),
             amount: 100,
             fee: 2,
+            integrator_fee: 0,
             status: RemittanceStatus::Pending,
             expiry: None,
+            corridor: test_corridor(&env),
         });
 
         remittances.push_back(Remittance {
@@ -397,13 +775,23 @@ mod tests {
             agent: addr_a.clone(),
             amount: 90,
             fee: 1,
+            integrator_fee: 0,
             status: RemittanceStatus::Pending,
             expiry: None,
+            corridor: test_corridor(&env),
         });
 
-        let net_transfers = compute_net_settlements(&remittances);
+        let net_transfers = compute_net_settlements(&env, &remittances).unwrap();
 
-        assert!(validate_net_settlement(&remittances, &net_transfers).is_ok());
+        let fee_collector = Address::generate(&env);
+        assert!(validate_net_settlement(
+            &env,
+            &remittances,
+            &net_transfers,
+            NettingMode::Bilateral,
+            &fee_collector
+        )
+        .is_ok());
     }
 
     #[test]
@@ -420,8 +808,10 @@ mod tests {
             agent: addr_b.clone(),
             amount: 100,
             fee: 2,
+            integrator_fee: 0,
             status: RemittanceStatus::Pending,
             expiry: None,
+            corridor: test_corridor(&env),
         });
         remittances1.push_back(Remittance {
             id: 2,
@@ -429,8 +819,10 @@ mod tests {
             agent: addr_a.clone(),
             amount: 90,
             fee: 1,
+            integrator_fee: 0,
             status: RemittanceStatus::Pending,
             expiry: None,
+            corridor: test_corridor(&env),
         });
 
         // Second ordering (reversed)
@@ -441,8 +833,10 @@ mod tests {
             agent: addr_a.clone(),
             amount: 90,
             fee: 1,
+            integrator_fee: 0,
             status: RemittanceStatus::Pending,
             expiry: None,
+            corridor: test_corridor(&env),
         });
         remittances2.push_back(Remittance {
             id: 1,
@@ -450,20 +844,333 @@ mod tests {
             agent: addr_b.clone(),
             amount: 100,
             fee: 2,
+            integrator_fee: 0,
             status: RemittanceStatus::Pending,
             expiry: None,
+            corridor: test_corridor(&env),
         });
 
-        let net1 = compute_net_settlements(&remittances1);
-        let net2 = compute_net_settlements(&remittances2);
+        let net1 = compute_net_settlements(&env, &remittances1).unwrap();
+        let net2 = compute_net_settlements(&env, &remittances2).unwrap();
 
         // Results should be identical regardless of input order
         assert_eq!(net1.len(), net2.len());
-        if net1.len() > 0 {
+        if !net1.is_empty() {
             let t1 = net1.get_unchecked(0);
             let t2 = net2.get_unchecked(0);
             assert_eq!(t1.net_amount, t2.net_amount);
             assert_eq!(t1.total_fees, t2.total_fees);
         }
     }
+
+    fn pending(env: &Env, id: u64, from: &Address, to: &Address, amount: i128, fee: i128) -> Remittance {
+        Remittance {
+            id,
+            sender: from.clone(),
+            agent: to.clone(),
+            amount,
+            fee,
+            integrator_fee: 0,
+            status: RemittanceStatus::Pending,
+            expiry: None,
+            corridor: test_corridor(env),
+        }
+    }
+
+    #[test]
+    fn test_multilateral_cycle_collapses() {
+        let env = Env::default();
+        let addr_a = Address::generate(&env);
+        let addr_b = Address::generate(&env);
+        let addr_c = Address::generate(&env);
+        let fee_collector = Address::generate(&env);
+
+        let mut remittances = Vec::new(&env);
+        remittances.push_back(pending(&env, 1, &addr_a, &addr_b, 100, 0));
+        remittances.push_back(pending(&env, 2, &addr_b, &addr_c, 100, 0));
+        remittances.push_back(pending(&env, 3, &addr_c, &addr_a, 100, 0));
+
+        let net_transfers =
+            compute_multilateral_net_settlements(&env, &remittances, &fee_collector).unwrap();
+
+        // Bilateral netting cannot offset anything here
+        assert_eq!(compute_net_settlements(&env, &remittances).unwrap().len(), 3);
+        assert_eq!(net_transfers.len(), 0);
+    }
+
+    #[test]
+    fn test_multilateral_cycle_with_fees_only_retains_fees() {
+        let env = Env::default();
+        let addr_a = Address::generate(&env);
+        let addr_b = Address::generate(&env);
+        let addr_c = Address::generate(&env);
+        let fee_collector = Address::generate(&env);
+
+        let mut remittances = Vec::new(&env);
+        remittances.push_back(pending(&env, 1, &addr_a, &addr_b, 100, 2));
+        remittances.push_back(pending(&env, 2, &addr_b, &addr_c, 100, 2));
+        remittances.push_back(pending(&env, 3, &addr_c, &addr_a, 100, 2));
+
+        let net_transfers =
+            compute_multilateral_net_settlements(&env, &remittances, &fee_collector).unwrap();
+
+        // Each party owes only its fee; every transfer goes to the fee collector
+        assert_eq!(net_transfers.len(), 3);
+        for transfer in net_transfers.iter() {
+            assert_eq!(transfer.net_amount.abs(), 2);
+            assert_eq!(transfer.total_fees, 2);
+            assert!(transfer.party_a == fee_collector || transfer.party_b == fee_collector);
+        }
+
+        assert!(validate_net_settlement(
+            &env,
+            &remittances,
+            &net_transfers,
+            NettingMode::Multilateral,
+            &fee_collector
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_multilateral_chain_routes_through_intermediary() {
+        let env = Env::default();
+        let addr_a = Address::generate(&env);
+        let addr_b = Address::generate(&env);
+        let addr_c = Address::generate(&env);
+        let fee_collector = Address::generate(&env);
+
+        let mut remittances = Vec::new(&env);
+        remittances.push_back(pending(&env, 1, &addr_a, &addr_b, 100, 0));
+        remittances.push_back(pending(&env, 2, &addr_b, &addr_c, 100, 0));
+
+        let net_transfers =
+            compute_multilateral_net_settlements(&env, &remittances, &fee_collector).unwrap();
+
+        // B is flat; A pays C directly
+        assert_eq!(net_transfers.len(), 1);
+        let transfer = net_transfers.get_unchecked(0);
+        let (from, to) = if transfer.net_amount > 0 {
+            (transfer.party_a.clone(), transfer.party_b.clone())
+        } else {
+            (transfer.party_b.clone(), transfer.party_a.clone())
+        };
+        assert_eq!(from, addr_a);
+        assert_eq!(to, addr_c);
+        assert_eq!(transfer.net_amount.abs(), 100);
+    }
+
+    #[test]
+    fn test_multilateral_order_independence() {
+        let env = Env::default();
+        let addr_a = Address::generate(&env);
+        let addr_b = Address::generate(&env);
+        let addr_c = Address::generate(&env);
+        let addr_d = Address::generate(&env);
+        let fee_collector = Address::generate(&env);
+
+        let r1 = pending(&env, 1, &addr_a, &addr_b, 300, 3);
+        let r2 = pending(&env, 2, &addr_c, &addr_d, 120, 1);
+        let r3 = pending(&env, 3, &addr_b, &addr_c, 80, 1);
+        let r4 = pending(&env, 4, &addr_d, &addr_a, 50, 0);
+
+        let mut forward = Vec::new(&env);
+        forward.push_back(r1.clone());
+        forward.push_back(r2.clone());
+        forward.push_back(r3.clone());
+        forward.push_back(r4.clone());
+
+        let mut reversed = Vec::new(&env);
+        reversed.push_back(r4);
+        reversed.push_back(r3);
+        reversed.push_back(r2);
+        reversed.push_back(r1);
+
+        let net1 = compute_multilateral_net_settlements(&env, &forward, &fee_collector).unwrap();
+        let net2 = compute_multilateral_net_settlements(&env, &reversed, &fee_collector).unwrap();
+
+        assert_eq!(net1, net2);
+        // 5 parties including the fee collector settle in at most 4 transfers
+        assert!(net1.len() <= 4);
+        assert!(validate_net_settlement(
+            &env,
+            &forward,
+            &net1,
+            NettingMode::Multilateral,
+            &fee_collector
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_bilateral_netting_rejects_overflow() {
+        let env = Env::default();
+        let addr_a = Address::generate(&env);
+        let addr_b = Address::generate(&env);
+
+        let mut remittances = Vec::new(&env);
+        remittances.push_back(pending(&env, 1, &addr_a, &addr_b, i128::MAX, 0));
+        remittances.push_back(pending(&env, 2, &addr_a, &addr_b, 1, 0));
+        assert_eq!(
+            compute_net_settlements(&env, &remittances),
+            Err(ContractError::Overflow)
+        );
+
+        let mut remittances = Vec::new(&env);
+        remittances.push_back(pending(&env, 1, &addr_a, &addr_b, 100, i128::MAX));
+        remittances.push_back(pending(&env, 2, &addr_b, &addr_a, 100, 1));
+        assert_eq!(
+            compute_net_settlements(&env, &remittances),
+            Err(ContractError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_validation_detects_party_imbalance() {
+        let env = Env::default();
+        let addr_a = Address::generate(&env);
+        let addr_b = Address::generate(&env);
+        let addr_c = Address::generate(&env);
+        let fee_collector = Address::generate(&env);
+
+        let mut remittances = Vec::new(&env);
+        remittances.push_back(pending(&env, 1, &addr_a, &addr_b, 100, 0));
+
+        // Same total volume and fees, but paid to the wrong party
+        let mut tampered = Vec::new(&env);
+        tampered.push_back(NetTransfer {
+            party_a: addr_a.clone(),
+            party_b: addr_c.clone(),
+            net_amount: 100,
+            total_fees: 0,
+        });
+
+        assert_eq!(
+            validate_net_settlement(
+                &env,
+                &remittances,
+                &tampered,
+                NettingMode::Multilateral,
+                &fee_collector
+            ),
+            Err(ContractError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_parties_ordered_by_canonical_encoding() {
+        let env = Env::default();
+        let fee_collector = Address::generate(&env);
+
+        let mut remittances = Vec::new(&env);
+        for i in 0..10 {
+            let sender = Address::generate(&env);
+            let agent = Address::generate(&env);
+            remittances.push_back(pending(&env, i, &sender, &agent, 100, 1));
+        }
+
+        for transfer in compute_net_settlements(&env, &remittances).unwrap().iter() {
+            assert!(address_to_bytes(&env, &transfer.party_a) < address_to_bytes(&env, &transfer.party_b));
+        }
+
+        let transfers =
+            compute_multilateral_net_settlements(&env, &remittances, &fee_collector).unwrap();
+        for transfer in transfers.iter() {
+            assert!(address_to_bytes(&env, &transfer.party_a) < address_to_bytes(&env, &transfer.party_b));
+        }
+    }
+
+    /// Builds a full batch where every remittance has a distinct sender and
+    /// agent, the worst case for party ordering.
+    fn max_size_batch(env: &Env) -> Vec<Remittance> {
+        let mut remittances = Vec::new(env);
+        for i in 0..MAX_BATCH_SIZE {
+            let sender = Address::generate(env);
+            let agent = Address::generate(env);
+            remittances.push_back(pending(env, i as u64, &sender, &agent, 1000 + i as i128, 25));
+        }
+        remittances
+    }
+
+    // Resource benchmarks at MAX_BATCH_SIZE. Run with `cargo test bench_`;
+    // they fail when the measured costs drift from the recorded ones, which
+    // are also listed in NET_SETTLEMENT.md.
+    use crate::{estimate_netting_cost, TX_CPU_INSTRUCTION_LIMIT, TX_MEMORY_LIMIT};
+
+    /// Checks that a measured cost is within a tenth of the recorded cost.
+    fn assert_near_recorded(measured: u64, recorded: u64) {
+        assert!(
+            measured.abs_diff(recorded) <= recorded / 10,
+            "measured {} against recorded {}",
+            measured,
+            recorded
+        );
+    }
+
+    /// Checks that the cost model's netting estimate bounds the measured
+    /// cost without overstating it by more than a third.
+    fn assert_estimate_fits((estimated_cpu, estimated_mem): (u64, u64), cpu: u64, mem: u64) {
+        assert!(cpu <= estimated_cpu && estimated_cpu <= cpu / 3 * 4);
+        assert!(mem <= estimated_mem && estimated_mem <= mem / 3 * 4);
+    }
+
+    #[test]
+    fn bench_bilateral_netting_at_max_batch_size() {
+        let env = Env::default();
+        let remittances = max_size_batch(&env);
+        let fee_collector = Address::generate(&env);
+
+        env.budget().reset_default();
+        let transfers = compute_net_settlements(&env, &remittances).unwrap();
+        validate_net_settlement(&env, &remittances, &transfers, NettingMode::Bilateral, &fee_collector)
+            .unwrap();
+
+        let cpu = env.budget().cpu_instruction_cost();
+        let mem = env.budget().memory_bytes_cost();
+
+        assert_eq!(transfers.len(), MAX_BATCH_SIZE);
+        assert_near_recorded(cpu, 10_304_114);
+        assert_near_recorded(mem, 690_064);
+        assert!(cpu < TX_CPU_INSTRUCTION_LIMIT / 4);
+        assert!(mem < TX_MEMORY_LIMIT / 4);
+
+        assert_estimate_fits(
+            estimate_netting_cost(NettingMode::Bilateral, MAX_BATCH_SIZE, MAX_BATCH_SIZE * 2),
+            cpu,
+            mem,
+        );
+    }
+
+    #[test]
+    fn bench_multilateral_netting_at_max_batch_size() {
+        let env = Env::default();
+        let remittances = max_size_batch(&env);
+        let fee_collector = Address::generate(&env);
+
+        env.budget().reset_default();
+        let transfers =
+            compute_multilateral_net_settlements(&env, &remittances, &fee_collector).unwrap();
+        validate_net_settlement(
+            &env,
+            &remittances,
+            &transfers,
+            NettingMode::Multilateral,
+            &fee_collector,
+        )
+        .unwrap();
+
+        let cpu = env.budget().cpu_instruction_cost();
+        let mem = env.budget().memory_bytes_cost();
+
+        assert_near_recorded(cpu, 18_169_219);
+        assert_near_recorded(mem, 1_258_102);
+        assert!(cpu < TX_CPU_INSTRUCTION_LIMIT / 4);
+        assert!(mem < TX_MEMORY_LIMIT / 4);
+
+        assert_estimate_fits(
+            estimate_netting_cost(NettingMode::Multilateral, MAX_BATCH_SIZE, MAX_BATCH_SIZE * 2),
+            cpu,
+            mem,
+        );
+    }
 }
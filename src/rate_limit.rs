@@ -4,7 +4,7 @@ use crate::ContractError;
 
 /// Rate limit configuration stored in instance storage
 #[contracttype]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct RateLimitConfig {
     /// Maximum number of requests allowed per window
     pub max_requests: u32,
@@ -26,7 +26,7 @@ struct RateLimitEntry {
 
 #[contracttype]
 #[derive(Clone)]
-enum RateLimitKey {
+pub(crate) enum RateLimitKey {
     /// Global rate limit configuration
     Config,
     /// Per-address rate limit tracking
@@ -0,0 +1,193 @@
+//! State digests for reconciling two deployments.
+//!
+//! After a migration the old and new contracts should hold the same state.
+//! A single snapshot hash only says whether everything matches; the digests
+//! here are computed per section and per range, so a mismatch can be
+//! narrowed down by bisecting the range on both contracts until the
+//! differing remittances or entries are found.
+//!
+//! ## Digest
+//!
+//! digest = SHA-256(section (u32 BE) || start (u64 BE) || end (u64 BE) || item...)
+//!
+//! where each item is the encoding of one entry of the section, in order.
+//! Remittances and addresses use the canonical encodings from `hashing.rs`
+//! and `migration.rs`; configuration and limits, which have none, are
+//! XDR-encoded as in the snapshot hash:
+//!
+//! | Section       | Range over                  | Item                                       |
+//! |---------------|-----------------------------|--------------------------------------------|
+//! | `Config`      | ignored, always everything  | `InstanceData` as XDR                      |
+//! | `Remittances` | remittance IDs              | remittance, settlement record, memo (below) |
+//! | `Agents`      | positions in address order  | `address_to_bytes`                         |
+//! | `Admins`      | positions in address order  | `address_to_bytes`                         |
+//! | `Tokens`      | positions in address order  | `address_to_bytes`                         |
+//! | `Limits`      | positions in (currency, country) order | `DailyLimit` as XDR             |
+//!
+//! A remittance item is `encode_remittance`, then 0x00 if the remittance has
+//! no settlement record or 0x01 followed by `encode_settlement_record`, then
+//! 0x00 if it has no memo or 0x01 followed by the 32-byte memo.
+//!
+//! Address and limit sections are sorted rather than listed in registry
+//! order, so two contracts holding the same set produce the same digest
+//! regardless of the order entries were registered in. Ranges are
+//! half-open, `[start, end)`, and are capped at `MAX_DIGEST_RANGE` entries;
+//! the returned digest reports the range it actually covers.
+
+use soroban_sdk::{contracttype, xdr::ToXdr, Address, Bytes, BytesN, Env, Map, String, Vec};
+
+use crate::hashing::address_to_bytes;
+use crate::{
+    encode_remittance, encode_settlement_record, export_instance_data, export_settlement_record,
+    ContractError, Remittance,
+};
+
+/// Maximum number of entries covered by one digest.
+pub const MAX_DIGEST_RANGE: u64 = 200;
+
+/// Section of contract state covered by a digest.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum StateSection {
+    /// Contract-level configuration and counters
+    Config = 1,
+    /// Remittances with their settlement records and memos
+    Remittances = 2,
+    /// Registered agents
+    Agents = 3,
+    /// Admins
+    Admins = 4,
+    /// Whitelisted tokens
+    Tokens = 5,
+    /// Daily send limits
+    Limits = 6,
+}
+
+/// Digest of one range of one section of contract state.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StateDigest {
+    /// Section the digest covers
+    pub section: StateSection,
+    /// First position or remittance ID covered
+    pub start: u64,
+    /// Position or remittance ID after the last one covered
+    pub end: u64,
+    /// Number of entries folded into the digest
+    pub item_count: u32,
+    /// SHA-256 over the section, range and entries
+    pub digest: BytesN<32>,
+}
+
+/// Computes the digest of `section` over `[start, end)`.
+pub fn compute_state_digest(
+    env: &Env,
+    section: StateSection,
+    start: u64,
+    end: u64,
+) -> Result<StateDigest, ContractError> {
+    let (start, end) = match section {
+        StateSection::Config => (0, 1),
+        _ => (start, end.min(start.saturating_add(MAX_DIGEST_RANGE))),
+    };
+
+    let mut items: Vec<Bytes> = Vec::new(env);
+    match section {
+        StateSection::Config => {
+            items.push_back(export_instance_data(env)?.to_xdr(env));
+        }
+        StateSection::Remittances => {
+            for id in start.max(1)..end {
+                if let Ok(remittance) = crate::storage::get_remittance(env, id) {
+                    items.push_back(encode_remittance_item(env, &remittance));
+                }
+            }
+        }
+        StateSection::Agents => {
+            push_sorted_addresses(env, &mut items, crate::storage::list_agents(env, 0, u32::MAX), start, end);
+        }
+        StateSection::Admins => {
+            push_sorted_addresses(env, &mut items, crate::storage::list_admins(env, 0, u32::MAX), start, end);
+        }
+        StateSection::Tokens => {
+            push_sorted_addresses(
+                env,
+                &mut items,
+                crate::storage::list_whitelisted_tokens(env, 0, u32::MAX),
+                start,
+                end,
+            );
+        }
+        StateSection::Limits => {
+            let mut sorted: Map<(String, String), i128> = Map::new(env);
+            for limit in crate::storage::list_daily_limits(env).iter() {
+                sorted.set((limit.currency, limit.country), limit.limit);
+            }
+            for (position, ((currency, country), limit)) in sorted.iter().enumerate() {
+                if in_range(position, start, end) {
+                    items.push_back(crate::DailyLimit { currency, country, limit }.to_xdr(env));
+                }
+            }
+        }
+    }
+
+    let mut data = Bytes::new(env);
+    data.extend_from_array(&(section as u32).to_be_bytes());
+    data.extend_from_array(&start.to_be_bytes());
+    data.extend_from_array(&end.to_be_bytes());
+    for item in items.iter() {
+        data.append(&item);
+    }
+
+    Ok(StateDigest {
+        section,
+        start,
+        end,
+        item_count: items.len(),
+        digest: env.crypto().sha256(&data).into(),
+    })
+}
+
+/// Encodes a remittance with its settlement record and memo.
+fn encode_remittance_item(env: &Env, remittance: &Remittance) -> Bytes {
+    let mut item = encode_remittance(env, remittance);
+    match export_settlement_record(env, remittance.id) {
+        Some(record) => {
+            item.push_back(1);
+            item.append(&encode_settlement_record(env, &record));
+        }
+        None => item.push_back(0),
+    }
+    match crate::storage::get_remittance_memo(env, remittance.id) {
+        Some(memo) => {
+            item.push_back(1);
+            item.extend_from_array(&memo.to_array());
+        }
+        None => item.push_back(0),
+    }
+    item
+}
+
+fn push_sorted_addresses(
+    env: &Env,
+    items: &mut Vec<Bytes>,
+    addresses: Vec<Address>,
+    start: u64,
+    end: u64,
+) {
+    let mut sorted: Map<Address, ()> = Map::new(env);
+    for address in addresses.iter() {
+        sorted.set(address, ());
+    }
+    for (position, address) in sorted.keys().iter().enumerate() {
+        if in_range(position, start, end) {
+            items.push_back(address_to_bytes(env, &address));
+        }
+    }
+}
+
+fn in_range(position: usize, start: u64, end: u64) -> bool {
+    let position = position as u64;
+    position >= start && position < end
+}
@@ -0,0 +1,239 @@
+//! Scheduled settlement cycles for the SwiftRemit contract.
+//!
+//! Instead of settling each payout on confirmation, a corridor can be put on
+//! a fixed settlement cycle (for example every four hours). Agents confirm
+//! payouts into the corridor's open cycle, and once the cycle's window has
+//! elapsed anyone can close it: the queued remittances are netted and
+//! settled together and a summary of the cycle is recorded.
+//!
+//! Cycles open lazily on the first confirmation and stay open until closed,
+//! so a confirmation arriving after the window ends but before the cycle is
+//! closed joins the current cycle.
+
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+use crate::{ContractError, Corridor, NettingMode};
+
+/// Settlement cycle configuration for a corridor.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementCycleConfig {
+    /// Length of a cycle in seconds
+    pub duration: u64,
+    /// Netting strategy applied when a cycle closes
+    pub mode: NettingMode,
+}
+
+/// A settlement cycle that is still collecting remittances.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementCycle {
+    /// Unique cycle identifier
+    pub cycle_id: u64,
+    /// Corridor the cycle settles
+    pub corridor: Corridor,
+    /// Ledger timestamp the cycle opened at
+    pub opened_at: u64,
+    /// Ledger timestamp from which the cycle can be closed
+    pub closes_at: u64,
+    /// Remittances confirmed into the cycle, in confirmation order
+    pub remittance_ids: Vec<u64>,
+}
+
+/// Record of a closed settlement cycle.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CycleSummary {
+    /// Unique cycle identifier
+    pub cycle_id: u64,
+    /// Corridor the cycle settled
+    pub corridor: Corridor,
+    /// Ledger timestamp the cycle opened at
+    pub opened_at: u64,
+    /// Ledger timestamp the cycle was closed at
+    pub closed_at: u64,
+    /// Remittances settled by the cycle
+    pub remittance_ids: Vec<u64>,
+    /// Distinct senders and agents with a position in the cycle, in address
+    /// order
+    pub participants: Vec<Address>,
+    /// Tokens paid out without netting (sum of amount minus fees)
+    pub gross_volume: i128,
+    /// Amount actually transferred on-chain after netting
+    pub net_volume: i128,
+    /// Platform and integrator fees collected by the cycle
+    pub fees: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub(crate) enum CycleKey {
+    /// Cycle configuration per corridor (persistent storage)
+    Config(Corridor),
+    /// Open cycle per corridor (persistent storage)
+    Open(Corridor),
+    /// Counter for generating cycle IDs (instance storage)
+    Counter,
+    /// Summary of a closed cycle (persistent storage)
+    Summary(u64),
+    /// Cycle a remittance is queued in (persistent storage)
+    Queued(u64),
+    /// Corridors with a cycle configuration, in the order they were first
+    /// configured (instance storage)
+    CycleCorridors,
+}
+
+/// Sets the settlement cycle configuration for a corridor.
+pub fn set_cycle_config(env: &Env, corridor: &Corridor, config: &SettlementCycleConfig) {
+    env.storage()
+        .persistent()
+        .set(&CycleKey::Config(corridor.clone()), config);
+
+    let mut corridors = list_cycle_corridors(env);
+    if !corridors.contains(corridor) {
+        corridors.push_back(corridor.clone());
+        env.storage().instance().set(&CycleKey::CycleCorridors, &corridors);
+    }
+}
+
+/// Lists the corridors with a cycle configuration, in the order they were
+/// first configured. Only these corridors can have an open cycle.
+pub fn list_cycle_corridors(env: &Env) -> Vec<Corridor> {
+    env.storage()
+        .instance()
+        .get(&CycleKey::CycleCorridors)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Gets the settlement cycle configuration for a corridor, if any.
+pub fn get_cycle_config(env: &Env, corridor: &Corridor) -> Option<SettlementCycleConfig> {
+    env.storage()
+        .persistent()
+        .get(&CycleKey::Config(corridor.clone()))
+}
+
+/// Gets the open cycle for a corridor, if any.
+pub fn get_open_cycle(env: &Env, corridor: &Corridor) -> Option<SettlementCycle> {
+    env.storage()
+        .persistent()
+        .get(&CycleKey::Open(corridor.clone()))
+}
+
+/// Stores the open cycle for its corridor and queues its remittances in it.
+pub fn set_open_cycle(env: &Env, cycle: &SettlementCycle) {
+    env.storage()
+        .persistent()
+        .set(&CycleKey::Open(cycle.corridor.clone()), cycle);
+    for remittance_id in cycle.remittance_ids.iter() {
+        env.storage()
+            .persistent()
+            .set(&CycleKey::Queued(remittance_id), &cycle.cycle_id);
+    }
+}
+
+/// Returns the ID of the most recently opened cycle, 0 before the first.
+pub fn get_cycle_counter(env: &Env) -> u64 {
+    env.storage().instance().get(&CycleKey::Counter).unwrap_or(0)
+}
+
+/// Sets the ID of the most recently opened cycle.
+pub fn set_cycle_counter(env: &Env, cycle_id: u64) {
+    env.storage().instance().set(&CycleKey::Counter, &cycle_id);
+}
+
+/// Gets the cycle a remittance is queued in, if any.
+pub fn get_queued_cycle(env: &Env, remittance_id: u64) -> Option<u64> {
+    env.storage().persistent().get(&CycleKey::Queued(remittance_id))
+}
+
+/// Fails if the remittance is queued in a settlement cycle.
+pub fn validate_not_queued(env: &Env, remittance_id: u64) -> Result<(), ContractError> {
+    if get_queued_cycle(env, remittance_id).is_some() {
+        return Err(ContractError::RemittanceQueued);
+    }
+    Ok(())
+}
+
+/// Queues a remittance in its corridor's open cycle, opening one if needed.
+///
+/// Returns the cycle the remittance was queued in.
+pub fn enqueue_remittance(
+    env: &Env,
+    corridor: &Corridor,
+    remittance_id: u64,
+    max_size: u32,
+) -> Result<SettlementCycle, ContractError> {
+    let config =
+        get_cycle_config(env, corridor).ok_or(ContractError::SettlementCycleNotConfigured)?;
+
+    let mut cycle = match get_open_cycle(env, corridor) {
+        Some(cycle) => cycle,
+        None => {
+            let cycle_id = get_cycle_counter(env)
+                .checked_add(1)
+                .ok_or(ContractError::Overflow)?;
+            set_cycle_counter(env, cycle_id);
+
+            let opened_at = env.ledger().timestamp();
+            SettlementCycle {
+                cycle_id,
+                corridor: corridor.clone(),
+                opened_at,
+                closes_at: opened_at.saturating_add(config.duration),
+                remittance_ids: Vec::new(env),
+            }
+        }
+    };
+
+    if cycle.remittance_ids.len() >= max_size {
+        return Err(ContractError::SettlementCycleFull);
+    }
+
+    cycle.remittance_ids.push_back(remittance_id);
+    env.storage()
+        .persistent()
+        .set(&CycleKey::Open(corridor.clone()), &cycle);
+    env.storage()
+        .persistent()
+        .set(&CycleKey::Queued(remittance_id), &cycle.cycle_id);
+
+    Ok(cycle)
+}
+
+/// Removes the open cycle for a corridor so it can be settled.
+///
+/// The cycle must have reached its closing time or be full.
+pub fn take_due_cycle(
+    env: &Env,
+    corridor: &Corridor,
+    max_size: u32,
+) -> Result<SettlementCycle, ContractError> {
+    let cycle = get_open_cycle(env, corridor).ok_or(ContractError::SettlementCycleNotFound)?;
+
+    if env.ledger().timestamp() < cycle.closes_at && cycle.remittance_ids.len() < max_size {
+        return Err(ContractError::SettlementCycleNotDue);
+    }
+
+    env.storage()
+        .persistent()
+        .remove(&CycleKey::Open(corridor.clone()));
+    for remittance_id in cycle.remittance_ids.iter() {
+        env.storage()
+            .persistent()
+            .remove(&CycleKey::Queued(remittance_id));
+    }
+
+    Ok(cycle)
+}
+
+/// Stores the summary of a closed cycle.
+pub fn set_cycle_summary(env: &Env, summary: &CycleSummary) {
+    env.storage()
+        .persistent()
+        .set(&CycleKey::Summary(summary.cycle_id), summary);
+}
+
+/// Gets the summary of a closed cycle.
+pub fn get_cycle_summary(env: &Env, cycle_id: u64) -> Option<CycleSummary> {
+    env.storage().persistent().get(&CycleKey::Summary(cycle_id))
+}
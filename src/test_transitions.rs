@@ -15,6 +15,13 @@ fn create_swiftremit_contract<'a>(env: &Env) -> SwiftRemitContractClient<'a> {
     SwiftRemitContractClient::new(env, &env.register_contract(None, SwiftRemitContract {}))
 }
 
+fn default_corridor(env: &Env) -> crate::Corridor {
+    crate::Corridor {
+        currency: soroban_sdk::String::from_str(env, "USD"),
+        country: soroban_sdk::String::from_str(env, "US"),
+    }
+}
+
 fn setup_contract(env: &Env) -> (SwiftRemitContractClient, token::StellarAssetClient, Address, Address, Address) {
     let admin = Address::generate(env);
     let token_admin = Address::generate(env);
@@ -40,7 +47,7 @@ fn test_lifecycle_pending_to_processing() {
     let (contract, _token, _admin, agent, sender) = setup_contract(&env);
 
     env.mock_all_auths();
-    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &None);
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &default_corridor(&env), &None, &None, &None, &None);
 
     let remittance = contract.get_remittance(&remittance_id);
     assert_eq!(remittance.status, RemittanceStatus::Pending);
@@ -57,7 +64,7 @@ fn test_lifecycle_pending_to_cancelled() {
     let (contract, _token, _admin, agent, sender) = setup_contract(&env);
 
     env.mock_all_auths();
-    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &None);
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &default_corridor(&env), &None, &None, &None, &None);
 
     let remittance = contract.get_remittance(&remittance_id);
     assert_eq!(remittance.status, RemittanceStatus::Pending);
@@ -74,7 +81,7 @@ fn test_lifecycle_processing_to_completed() {
     let (contract, _token, _admin, agent, sender) = setup_contract(&env);
 
     env.mock_all_auths();
-    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &None);
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &default_corridor(&env), &None, &None, &None, &None);
 
     contract.start_processing(&remittance_id);
 
@@ -93,7 +100,7 @@ fn test_lifecycle_processing_to_failed() {
     let (contract, _token, _admin, agent, sender) = setup_contract(&env);
 
     env.mock_all_auths();
-    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &None);
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &default_corridor(&env), &None, &None, &None, &None);
 
     contract.start_processing(&remittance_id);
 
@@ -113,7 +120,7 @@ fn test_invalid_transition_pending_to_completed() {
     let (contract, _token, _admin, agent, sender) = setup_contract(&env);
 
     env.mock_all_auths();
-    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &None);
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &default_corridor(&env), &None, &None, &None, &None);
 
     // Should fail: cannot go directly from Pending to Completed
     contract.confirm_payout(&remittance_id);
@@ -126,7 +133,7 @@ fn test_invalid_transition_pending_to_failed() {
     let (contract, _token, _admin, agent, sender) = setup_contract(&env);
 
     env.mock_all_auths();
-    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &None);
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &default_corridor(&env), &None, &None, &None, &None);
 
     // Should fail: cannot go directly from Pending to Failed
     contract.mark_failed(&remittance_id);
@@ -139,7 +146,7 @@ fn test_invalid_transition_processing_to_cancelled() {
     let (contract, _token, _admin, agent, sender) = setup_contract(&env);
 
     env.mock_all_auths();
-    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &None);
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &default_corridor(&env), &None, &None, &None, &None);
 
     contract.start_processing(&remittance_id);
 
@@ -154,7 +161,7 @@ fn test_terminal_state_completed_cannot_transition() {
     let (contract, _token, _admin, agent, sender) = setup_contract(&env);
 
     env.mock_all_auths();
-    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &None);
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &default_corridor(&env), &None, &None, &None, &None);
 
     contract.start_processing(&remittance_id);
     contract.confirm_payout(&remittance_id);
@@ -170,7 +177,7 @@ fn test_terminal_state_cancelled_cannot_transition() {
     let (contract, _token, _admin, agent, sender) = setup_contract(&env);
 
     env.mock_all_auths();
-    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &None);
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &default_corridor(&env), &None, &None, &None, &None);
 
     contract.cancel_remittance(&remittance_id);
 
@@ -185,7 +192,7 @@ fn test_terminal_state_failed_cannot_transition() {
     let (contract, _token, _admin, agent, sender) = setup_contract(&env);
 
     env.mock_all_auths();
-    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &None);
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &default_corridor(&env), &None, &None, &None, &None);
 
     contract.start_processing(&remittance_id);
     contract.mark_failed(&remittance_id);
@@ -200,7 +207,7 @@ fn test_transition_events_logged() {
     let (contract, _token, _admin, agent, sender) = setup_contract(&env);
 
     env.mock_all_auths();
-    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &None);
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &default_corridor(&env), &None, &None, &None, &None);
 
     contract.start_processing(&remittance_id);
     contract.confirm_payout(&remittance_id);
@@ -217,7 +224,7 @@ fn test_failed_remittance_refunds_sender() {
 
     env.mock_all_auths();
     
-    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &None);
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &default_corridor(&env), &None, &None, &None, &None);
 
     contract.start_processing(&remittance_id);
     contract.mark_failed(&remittance_id);
@@ -233,8 +240,8 @@ fn test_multiple_remittances_independent_lifecycles() {
 
     env.mock_all_auths();
     
-    let remittance_id_1 = contract.create_remittance(&sender, &agent, &1000, &None);
-    let remittance_id_2 = contract.create_remittance(&sender, &agent, &2000, &None);
+    let remittance_id_1 = contract.create_remittance(&sender, &agent, &1000, &default_corridor(&env), &None, &None, &None, &None);
+    let remittance_id_2 = contract.create_remittance(&sender, &agent, &2000, &default_corridor(&env), &None, &None, &None, &None);
 
     // First remittance: Pending -> Processing -> Completed
     contract.start_processing(&remittance_id_1);
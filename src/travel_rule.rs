@@ -0,0 +1,180 @@
+//! Travel-rule compliance for the SwiftRemit contract.
+//!
+//! Transfers at or above a per-corridor threshold must carry a commitment
+//! hash of the originator/beneficiary payload exchanged between VASPs
+//! off-chain. The receiving agent acknowledges the exchange with its own
+//! hash before the payout can be confirmed. Only hashes are stored on-chain;
+//! the payloads themselves never leave the VASPs.
+
+use soroban_sdk::{contracttype, BytesN, Env, Vec};
+
+use crate::{ContractError, Corridor};
+
+/// Travel-rule exchange record for a single remittance.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TravelRuleRecord {
+    /// Remittance the exchange belongs to
+    pub remittance_id: u64,
+    /// Sender-side commitment hash of the travel-rule payload
+    pub originator_commitment: BytesN<32>,
+    /// Whether the agent has acknowledged the exchange
+    pub acknowledged: bool,
+    /// Agent-side acknowledgement hash, set once the beneficiary VASP confirms
+    /// receipt; all zeros until `acknowledged`
+    pub beneficiary_ack: BytesN<32>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub(crate) enum TravelRuleKey {
+    /// Minimum amount requiring travel-rule data, per corridor (persistent storage)
+    Threshold(Corridor),
+    /// Travel-rule record per remittance (persistent storage)
+    Record(u64),
+    /// Corridors with a threshold, in the order they were first configured
+    /// (instance storage)
+    Corridors,
+}
+
+/// Sets the travel-rule threshold for a corridor.
+pub fn set_travel_rule_threshold(env: &Env, corridor: &Corridor, threshold: i128) {
+    env.storage()
+        .persistent()
+        .set(&TravelRuleKey::Threshold(corridor.clone()), &threshold);
+
+    let mut corridors = get_threshold_corridors(env);
+    if !corridors.contains(corridor) {
+        corridors.push_back(corridor.clone());
+        env.storage()
+            .instance()
+            .set(&TravelRuleKey::Corridors, &corridors);
+    }
+}
+
+fn get_threshold_corridors(env: &Env) -> Vec<Corridor> {
+    env.storage()
+        .instance()
+        .get(&TravelRuleKey::Corridors)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Lists every configured threshold with its corridor, in the order the
+/// corridors were first configured.
+pub fn list_travel_rule_thresholds(env: &Env) -> Vec<(Corridor, i128)> {
+    let mut thresholds = Vec::new(env);
+    for corridor in get_threshold_corridors(env).iter() {
+        if let Some(threshold) = get_travel_rule_threshold(env, &corridor) {
+            thresholds.push_back((corridor, threshold));
+        }
+    }
+    thresholds
+}
+
+/// Gets the travel-rule threshold for a corridor, if one is configured.
+pub fn get_travel_rule_threshold(env: &Env, corridor: &Corridor) -> Option<i128> {
+    env.storage()
+        .persistent()
+        .get(&TravelRuleKey::Threshold(corridor.clone()))
+}
+
+/// Returns true if a transfer of `amount` in `corridor` requires travel-rule data.
+///
+/// Corridors without a configured threshold never require it.
+pub fn requires_travel_rule(env: &Env, corridor: &Corridor, amount: i128) -> bool {
+    match get_travel_rule_threshold(env, corridor) {
+        Some(threshold) => amount >= threshold,
+        None => false,
+    }
+}
+
+/// Gets the travel-rule record for a remittance.
+pub fn get_travel_rule_record(env: &Env, remittance_id: u64) -> Option<TravelRuleRecord> {
+    env.storage()
+        .persistent()
+        .get(&TravelRuleKey::Record(remittance_id))
+}
+
+/// Stores the travel-rule record for a remittance.
+pub fn set_travel_rule_record(env: &Env, record: &TravelRuleRecord) {
+    env.storage()
+        .persistent()
+        .set(&TravelRuleKey::Record(record.remittance_id), record);
+}
+
+/// Checks that a remittance carrying a travel-rule commitment has been
+/// acknowledged by the agent. Remittances without a record pass.
+pub fn validate_travel_rule_acknowledged(
+    env: &Env,
+    remittance_id: u64,
+) -> Result<(), ContractError> {
+    if let Some(record) = get_travel_rule_record(env, remittance_id) {
+        if !record.acknowledged {
+            return Err(ContractError::TravelRuleAckRequired);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::{Env, String};
+
+    fn corridor(env: &Env) -> Corridor {
+        Corridor {
+            currency: String::from_str(env, "MXN"),
+            country: String::from_str(env, "MX"),
+        }
+    }
+
+    #[test]
+    fn test_no_threshold_never_requires_travel_rule() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, crate::SwiftRemitContract);
+
+        env.as_contract(&contract_id, || {
+            assert!(!requires_travel_rule(&env, &corridor(&env), i128::MAX));
+        });
+    }
+
+    #[test]
+    fn test_threshold_is_inclusive() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, crate::SwiftRemitContract);
+
+        env.as_contract(&contract_id, || {
+            set_travel_rule_threshold(&env, &corridor(&env), 1000);
+
+            assert!(!requires_travel_rule(&env, &corridor(&env), 999));
+            assert!(requires_travel_rule(&env, &corridor(&env), 1000));
+        });
+    }
+
+    #[test]
+    fn test_ack_required_only_with_record() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, crate::SwiftRemitContract);
+
+        env.as_contract(&contract_id, || {
+            assert!(validate_travel_rule_acknowledged(&env, 1).is_ok());
+
+            let mut record = TravelRuleRecord {
+                remittance_id: 1,
+                originator_commitment: BytesN::from_array(&env, &[1u8; 32]),
+                acknowledged: false,
+                beneficiary_ack: BytesN::from_array(&env, &[0u8; 32]),
+            };
+            set_travel_rule_record(&env, &record);
+            assert_eq!(
+                validate_travel_rule_acknowledged(&env, 1),
+                Err(ContractError::TravelRuleAckRequired)
+            );
+
+            record.acknowledged = true;
+            record.beneficiary_ack = BytesN::from_array(&env, &[2u8; 32]);
+            set_travel_rule_record(&env, &record);
+            assert!(validate_travel_rule_acknowledged(&env, 1).is_ok());
+        });
+    }
+}
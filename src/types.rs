@@ -3,7 +3,9 @@
 //! This module defines the core data structures used throughout the contract,
 //! including remittance records and status enums.
 
-use soroban_sdk::{contracttype, Address};
+use soroban_sdk::{contracttype, Address, BytesN, String, Vec};
+
+use crate::NetTransfer;
 
 /// Status of a remittance transaction.
 ///
@@ -11,6 +13,7 @@ use soroban_sdk::{contracttype, Address};
 /// - `Pending`: Initial state after creation, awaiting agent confirmation
 /// - `Completed`: Agent has confirmed payout and received funds
 /// - `Cancelled`: Sender has cancelled and received refund
+/// - `Finalized`: Admin has finalized a completed remittance
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum RemittanceStatus {
@@ -20,6 +23,8 @@ pub enum RemittanceStatus {
     Completed,
     /// Remittance has been cancelled and refunded to sender
     Cancelled,
+    /// Completed remittance has been finalized by an admin
+    Finalized,
 }
 
 /// A remittance transaction record.
@@ -39,10 +44,63 @@ pub struct Remittance {
     pub amount: i128,
     /// Platform fee deducted from the amount (in USDC)
     pub fee: i128,
+    /// Integrator fee deducted from the amount (in USDC)
+    pub integrator_fee: i128,
     /// Current status of the remittance
     pub status: RemittanceStatus,
     /// Optional expiry timestamp (seconds since epoch) for settlement
     pub expiry: Option<u64>,
+    /// Destination corridor the payout is made in
+    pub corridor: Corridor,
+}
+
+/// Portable proof that a remittance was settled.
+///
+/// Produced when a remittance settles and stored for `get_receipt`. The
+/// receipt's canonical encoding and hash are defined in `hashing.rs`, so an
+/// archived receipt can be checked against `get_receipt_hash` or the
+/// `settle.receipt` event.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementReceipt {
+    /// Canonical settlement ID
+    pub settlement_id: BytesN<32>,
+    /// Hash schema version `settlement_id` was computed with
+    pub settlement_schema_version: u32,
+    /// ID of the settled remittance
+    pub remittance_id: u64,
+    /// Address of the sender
+    pub sender: Address,
+    /// Address of the agent paid out
+    pub agent: Address,
+    /// Total amount sent by the sender
+    pub amount: i128,
+    /// Platform fee deducted from the amount
+    pub fee: i128,
+    /// Amount paid out to the agent
+    pub payout_amount: i128,
+    /// Expiry timestamp of the remittance, if any
+    pub expiry: Option<u64>,
+    /// Corridor the payout was made in
+    pub corridor: Corridor,
+    /// Remittance status at settlement
+    pub status: RemittanceStatus,
+    /// Ledger sequence the remittance settled in
+    pub ledger_sequence: u32,
+    /// Ledger timestamp the remittance settled at
+    pub timestamp: u64,
+}
+
+/// A payout corridor: the destination currency and country of a remittance.
+///
+/// Uses the same normalized (uppercase) codes as daily send limits.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Corridor {
+    /// Destination currency code (e.g., "MXN")
+    pub currency: String,
+    /// Destination country code (e.g., "MX")
+    pub country: String,
 }
 
 /// Entry for batch settlement processing.
@@ -54,6 +112,16 @@ pub struct BatchSettlementEntry {
     pub remittance_id: u64,
 }
 
+/// A batch entry that was excluded from a best-effort settlement.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SkippedSettlement {
+    /// The ID of the remittance that was not settled
+    pub remittance_id: u64,
+    /// Code of the `ContractError` that excluded it
+    pub code: u32,
+}
+
 /// Result of a batch settlement operation.
 /// Contains the IDs of successfully settled remittances.
 #[contracttype]
@@ -61,6 +129,32 @@ pub struct BatchSettlementEntry {
 pub struct BatchSettlementResult {
     /// List of successfully settled remittance IDs
     pub settled_ids: Vec<u64>,
+    /// Entries excluded from the batch, in entry order (best-effort mode only)
+    pub skipped: Vec<SkippedSettlement>,
+    /// ID under which the batch's Merkle commitment is stored, or `None`
+    /// if nothing was settled
+    pub batch_id: Option<u64>,
+}
+
+/// Dry-run result of a batch settlement.
+/// Predicts the netting outcome without executing state changes.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchSettlementPreview {
+    /// Net transfers the batch would produce
+    pub transfers: Vec<NetTransfer>,
+    /// Payout transfers needed without netting (one per valid entry)
+    pub gross_transfer_count: u32,
+    /// Payout transfers needed after netting
+    pub net_transfer_count: u32,
+    /// Tokens paid out without netting (sum of amount minus fees)
+    pub gross_volume: i128,
+    /// Tokens paid out after netting
+    pub net_volume: i128,
+    /// Platform fees the batch would collect
+    pub total_fees: i128,
+    /// Entries that would be excluded, with the error that excludes each one
+    pub skipped: Vec<SkippedSettlement>,
 }
 
 /// Result of a settlement simulation.
@@ -70,7 +164,7 @@ pub struct BatchSettlementResult {
 pub struct SettlementSimulation {
     /// Whether the settlement would succeed
     pub would_succeed: bool,
-    /// The payout amount the agent would receive (amount - fee)
+    /// The payout amount the agent would receive (amount minus fees)
     pub payout_amount: i128,
     /// The platform fee that would be collected
     pub fee: i128,
@@ -91,4 +185,24 @@ pub struct DailyLimit {
 pub struct TransferRecord {
     pub timestamp: u64,
     pub amount: i128,
+    /// Corridor whose daily limit the transfer counts against
+    pub corridor: Corridor,
+}
+
+/// Stored outcome of a `create_remittance` call made with an idempotency key.
+///
+/// Records live in temporary storage keyed by `(sender, key)` and are
+/// consulted on retries to return the original remittance ID instead of
+/// debiting the sender a second time.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IdempotencyRecord {
+    /// The client-provided idempotency key
+    pub key: String,
+    /// SHA-256 hash of the original request parameters
+    pub request_hash: BytesN<32>,
+    /// The remittance ID returned by the original request
+    pub remittance_id: u64,
+    /// Ledger timestamp after which the key may be reused
+    pub expires_at: u64,
 }
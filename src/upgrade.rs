@@ -0,0 +1,242 @@
+//! In-place code upgrades and storage schema versioning.
+//!
+//! Upgrading replaces the contract's WASM while keeping its address and
+//! storage, so integrators do not have to switch contracts. Storage written
+//! by older code may use older layouts; the storage schema version records
+//! which layouts the stored data uses.
+//!
+//! ## Schema Versions
+//!
+//! | Version | Change                                                        |
+//! |---------|---------------------------------------------------------------|
+//! | 1       | Original layout; also assumed when no version is stored       |
+//! | 2       | `Remittance` gains `corridor`                                 |
+//! | 3       | `Remittance` gains `integrator_fee`                           |
+//!
+//! Older layouts are converted lazily, since `get_remittance` decodes
+//! any of them, and eagerly by `migrate_storage`. That rewrites the
+//! remittances in batches and raises the stored version once every
+//! remittance uses the current layout. Remittances created before version 2
+//! have no corridor and are given the empty legacy corridor; remittances
+//! created before version 3 were charged no integrator fee.
+//!
+//! Settlement duplicate-detection flags stored before settlement IDs are
+//! left as they are; `get_settlement_id` already treats them as settled
+//! without an ID. Agents registered before registries existed are added to
+//! the agent registry as `migrate_storage` rewrites their remittances; an
+//! agent without remittances is added by registering it again.
+
+use soroban_sdk::{
+    contracttype, symbol_short, Address, BytesN, Env, Map, String, Symbol, TryFromVal, Val,
+};
+
+use crate::{
+    backfill_agent_registry, emit_contract_upgraded, emit_storage_migrated,
+    emit_storage_rewritten, get_remittance, get_remittance_counter, set_remittance, ContractError,
+    Corridor, Remittance, RemittanceStatus,
+};
+
+/// Storage schema version written by this code.
+pub const STORAGE_SCHEMA_VERSION: u32 = 3;
+
+/// Maximum number of remittances rewritten by one `migrate_storage` call.
+pub const MAX_STORAGE_MIGRATION_BATCH: u32 = 100;
+
+/// Code and storage versions of a deployed contract.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractVersion {
+    /// Version of the running code
+    pub code_version: String,
+    /// Storage schema version of the stored data
+    pub storage_schema_version: u32,
+    /// Storage schema version the running code writes
+    pub latest_storage_schema_version: u32,
+}
+
+/// Progress of a storage migration.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StorageMigrationStatus {
+    /// Storage schema version of the stored data
+    pub schema_version: u32,
+    /// Storage schema version being migrated to
+    pub target_version: u32,
+    /// Next remittance ID to rewrite
+    pub next_remittance_id: u64,
+    /// Highest remittance ID to rewrite
+    pub remittance_counter: u64,
+}
+
+/// `Remittance` layout of storage schema version 1.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct RemittanceV1 {
+    pub id: u64,
+    pub sender: Address,
+    pub agent: Address,
+    pub amount: i128,
+    pub fee: i128,
+    pub status: RemittanceStatus,
+    pub expiry: Option<u64>,
+}
+
+/// `Remittance` layout of storage schema version 2.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct RemittanceV2 {
+    pub id: u64,
+    pub sender: Address,
+    pub agent: Address,
+    pub amount: i128,
+    pub fee: i128,
+    pub status: RemittanceStatus,
+    pub expiry: Option<u64>,
+    pub corridor: Corridor,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub(crate) enum UpgradeKey {
+    /// Storage schema version of the stored data (instance storage)
+    SchemaVersion,
+    /// Next remittance ID to rewrite during a migration (instance storage)
+    MigrationCursor,
+}
+
+/// Returns the storage schema version of the stored data.
+pub fn get_storage_schema_version(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&UpgradeKey::SchemaVersion)
+        .unwrap_or(1)
+}
+
+/// Sets the storage schema version of the stored data.
+pub fn set_storage_schema_version(env: &Env, version: u32) {
+    env.storage()
+        .instance()
+        .set(&UpgradeKey::SchemaVersion, &version);
+}
+
+/// Returns the code and storage versions.
+pub fn get_contract_version(env: &Env) -> ContractVersion {
+    ContractVersion {
+        code_version: String::from_str(env, env!("CARGO_PKG_VERSION")),
+        storage_schema_version: get_storage_schema_version(env),
+        latest_storage_schema_version: STORAGE_SCHEMA_VERSION,
+    }
+}
+
+/// Corridor given to remittances created before corridors were stored.
+pub fn legacy_corridor(env: &Env) -> Corridor {
+    Corridor {
+        currency: String::from_str(env, ""),
+        country: String::from_str(env, ""),
+    }
+}
+
+/// Decodes a stored remittance in the current, version 2 or version 1 layout.
+pub(crate) fn decode_remittance(env: &Env, raw: &Val) -> Option<Remittance> {
+    let fields = Map::<Symbol, Val>::try_from_val(env, raw).ok()?;
+    if fields.contains_key(Symbol::new(env, "integrator_fee")) {
+        return Remittance::try_from_val(env, raw).ok();
+    }
+
+    if fields.contains_key(symbol_short!("corridor")) {
+        let legacy = RemittanceV2::try_from_val(env, raw).ok()?;
+        return Some(Remittance {
+            id: legacy.id,
+            sender: legacy.sender,
+            agent: legacy.agent,
+            amount: legacy.amount,
+            fee: legacy.fee,
+            integrator_fee: 0,
+            status: legacy.status,
+            expiry: legacy.expiry,
+            corridor: legacy.corridor,
+        });
+    }
+
+    let legacy = RemittanceV1::try_from_val(env, raw).ok()?;
+    Some(Remittance {
+        id: legacy.id,
+        sender: legacy.sender,
+        agent: legacy.agent,
+        amount: legacy.amount,
+        fee: legacy.fee,
+        integrator_fee: 0,
+        status: legacy.status,
+        expiry: legacy.expiry,
+        corridor: legacy_corridor(env),
+    })
+}
+
+/// Replaces the contract's code, keeping its address and storage.
+pub fn upgrade_contract(env: &Env, caller: &Address, new_wasm_hash: &BytesN<32>) {
+    env.deployer()
+        .update_current_contract_wasm(new_wasm_hash.clone());
+    emit_contract_upgraded(env, caller.clone(), new_wasm_hash.clone());
+}
+
+/// Rewrites up to `limit` remittances in the current layout, continuing
+/// where the previous call stopped, and raises the stored schema version
+/// once all remittances are rewritten.
+///
+/// # Errors
+///
+/// * `UnsupportedMigrationVersion` - The stored data is newer than this code
+pub fn migrate_storage(
+    env: &Env,
+    caller: &Address,
+    limit: u32,
+) -> Result<StorageMigrationStatus, ContractError> {
+    let from_version = get_storage_schema_version(env);
+    if from_version > STORAGE_SCHEMA_VERSION {
+        return Err(ContractError::UnsupportedMigrationVersion);
+    }
+
+    let counter = get_remittance_counter(env)?;
+    if from_version == STORAGE_SCHEMA_VERSION {
+        return Ok(StorageMigrationStatus {
+            schema_version: from_version,
+            target_version: STORAGE_SCHEMA_VERSION,
+            next_remittance_id: counter + 1,
+            remittance_counter: counter,
+        });
+    }
+
+    let start: u64 = env
+        .storage()
+        .instance()
+        .get(&UpgradeKey::MigrationCursor)
+        .unwrap_or(1);
+    let next = (start + limit.min(MAX_STORAGE_MIGRATION_BATCH) as u64).min(counter + 1);
+
+    for id in start..next {
+        if let Ok(remittance) = get_remittance(env, id) {
+            set_remittance(env, id, &remittance);
+            backfill_agent_registry(env, &remittance.agent);
+        }
+    }
+    if next > start {
+        emit_storage_rewritten(env, caller.clone(), start, next);
+    }
+
+    let schema_version = if next > counter {
+        env.storage().instance().remove(&UpgradeKey::MigrationCursor);
+        set_storage_schema_version(env, STORAGE_SCHEMA_VERSION);
+        emit_storage_migrated(env, caller.clone(), from_version, STORAGE_SCHEMA_VERSION);
+        STORAGE_SCHEMA_VERSION
+    } else {
+        env.storage().instance().set(&UpgradeKey::MigrationCursor, &next);
+        from_version
+    };
+
+    Ok(StorageMigrationStatus {
+        schema_version,
+        target_version: STORAGE_SCHEMA_VERSION,
+        next_remittance_id: next,
+        remittance_counter: counter,
+    })
+}
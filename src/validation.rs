@@ -2,15 +2,15 @@
 //!
 //! This module provides validation functions for Stellar addresses used in
 //! contract operations.
+//!
+//! Centralized validation module for all API requests.
+//! Validates required fields before controller logic to prevent invalid data
+//! from reaching business logic.
 
-use soroban_sdk::Address;
+use soroban_sdk::{Address, Env};
 
 use crate::{ContractError, is_agent_registered, is_paused, get_remittance, RemittanceStatus};
 
-/// Centralized validation module for all API requests.
-/// Validates required fields before controller logic to prevent invalid data
-/// from reaching business logic.
-
 /// Validates that an address is properly formatted and not empty.
 ///
 /// Stellar addresses in Soroban are represented by the Address type,
@@ -39,6 +39,7 @@ pub fn validate_address(address: &Address) -> Result<(), ContractError> {
     // In Soroban, the Address type is guaranteed to be valid by the SDK,
     // so this function primarily serves as a placeholder for future validation logic
     // and to make the code more explicit about validation requirements.
+    let _ = address;
 
     Ok(())
 }
@@ -146,6 +147,7 @@ pub fn validate_create_remittance_request(
     agent: &Address,
     amount: i128,
 ) -> Result<(), ContractError> {
+    crate::validate_no_migration_session(env)?;
     validate_address(sender)?;
     validate_address(agent)?;
     validate_amount(amount)?;
@@ -153,17 +155,50 @@ pub fn validate_create_remittance_request(
     Ok(())
 }
 
+/// Maximum length in bytes of a client-supplied idempotency key.
+pub const MAX_IDEMPOTENCY_KEY_LEN: u32 = 255;
+
+/// Validates that an idempotency key is non-empty and within the length limit.
+pub fn validate_idempotency_key(key: &soroban_sdk::String) -> Result<(), ContractError> {
+    if key.is_empty() || key.len() > MAX_IDEMPOTENCY_KEY_LEN {
+        return Err(ContractError::InvalidIdempotencyKey);
+    }
+    Ok(())
+}
+
 /// Comprehensive validation for confirm_payout request.
 pub fn validate_confirm_payout_request(
     env: &Env,
     remittance_id: u64,
 ) -> Result<crate::Remittance, ContractError> {
     validate_not_paused(env)?;
+    crate::validate_no_migration_session(env)?;
     let remittance = validate_remittance_exists(env, remittance_id)?;
     validate_remittance_pending(&remittance)?;
     validate_no_duplicate_settlement(env, remittance_id)?;
     validate_settlement_not_expired(env, remittance.expiry)?;
     validate_address(&remittance.agent)?;
+    crate::validate_not_queued(env, remittance_id)?;
+    crate::validate_not_batched(env, remittance_id)?;
+    Ok(remittance)
+}
+
+/// Comprehensive validation for a single batch settlement entry.
+///
+/// Applies the same checks as `confirm_payout`, including travel-rule
+/// acknowledgement, except the paused check which is done once per batch.
+pub fn validate_batch_settlement_entry(
+    env: &Env,
+    remittance_id: u64,
+) -> Result<crate::Remittance, ContractError> {
+    let remittance = validate_remittance_exists(env, remittance_id)?;
+    validate_remittance_pending(&remittance)?;
+    validate_no_duplicate_settlement(env, remittance_id)?;
+    validate_settlement_not_expired(env, remittance.expiry)?;
+    validate_address(&remittance.agent)?;
+    crate::validate_travel_rule_acknowledged(env, remittance_id)?;
+    crate::validate_not_queued(env, remittance_id)?;
+    crate::validate_not_batched(env, remittance_id)?;
     Ok(remittance)
 }
 
@@ -172,9 +207,12 @@ pub fn validate_cancel_remittance_request(
     env: &Env,
     remittance_id: u64,
 ) -> Result<crate::Remittance, ContractError> {
+    crate::validate_no_migration_session(env)?;
     let remittance = validate_remittance_exists(env, remittance_id)?;
     validate_remittance_pending(&remittance)?;
     validate_address(&remittance.sender)?;
+    crate::validate_not_queued(env, remittance_id)?;
+    crate::validate_not_batched(env, remittance_id)?;
     Ok(remittance)
 }
 
@@ -183,6 +221,7 @@ pub fn validate_withdraw_fees_request(
     env: &Env,
     to: &Address,
 ) -> Result<i128, ContractError> {
+    crate::validate_no_migration_session(env)?;
     validate_address(to)?;
     let fees = crate::get_accumulated_fees(env)?;
     validate_fees_available(fees)?;
@@ -195,6 +234,9 @@ pub fn validate_update_fee_request(fee_bps: u32) -> Result<(), ContractError> {
 }
 
 /// Comprehensive validation for admin operations.
+///
+/// Before `initialize` there is no admin yet, so the deployer only has to
+/// authorize the call; this lets it whitelist the token it initializes with.
 pub fn validate_admin_operation(
     env: &Env,
     caller: &Address,
@@ -202,20 +244,36 @@ pub fn validate_admin_operation(
 ) -> Result<(), ContractError> {
     validate_address(caller)?;
     validate_address(target)?;
+    if !crate::has_admin(env) {
+        caller.require_auth();
+        return Ok(());
+    }
     crate::require_admin(env, caller)?;
     Ok(())
 }
 
+/// Longest symbol `normalize_symbol` converts; longer symbols are kept as given.
+pub const MAX_SYMBOL_LEN: usize = 32;
+
 /// Normalizes an asset symbol to uppercase canonical form.
 pub fn normalize_symbol(env: &Env, symbol: &soroban_sdk::String) -> soroban_sdk::String {
     let len = symbol.len() as usize;
-    let mut bytes = soroban_sdk::Bytes::new(env);
-    for i in 0..len {
-        let b = symbol.get(i as u32).unwrap();
-        let upper = if b >= b'a' && b <= b'z' { b - 32 } else { b };
-        bytes.push_back(upper);
+    if len > MAX_SYMBOL_LEN {
+        return symbol.clone();
+    }
+
+    let mut buf = [0u8; MAX_SYMBOL_LEN];
+    symbol.copy_into_slice(&mut buf[..len]);
+    buf[..len].make_ascii_uppercase();
+    soroban_sdk::String::from_bytes(env, &buf[..len])
+}
+
+/// Normalizes both codes of a corridor to uppercase canonical form.
+pub fn normalize_corridor(env: &Env, corridor: &crate::Corridor) -> crate::Corridor {
+    crate::Corridor {
+        currency: normalize_symbol(env, &corridor.currency),
+        country: normalize_symbol(env, &corridor.country),
     }
-    soroban_sdk::String::from_bytes(env, &bytes)
 }
 
 #[cfg(test)]
@@ -258,6 +316,16 @@ mod tests {
         assert_eq!(validate_amount(-1000), Err(ContractError::InvalidAmount));
     }
 
+    #[test]
+    fn test_validate_idempotency_key() {
+        let env = Env::default();
+        let empty = soroban_sdk::String::from_str(&env, "");
+        let valid = soroban_sdk::String::from_str(&env, "order-42_retry");
+
+        assert!(validate_idempotency_key(&valid).is_ok());
+        assert_eq!(validate_idempotency_key(&empty), Err(ContractError::InvalidIdempotencyKey));
+    }
+
     #[test]
     fn test_validate_fees_available_valid() {
         assert!(validate_fees_available(1).is_ok());